@@ -0,0 +1,45 @@
+//! Benchmarks for the hot paths of the reactive graph. Run with `cargo bench`.
+use bevy_rx::prelude::*;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+/// The number of memos chained after the signal in the alternating benchmark.
+const CHAIN_DEPTH: usize = 10;
+
+fn send_unchanged(c: &mut Criterion) {
+    let mut reactor = ReactiveContext::<()>::default();
+    let signal = reactor.new_signal(1);
+    reactor.new_memo((signal,), |(n,)| n * 2);
+
+    c.bench_function("send unchanged signal", |b| {
+        b.iter(|| reactor.send_signal(signal, black_box(1)))
+    });
+}
+
+fn send_alternating(c: &mut Criterion) {
+    let mut reactor = ReactiveContext::<()>::default();
+    let signal = reactor.new_signal(0);
+    let mut last = reactor.new_memo((signal,), |(n,)| n + 1);
+    for _ in 1..CHAIN_DEPTH {
+        last = reactor.new_memo((last,), |(n,)| n + 1);
+    }
+
+    let mut value = 0;
+    c.bench_function("send alternating signal through memo chain", |b| {
+        b.iter(|| {
+            value = 1 - value;
+            reactor.send_signal(signal, black_box(value));
+        })
+    });
+    assert_eq!(*reactor.read(last), value + CHAIN_DEPTH as i32);
+}
+
+fn read_memo(c: &mut Criterion) {
+    let mut reactor = ReactiveContext::<()>::default();
+    let signal = reactor.new_signal(1);
+    let memo = reactor.new_memo((signal,), |(n,)| n * 2);
+
+    c.bench_function("read memo", |b| b.iter(|| *reactor.read(black_box(memo))));
+}
+
+criterion_group!(benches, send_unchanged, send_alternating, read_memo);
+criterion_main!(benches);
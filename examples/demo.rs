@@ -1,4 +1,4 @@
-use std::time::Instant;
+use bevy_utils::Instant;
 
 use bevy_app::{prelude::*, ScheduleRunnerPlugin};
 use bevy_ecs::prelude::*;
@@ -58,13 +58,16 @@ fn update(mut reactor: Reactor, buttons: Query<&Button>, lock: Query<&Lock>) {
     assert!(reactor.read(lock1.unlocked));
 
     let start = Instant::now();
+    let mut changes = 0;
     for _ in 0..1_000_000 {
-        reactor.send_signal(button1.active, true); // diffing prevents triggering a recompute
+        // diffing prevents triggering a recompute
+        changes += reactor.send_signal(button1.active, true) as usize;
     }
     println!(
         "Sending 1,000,000 signals with same value = {:#?}/iter",
         start.elapsed() / 1_000_000
     );
+    assert_eq!(changes, 0);
 
     let start = Instant::now();
     for i in 1..=1_000_000 {
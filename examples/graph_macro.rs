@@ -0,0 +1,23 @@
+//! Using `rx!` to declare a whole graph at once. The inputs of each memo are found from the nodes
+//! its expression names, or listed before `=>` when they can't be found.
+use bevy_rx::prelude::*;
+
+fn main() {
+    let mut reactor = ReactiveContext::<()>::default();
+
+    let bonus = 5;
+    rx! {
+        reactor;
+        let base = signal(10);
+        let multiplier = signal(2);
+        let damage = memo(base * multiplier + bonus);
+        let label = lazy_memo(damage => format!("{damage} damage"));
+    }
+    assert_eq!(*reactor.read(damage), 25);
+    assert_eq!(reactor.read(label), "25 damage");
+
+    reactor.send_signal(multiplier, 3);
+    assert_eq!(*reactor.read(damage), 35);
+    assert_eq!(reactor.read(label), "35 damage");
+    println!("{}", reactor.read(label));
+}
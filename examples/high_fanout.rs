@@ -0,0 +1,37 @@
+//! Compares sending to a signal with a very large number of subscribers, when its subscribers are
+//! stored in the default `Vec` until there are enough to index them, and when they are indexed from
+//! the start with `new_signal_high_fanout`.
+use std::time::{Duration, Instant};
+
+use bevy_rx::prelude::*;
+
+const SENDS: u32 = 20;
+
+fn time_sends(
+    subscribers: usize,
+    new_signal: impl Fn(&mut ReactiveContext<()>, u32) -> Signal<u32>,
+) -> Duration {
+    let mut reactor = ReactiveContext::<()>::default();
+    let signal = new_signal(&mut reactor, 0);
+    for i in 0..subscribers as u32 {
+        reactor.new_memo((signal,), move |(n,)| n + i);
+    }
+
+    let start = Instant::now();
+    for i in 1..=SENDS {
+        reactor.send_signal(signal, i);
+    }
+    start.elapsed() / SENDS
+}
+
+fn main() {
+    for subscribers in [10, 1_000, 10_000, 50_000] {
+        let vec = time_sends(subscribers, |rctx, value| rctx.new_signal(value));
+        let indexed = time_sends(subscribers, |rctx, value| {
+            rctx.new_signal_high_fanout(value)
+        });
+        println!(
+            "{subscribers:>6} subscribers: vec {vec:>10.2?}, indexed {indexed:>10.2?} per send"
+        );
+    }
+}
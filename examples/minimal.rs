@@ -1,6 +1,7 @@
 use bevy_ecs::system::Res;
 use bevy_rx::effect::EffectData;
 
+#[allow(dead_code)] // Only used to name the context's state type.
 struct MyState(usize);
 
 fn main() {
@@ -19,7 +20,8 @@ fn main() {
     // We can also define the calculation as a function
     let welcome = reactor.new_memo((full_name, age), welcome_message);
 
-    let effect = reactor.new_deferred_effect(welcome, print_welcome);
+    // Effects run a system when an observable changes.
+    let effect = welcome.effect(&mut reactor, print_welcome);
 
     dbg!(reactor.effect_system(effect).unwrap().name());
 
@@ -0,0 +1,34 @@
+//! Using `#[derive(MemoQuery)]` to pass a struct of observables as the inputs of a memo, so the
+//! calculation accesses them by name instead of by position.
+use bevy_rx::prelude::*;
+
+#[derive(Clone, Copy, MemoQuery)]
+struct HudInputs {
+    player_health: Signal<u32>,
+    max_health: Signal<u32>,
+    enemy_count: Memo<usize>,
+}
+
+fn main() {
+    let mut reactor = ReactiveContext::<()>::default();
+
+    let enemies = reactor.new_signal(vec!["slime", "bat"]);
+    let inputs = HudInputs {
+        player_health: reactor.new_signal(80),
+        max_health: reactor.new_signal(100),
+        enemy_count: reactor.new_memo((enemies,), |(enemies,)| enemies.len()),
+    };
+
+    let hud = reactor.new_memo(inputs, |inputs: HudInputsQuery| {
+        format!(
+            "{}/{} HP, {} enemies",
+            inputs.player_health, inputs.max_health, inputs.enemy_count
+        )
+    });
+    assert_eq!(reactor.read(hud), "80/100 HP, 2 enemies");
+
+    reactor.send_signal(enemies, vec!["slime"]);
+    reactor.send_signal(inputs.player_health, 95);
+    assert_eq!(reactor.read(hud), "95/100 HP, 1 enemies");
+    println!("{}", reactor.read(hud));
+}
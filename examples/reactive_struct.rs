@@ -0,0 +1,30 @@
+//! Using `#[derive(Reactive)]` to store every field of a struct in its own signal.
+use bevy_rx::prelude::*;
+
+#[derive(Reactive)]
+struct Player {
+    name: String,
+    health: u32,
+}
+
+fn main() {
+    let mut reactor = ReactiveContext::<()>::default();
+
+    let player = Player {
+        name: "Jane".to_string(),
+        health: 100,
+    }
+    .into_reactive(&mut reactor);
+
+    // The generated struct holds a signal per field, which can be used like any other signal.
+    let status = reactor.new_memo((player.name, player.health), |(name, health)| {
+        format!("{name}: {health} HP")
+    });
+
+    player.set_health(&mut reactor, 80);
+    assert_eq!(*player.health(&mut reactor), 80);
+    println!("{}", reactor.read(status));
+
+    let snapshot = player.get(&mut reactor);
+    println!("{} has {} health", snapshot.name, snapshot.health);
+}
@@ -0,0 +1,45 @@
+//! Counts heap allocations while repeatedly sending alternating values to a signal, to keep the
+//! hot path of `send_signal` allocation free.
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use bevy_rx::prelude::*;
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn main() {
+    const SENDS: usize = 100_000;
+
+    let mut reactor = ReactiveContext::<()>::default();
+    let signal = reactor.new_signal(0);
+    let memo = reactor.new_memo((signal,), |(n,)| n * 2);
+    reactor.new_memo((memo,), |(n,)| n + 1);
+
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    for i in 0..SENDS {
+        reactor.send_signal(signal, i % 2);
+    }
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+    println!(
+        "{allocations} allocations for {SENDS} sends ({:.3} per send)",
+        allocations as f64 / SENDS as f64
+    );
+}
@@ -0,0 +1,20 @@
+//! Awaiting a signal from an async task, e.g. the result of a confirmation dialog.
+use bevy_rx::prelude::*;
+use bevy_tasks::block_on;
+
+fn main() {
+    let mut reactor = ReactiveContext::<()>::default();
+    let button_pressed = reactor.new_signal(false);
+
+    let pressed = button_pressed.wait_for(&mut reactor, |pressed| *pressed);
+    let task = std::thread::spawn(move || {
+        block_on(async move {
+            pressed.await;
+            "Saving..."
+        })
+    });
+
+    // The task resumes once the button is pressed, e.g. by an input system on a later frame.
+    button_pressed.send(&mut reactor, true);
+    println!("{}", task.join().unwrap());
+}
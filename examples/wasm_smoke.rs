@@ -0,0 +1,30 @@
+//! Builds a small reactive graph, sends signals and flushes effects without relying on timing, to
+//! check that the reactive core works when targeting `wasm32-unknown-unknown`.
+use bevy_ecs::prelude::*;
+use bevy_rx::{effect::EffectData, ReactiveContext};
+
+#[derive(Resource, Default)]
+struct LastArea(Option<i32>);
+
+fn main() {
+    let mut world = World::new();
+    world.init_resource::<LastArea>();
+    let mut reactor = ReactiveContext::<World>::default();
+
+    let width = reactor.new_signal(2);
+    let height = reactor.new_signal(3);
+    let area = reactor.new_memo((width, height), |(w, h)| w * h);
+    reactor.new_deferred_effect(area, record_area);
+    assert_eq!(*reactor.read(area), 6);
+
+    reactor.send_signal(width, 4);
+    assert_eq!(*reactor.read(area), 12);
+
+    reactor.flush_effects(&mut world);
+    assert_eq!(world.resource::<LastArea>().0, Some(12));
+    println!("Area: {}", reactor.read(area));
+}
+
+fn record_area(area: Res<EffectData<i32>>, mut last: ResMut<LastArea>) {
+    last.0 = Some(**area);
+}
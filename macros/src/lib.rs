@@ -0,0 +1,460 @@
+use proc_macro::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    parenthesized,
+    parse::{Parse, ParseStream},
+    parse_macro_input,
+    punctuated::Punctuated,
+    token::Comma,
+    visit::{self, Visit},
+    Arm, Block, Data, DeriveInput, Expr, ExprClosure, ExprForLoop, ExprIf, ExprPath, ExprWhile,
+    Field, Fields, Ident, Local, Pat, PatIdent, Stmt, Token,
+};
+
+/// Generates a signal-backed version of a plain struct, named `Reactive{StructName}`.
+///
+/// Every field of type `T` becomes a public `Signal<T>` field on the generated struct, which has:
+/// - `new(rctx, value)` to create the signals from a plain value, also available as
+///   `value.into_reactive(rctx)`,
+/// - an accessor method per field that reads its signal, and a `set_{field}` method that sends it
+///   and returns whether it changed,
+/// - `get(rctx)` to read every field back into a plain value.
+///
+/// Field types must be `Clone + PartialEq + Send + Sync + 'static`, like any signal.
+#[proc_macro_derive(Reactive)]
+pub fn derive_reactive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let vis = &input.vis;
+    let name = &input.ident;
+    let reactive_name = format_ident!("Reactive{}", name);
+
+    let fields = match named_fields(&input, "Reactive") {
+        Ok(fields) => fields,
+        Err(error) => return error.to_compile_error().into(),
+    };
+
+    let field_vis = fields.iter().map(|field| &field.vis);
+    let field_names: Vec<_> = fields.iter().map(|field| &field.ident).collect();
+    let field_types: Vec<_> = fields.iter().map(|field| &field.ty).collect();
+    let setters = field_names
+        .iter()
+        .map(|field| format_ident!("set_{}", field.as_ref().unwrap()));
+    let doc = format!("A reactive version of [`{name}`], with every field stored in a signal.");
+
+    quote! {
+        #[doc = #doc]
+        #[derive(Clone, Copy)]
+        #vis struct #reactive_name {
+            #(#field_vis #field_names: ::bevy_rx::signal::Signal<#field_types>,)*
+        }
+
+        impl #reactive_name {
+            /// Create a signal for every field, initialized from `value`.
+            pub fn new<S>(rctx: &mut ::bevy_rx::ReactiveContext<S>, value: #name) -> Self {
+                Self {
+                    #(#field_names: rctx.new_signal(value.#field_names),)*
+                }
+            }
+
+            /// Read the current value of every field.
+            pub fn get<S>(&self, rctx: &mut ::bevy_rx::ReactiveContext<S>) -> #name {
+                #name {
+                    #(#field_names: ::core::clone::Clone::clone(rctx.read(self.#field_names)),)*
+                }
+            }
+
+            #(
+                pub fn #field_names<'r, S>(
+                    &self,
+                    rctx: &'r mut ::bevy_rx::ReactiveContext<S>,
+                ) -> &'r #field_types {
+                    rctx.read(self.#field_names)
+                }
+
+                pub fn #setters<S>(
+                    &self,
+                    rctx: &mut ::bevy_rx::ReactiveContext<S>,
+                    value: #field_types,
+                ) -> bool {
+                    rctx.send_signal(self.#field_names, value)
+                }
+            )*
+        }
+
+        impl #name {
+            /// Create a signal for every field, initialized from this value.
+            pub fn into_reactive<S>(
+                self,
+                rctx: &mut ::bevy_rx::ReactiveContext<S>,
+            ) -> #reactive_name {
+                #reactive_name::new(rctx, self)
+            }
+        }
+    }
+    .into()
+}
+
+/// Implements `MemoQuery` for a struct of observables, so it can be used as the inputs of a memo
+/// instead of a tuple. The memo's calculation receives a generated `{StructName}Query` struct, with
+/// a field holding a reference to the value of each observable, so inputs are accessed by name.
+///
+/// The struct must also derive `Clone` and `Copy`, and have at least one field. Every field must be
+/// an observable, like a `Signal` or `Memo`.
+#[proc_macro_derive(MemoQuery)]
+pub fn derive_memo_query(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let vis = &input.vis;
+    let name = &input.ident;
+    let query_name = format_ident!("{}Query", name);
+
+    let fields = match named_fields(&input, "MemoQuery") {
+        Ok(fields) => fields,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    if fields.is_empty() {
+        return syn::Error::new_spanned(
+            name,
+            "`MemoQuery` requires at least one field, a memo without inputs is never recomputed",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    let field_names: Vec<_> = fields.iter().map(|field| &field.ident).collect();
+    let field_types: Vec<_> = fields.iter().map(|field| &field.ty).collect();
+    let doc = format!("The values of the observables in [`{name}`], read by a memo.");
+
+    quote! {
+        #[doc = #doc]
+        #vis struct #query_name<'a> {
+            #(pub #field_names: &'a <#field_types as ::bevy_rx::observable::Observable>::DataType,)*
+        }
+
+        impl<T> ::bevy_rx::memo::MemoQuery<T> for #name {
+            type Query<'a> = #query_name<'a>;
+
+            fn read_and_derive(
+                world: &mut ::bevy_rx::__private::World,
+                reader: ::bevy_rx::__private::Entity,
+                derive_fn: impl Fn(Self::Query<'_>) -> T,
+                input_deps: Self,
+            ) -> Option<T> {
+                #(
+                    if !::bevy_rx::__private::subscribe(world, &input_deps.#field_names, reader) {
+                        return None;
+                    }
+                )*
+                Self::derive(world, derive_fn, input_deps)
+            }
+
+            fn derive(
+                world: &::bevy_rx::__private::World,
+                derive_fn: impl Fn(Self::Query<'_>) -> T,
+                input_deps: Self,
+            ) -> Option<T> {
+                Some(derive_fn(#query_name {
+                    #(#field_names: ::bevy_rx::__private::read(world, &input_deps.#field_names)?,)*
+                }))
+            }
+
+            fn reactive_entities(&self) -> Vec<::bevy_rx::__private::Entity> {
+                vec![#(::bevy_rx::observable::Observable::reactive_entity(&self.#field_names),)*]
+            }
+        }
+    }
+    .into()
+}
+
+/// Declares a reactive graph, expanding every node to a `let` binding of its handle:
+///
+/// ```ignore
+/// rx! {
+///     reactor;
+///     let a = signal(1);
+///     let b = signal(2);
+///     let sum = memo(a + b);
+///     let label = lazy_memo(sum => format!("{sum}"));
+/// }
+/// ```
+///
+/// The first item is the context the nodes are created in, usually a variable, which is used once
+/// per node. Each node is one of:
+/// - `signal(value)`, a signal with an initial value, see `ReactiveContext::new_signal`,
+/// - `memo(expr)`, a memo computing `expr`, see `ReactiveContext::new_memo`,
+/// - `lazy_memo(expr)`, a memo only computed when read, see `ReactiveContext::new_lazy_memo`.
+///
+/// The inputs of a memo are the nodes declared earlier in the same macro that `expr` names. Inside
+/// `expr`, they are references to the values of those nodes. Nodes only named inside other
+/// macros, like `format!`, can't be found, so the inputs can be listed explicitly instead, as in
+/// `memo(a, b => expr)`. Names bound inside `expr`, like closure parameters, are not inputs, even
+/// if they shadow a node. Other variables used by `expr` are moved into the calculation.
+#[proc_macro]
+pub fn rx(input: TokenStream) -> TokenStream {
+    let graph = parse_macro_input!(input as RxGraph);
+    let context = &graph.context;
+    let mut declared = Vec::new();
+    let mut bindings = Vec::new();
+    for node in graph.nodes {
+        let name = &node.name;
+        let value = &node.value;
+        let binding = match node.kind.to_string().as_str() {
+            "signal" => quote! { let #name = #context.new_signal(#value); },
+            kind @ ("memo" | "lazy_memo") => {
+                let inputs = match node.inputs {
+                    Some(inputs) => inputs,
+                    None => MemoInputs::infer(value, &declared),
+                };
+                if inputs.is_empty() {
+                    return syn::Error::new_spanned(
+                        name,
+                        format!(
+                            "no inputs found for `{name}`, list them explicitly with \
+                            `{kind}(a, b => ...)`"
+                        ),
+                    )
+                    .to_compile_error()
+                    .into();
+                }
+                let constructor = format_ident!("new_{}", kind);
+                quote! {
+                    let #name = #context.#constructor(
+                        (#(#inputs,)*),
+                        move |(#(#inputs,)*)| #value,
+                    );
+                }
+            }
+            _ => {
+                return syn::Error::new_spanned(
+                    &node.kind,
+                    "expected `signal`, `memo`, or `lazy_memo`",
+                )
+                .to_compile_error()
+                .into();
+            }
+        };
+        bindings.push(binding);
+        declared.push(node.name);
+    }
+    quote! { #(#bindings)* }.into()
+}
+
+/// The input of [`rx!`]: the context, followed by the nodes of the graph.
+struct RxGraph {
+    context: Expr,
+    nodes: Vec<RxNode>,
+}
+
+/// A node declared in [`rx!`], e.g. `let sum = memo(a + b);`.
+struct RxNode {
+    name: Ident,
+    kind: Ident,
+    /// The inputs listed before `=>`, if any.
+    inputs: Option<Vec<Ident>>,
+    value: Expr,
+}
+
+impl Parse for RxGraph {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let context = input.parse()?;
+        if let Expr::Let(node) = &context {
+            return Err(syn::Error::new_spanned(
+                node,
+                "expected the context the nodes are created in, like `reactor;`, before the nodes",
+            ));
+        }
+        input.parse::<Token![;]>()?;
+        let mut nodes = Vec::new();
+        while !input.is_empty() {
+            nodes.push(input.parse()?);
+        }
+        Ok(Self { context, nodes })
+    }
+}
+
+impl Parse for RxNode {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<Token![let]>()?;
+        let name = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let kind = input.parse()?;
+        let content;
+        parenthesized!(content in input);
+        let listed = content.fork();
+        let inputs = match Punctuated::<Ident, Comma>::parse_separated_nonempty(&listed) {
+            Ok(_) if listed.peek(Token![=>]) => {
+                let inputs = Punctuated::<Ident, Comma>::parse_separated_nonempty(&content)?;
+                content.parse::<Token![=>]>()?;
+                Some(inputs.into_iter().collect())
+            }
+            _ => None,
+        };
+        let value = content.parse()?;
+        input.parse::<Token![;]>()?;
+        Ok(Self {
+            name,
+            kind,
+            inputs,
+            value,
+        })
+    }
+}
+
+/// Finds the inputs of a memo: the declared nodes named in its expression, in order. Names bound
+/// inside the expression, like closure parameters or `let` bindings, shadow the declared nodes
+/// while they are in scope.
+struct MemoInputs<'a> {
+    declared: &'a [Ident],
+    found: Vec<Ident>,
+    /// The names bound inside the expression that are currently in scope.
+    bound: Vec<Ident>,
+}
+
+impl<'a> MemoInputs<'a> {
+    fn infer(value: &Expr, declared: &'a [Ident]) -> Vec<Ident> {
+        let mut inputs = Self {
+            declared,
+            found: Vec::new(),
+            bound: Vec::new(),
+        };
+        inputs.visit_expr(value);
+        inputs.found
+    }
+
+    /// Visit `scope` with the names bound by `pats` in scope.
+    fn visit_bound<'p>(
+        &mut self,
+        pats: impl IntoIterator<Item = &'p Pat>,
+        scope: impl FnOnce(&mut Self),
+    ) {
+        let len = self.bound.len();
+        for pat in pats {
+            self.bind(pat);
+        }
+        scope(self);
+        self.bound.truncate(len);
+    }
+
+    /// Bring the names bound by `pat` into scope, until the enclosing scope ends.
+    fn bind(&mut self, pat: &Pat) {
+        struct Bindings<'b>(&'b mut Vec<Ident>);
+        impl<'ast> Visit<'ast> for Bindings<'_> {
+            fn visit_pat_ident(&mut self, pat: &'ast PatIdent) {
+                self.0.push(pat.ident.clone());
+                visit::visit_pat_ident(self, pat);
+            }
+        }
+        Bindings(&mut self.bound).visit_pat(pat);
+    }
+}
+
+impl<'ast> Visit<'ast> for MemoInputs<'_> {
+    fn visit_expr_path(&mut self, path: &'ast ExprPath) {
+        if let Some(ident) = path.path.get_ident() {
+            if self.declared.contains(ident)
+                && !self.bound.contains(ident)
+                && !self.found.contains(ident)
+            {
+                self.found.push(ident.clone());
+            }
+        }
+    }
+
+    fn visit_expr_closure(&mut self, closure: &'ast ExprClosure) {
+        self.visit_bound(&closure.inputs, |inputs| inputs.visit_expr(&closure.body));
+    }
+
+    fn visit_block(&mut self, block: &'ast Block) {
+        let len = self.bound.len();
+        for stmt in &block.stmts {
+            self.visit_stmt(stmt);
+            if let Stmt::Local(local) = stmt {
+                self.bind(&local.pat);
+            }
+        }
+        self.bound.truncate(len);
+    }
+
+    fn visit_local(&mut self, local: &'ast Local) {
+        // The pattern is bound by `visit_block` once the initializer has been visited.
+        if let Some(init) = &local.init {
+            self.visit_expr(&init.expr);
+            if let Some((_, diverge)) = &init.diverge {
+                self.visit_expr(diverge);
+            }
+        }
+    }
+
+    fn visit_arm(&mut self, arm: &'ast Arm) {
+        self.visit_bound([&arm.pat], |inputs| {
+            if let Some((_, guard)) = &arm.guard {
+                inputs.visit_expr(guard);
+            }
+            inputs.visit_expr(&arm.body);
+        });
+    }
+
+    fn visit_expr_for_loop(&mut self, for_loop: &'ast ExprForLoop) {
+        self.visit_expr(&for_loop.expr);
+        self.visit_bound([&*for_loop.pat], |inputs| {
+            inputs.visit_block(&for_loop.body)
+        });
+    }
+
+    fn visit_expr_if(&mut self, expr_if: &'ast ExprIf) {
+        match &*expr_if.cond {
+            Expr::Let(cond) => {
+                self.visit_expr(&cond.expr);
+                self.visit_bound([&*cond.pat], |inputs| {
+                    inputs.visit_block(&expr_if.then_branch)
+                });
+            }
+            cond => {
+                self.visit_expr(cond);
+                self.visit_block(&expr_if.then_branch);
+            }
+        }
+        if let Some((_, else_branch)) = &expr_if.else_branch {
+            self.visit_expr(else_branch);
+        }
+    }
+
+    fn visit_expr_while(&mut self, expr_while: &'ast ExprWhile) {
+        match &*expr_while.cond {
+            Expr::Let(cond) => {
+                self.visit_expr(&cond.expr);
+                self.visit_bound([&*cond.pat], |inputs| inputs.visit_block(&expr_while.body));
+            }
+            cond => {
+                self.visit_expr(cond);
+                self.visit_block(&expr_while.body);
+            }
+        }
+    }
+}
+
+/// The named fields of the struct the `derive` is applied to, or an error if it isn't a struct
+/// with named fields and no generics.
+fn named_fields<'a>(
+    input: &'a DeriveInput,
+    derive: &str,
+) -> Result<&'a Punctuated<Field, Comma>, syn::Error> {
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            format!("`{derive}` does not support generics"),
+        ));
+    }
+    match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => Ok(&fields.named),
+            _ => Err(syn::Error::new_spanned(
+                &input.ident,
+                format!("`{derive}` requires named fields"),
+            )),
+        },
+        _ => Err(syn::Error::new_spanned(
+            &input.ident,
+            format!("`{derive}` can only be derived for structs"),
+        )),
+    }
+}
@@ -0,0 +1,234 @@
+//! Effects that run async work, like loading a file or a network request, off of the main thread,
+//! and write the result back to a signal when it completes. Async code can also wait for a value
+//! with [`ReactiveContext::wait_for`].
+//!
+//! Tasks are spawned on bevy's [`AsyncComputeTaskPool`], and can't access the reactive world
+//! while they run. Instead, a completed task sends its write through a channel, and the channel is
+//! drained on the next [`ReactiveContext::flush_effects`], where every completed write is applied
+//! in a single traversal.
+
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc, Mutex,
+    },
+    task::{Context, Poll, Waker},
+};
+
+use bevy_ecs::prelude::*;
+use bevy_tasks::{AsyncComputeTaskPool, TaskPool};
+
+use crate::{
+    callback::{RxCallbacks, Subscription},
+    observable::RxObservableData,
+    signal::Signal,
+    traversal, Observable, ReactiveContext,
+};
+
+/// A signal write sent back from a completed async task.
+type AsyncWrite = dyn FnOnce(&mut World, &mut Vec<Entity>) + Send + Sync;
+
+/// The channel that completed async effects send their writes through.
+#[derive(Resource)]
+pub(crate) struct RxAsyncWrites {
+    sender: Sender<Box<AsyncWrite>>,
+    // `Receiver` is not `Sync`, but is only ever accessed through `&mut World`.
+    receiver: Mutex<Receiver<Box<AsyncWrite>>>,
+}
+
+impl Default for RxAsyncWrites {
+    fn default() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            sender,
+            receiver: Mutex::new(receiver),
+        }
+    }
+}
+
+impl RxAsyncWrites {
+    /// Run `task` with a clone of the value of `observable` every time it changes, and write the
+    /// output of the future it returns to `target`.
+    pub(crate) fn spawn<S, T, U, Fut>(
+        rctx: &mut ReactiveContext<S>,
+        observable: impl Observable<DataType = T>,
+        target: Signal<U>,
+        task: impl Fn(T) -> Fut + Send + Sync + 'static,
+    ) -> Subscription
+    where
+        T: Clone + Send + Sync + PartialEq + 'static,
+        U: Clone + Send + Sync + PartialEq + 'static,
+        Fut: Future<Output = U> + Send + 'static,
+    {
+        let sender = rctx.reactive_state.resource::<Self>().sender.clone();
+        let target = target.reactive_entity();
+        let callback = move |value: &T| {
+            let future = task(value.clone());
+            let sender = sender.clone();
+            AsyncComputeTaskPool::get_or_init(TaskPool::new)
+                .spawn(async move {
+                    let value = future.await;
+                    // The receiver is only dropped along with the reactive context.
+                    let _ = sender.send(Box::new(move |world: &mut World, stack: &mut _| {
+                        if world.get_entity(target).is_some() {
+                            RxObservableData::update_value(world, stack, target, value);
+                        }
+                    }));
+                })
+                .detach();
+        };
+        RxCallbacks::add(
+            &mut rctx.reactive_state,
+            observable.reactive_entity(),
+            callback,
+        )
+    }
+
+    /// Discard the writes of async effects that have completed but not been applied. Tasks that
+    /// are still running are not cancelled, their writes are skipped once their target is gone.
+    pub(crate) fn clear(rx_world: &mut World) {
+        rx_world
+            .resource_mut::<Self>()
+            .receiver
+            .get_mut()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .try_iter()
+            .for_each(drop);
+    }
+
+    /// Apply the writes of every async effect that has completed since the last call, and
+    /// propagate the changes in a single traversal.
+    pub(crate) fn apply(rx_world: &mut World) {
+        let writes: Vec<_> = rx_world
+            .resource_mut::<Self>()
+            .receiver
+            .get_mut()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .try_iter()
+            .collect();
+        if writes.is_empty() {
+            return;
+        }
+        traversal::send(rx_world, move |world, stack| {
+            for write in writes {
+                write(world, stack);
+            }
+        });
+    }
+}
+
+/// Resolves with the first value of an observable that satisfies a predicate, see
+/// [`ReactiveContext::wait_for`].
+struct WaitFor<T> {
+    state: Arc<Mutex<WaitState<T>>>,
+    /// Unsubscribes the check when the future is dropped before it resolves.
+    subscription: Option<Subscription>,
+    /// Sends `prune` to the reactive world when the future is dropped, so the callback is removed
+    /// on the next [`ReactiveContext::flush_effects`] instead of the next change.
+    prune: Option<(Sender<Box<AsyncWrite>>, Box<AsyncWrite>)>,
+}
+
+struct WaitState<T> {
+    value: Option<T>,
+    waker: Option<Waker>,
+}
+
+impl<T: Clone> Future for WaitFor<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        match &state.value {
+            // The value is kept, so polling again after `Ready` resolves with it again.
+            Some(value) => Poll::Ready(value.clone()),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<T> Drop for WaitFor<T> {
+    fn drop(&mut self) {
+        self.subscription.take();
+        if let Some((sender, prune)) = self.prune.take() {
+            // The receiver is only dropped along with the reactive context.
+            let _ = sender.send(prune);
+        }
+    }
+}
+
+impl<S> ReactiveContext<S> {
+    /// Returns a future that resolves with the value of the `observable` once it satisfies
+    /// `predicate`, e.g. to await the result of a dialog from an async task. If the current value
+    /// already satisfies it, the future resolves with that value. Stale lazy memos are computed
+    /// first, like [`ReactiveContext::read`].
+    ///
+    /// The check runs as a callback while changes propagate, and is removed once it succeeds.
+    /// Dropping the future unsubscribes the check immediately, and removes it on the next
+    /// [`ReactiveContext::flush_effects`]. The future never resolves if the observable is disposed
+    /// first.
+    pub fn wait_for<O: Observable>(
+        &mut self,
+        observable: O,
+        predicate: impl Fn(&O::DataType) -> bool + Send + Sync + 'static,
+    ) -> impl Future<Output = O::DataType> + Send + 'static
+    where
+        O::DataType: Clone,
+    {
+        let state = Arc::new(Mutex::new(WaitState {
+            value: None,
+            waker: None,
+        }));
+        let entity = observable.reactive_entity();
+        let mut wait = WaitFor {
+            state: state.clone(),
+            subscription: None,
+            prune: None,
+        };
+        self.pull(entity);
+        match RxObservableData::<O::DataType>::read(&self.reactive_state, entity) {
+            Ok(value) if predicate(value) => {
+                state.lock().unwrap().value = Some(value.clone());
+            }
+            _ if self.reactive_state.get_entity(entity).is_some() => {
+                let waiting = Arc::downgrade(&state);
+                let subscription =
+                    RxCallbacks::add_while(&mut self.reactive_state, entity, move |value| {
+                        let Some(state) = waiting.upgrade() else {
+                            return false; // The future was dropped.
+                        };
+                        if !predicate(value) {
+                            return true;
+                        }
+                        let mut state = state
+                            .lock()
+                            .unwrap_or_else(|poisoned| poisoned.into_inner());
+                        state.value = Some(value.clone());
+                        if let Some(waker) = state.waker.take() {
+                            waker.wake();
+                        }
+                        false
+                    });
+                let sender = self
+                    .reactive_state
+                    .resource::<RxAsyncWrites>()
+                    .sender
+                    .clone();
+                let prune: Box<AsyncWrite> = Box::new(move |world: &mut World, _: &mut _| {
+                    RxCallbacks::<O::DataType>::prune(world, entity);
+                });
+                wait.subscription = Some(subscription);
+                wait.prune = Some((sender, prune));
+            }
+            _ => {}
+        }
+        wait
+    }
+}
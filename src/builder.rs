@@ -0,0 +1,94 @@
+//! A helper for constructing large graphs, see [`GraphBuilder`].
+
+use std::{
+    any::{Any, TypeId},
+    marker::PhantomData,
+};
+
+use bevy_ecs::prelude::*;
+use bevy_utils::HashMap;
+
+use crate::{
+    memo::{Memo, MemoQuery},
+    observable::Observable,
+    signal::Signal,
+    ReactiveContext,
+};
+
+/// Builds a reactive graph, naming intermediate nodes so they can be reused, and deduplicating
+/// memos that would compute the same thing.
+///
+/// Two memos are considered the same when they have the same inputs, and the same calculation
+/// function that captures nothing. Closures that capture state are never deduplicated, because the
+/// captured values might differ.
+pub struct GraphBuilder<'a, S> {
+    rctx: &'a mut ReactiveContext<S>,
+    names: HashMap<String, Box<dyn Any + Send + Sync>>,
+    memos: HashMap<(TypeId, Vec<Entity>), Entity>,
+    dependencies: Vec<(Entity, Vec<Entity>)>,
+}
+
+impl<'a, S> GraphBuilder<'a, S> {
+    pub(crate) fn new(rctx: &'a mut ReactiveContext<S>) -> Self {
+        Self {
+            rctx,
+            names: HashMap::default(),
+            memos: HashMap::default(),
+            dependencies: Vec::new(),
+        }
+    }
+
+    /// Create a signal. See [`ReactiveContext::new_signal`].
+    pub fn signal<T: Clone + Send + Sync + PartialEq + 'static>(
+        &mut self,
+        initial_value: T,
+    ) -> Signal<T> {
+        self.rctx.new_signal(initial_value)
+    }
+
+    /// Create a memo, or return the existing one if an identical memo was already built. See
+    /// [`ReactiveContext::new_memo`].
+    pub fn memo<T, D, F>(&mut self, input_deps: D, derive_fn: F) -> Memo<T>
+    where
+        T: Clone + Send + Sync + PartialEq + 'static,
+        D: MemoQuery<T>,
+        F: Fn(D::Query<'_>) -> T + Send + Sync + Clone + 'static,
+    {
+        let inputs = input_deps.reactive_entities();
+        // A zero sized closure captures nothing, so its type uniquely identifies its behavior.
+        let key = (std::mem::size_of::<F>() == 0).then(|| (TypeId::of::<F>(), inputs.clone()));
+        if let Some(entity) = key.as_ref().and_then(|key| self.memos.get(key)) {
+            return Memo {
+                reactor_entity: *entity,
+                p: PhantomData,
+            };
+        }
+        let memo = self.rctx.new_memo(input_deps, derive_fn);
+        if let Some(key) = key {
+            self.memos.insert(key, memo.reactor_entity);
+        }
+        self.dependencies.push((memo.reactor_entity, inputs));
+        memo
+    }
+
+    /// Give a node a name, so it can be retrieved later with [`GraphBuilder::get`].
+    pub fn name<O: Observable>(&mut self, name: impl Into<String>, observable: O) -> O {
+        self.names.insert(name.into(), Box::new(observable));
+        observable
+    }
+
+    /// Get the node with this name, if one was named with the same handle type.
+    pub fn get<O: Observable>(&self, name: &str) -> Option<O> {
+        self.names.get(name)?.downcast_ref::<O>().copied()
+    }
+
+    /// Every memo created by the builder, with the entities of its inputs.
+    pub fn dependencies(&self) -> &[(Entity, Vec<Entity>)] {
+        &self.dependencies
+    }
+
+    /// The context the graph is being built in.
+    pub fn context(&mut self) -> &mut ReactiveContext<S> {
+        self.rctx
+    }
+}
@@ -0,0 +1,178 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+use bevy_ecs::prelude::*;
+
+use crate::{
+    error::{RxError, RxPanicHook},
+    observable::RxObservableData,
+};
+
+/// A plain callback invoked with the new value of an observable.
+type CallbackFn<T> = dyn FnMut(&T) + Send + Sync;
+
+/// A handle to a callback registered with [`crate::ReactiveContext::on_change`]. The callback is
+/// unsubscribed when this is dropped.
+#[must_use = "the callback is unsubscribed as soon as the subscription is dropped"]
+#[derive(Debug)]
+pub struct Subscription {
+    alive: Arc<AtomicBool>,
+}
+
+impl Subscription {
+    /// Keep the callback subscribed for as long as its observable exists.
+    pub fn detach(self) {
+        std::mem::forget(self);
+    }
+
+    /// Returns `true` if the callback has not been unsubscribed.
+    pub fn is_active(&self) -> bool {
+        self.alive.load(Ordering::Relaxed)
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        self.alive.store(false, Ordering::Relaxed);
+    }
+}
+
+/// A list of callbacks, each unsubscribed when its [`Subscription`] is dropped.
+pub(crate) struct Listeners<F: ?Sized> {
+    entries: Vec<(Arc<AtomicBool>, Box<F>)>,
+}
+
+impl<F: ?Sized> Default for Listeners<F> {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+}
+
+impl<F: ?Sized> Listeners<F> {
+    pub(crate) fn add(&mut self, callback: Box<F>) -> Subscription {
+        self.add_with(|_| callback)
+    }
+
+    /// Add the callback built by `build`, which receives the flag that unsubscribes it when
+    /// cleared, so the callback can unsubscribe itself.
+    pub(crate) fn add_with(
+        &mut self,
+        build: impl FnOnce(&Arc<AtomicBool>) -> Box<F>,
+    ) -> Subscription {
+        let alive = Arc::new(AtomicBool::new(true));
+        self.entries.push((alive.clone(), build(&alive)));
+        Subscription { alive }
+    }
+
+    /// Invoke every live callback with `call`. Callbacks whose [`Subscription`] has been dropped
+    /// are removed.
+    pub(crate) fn notify(&mut self, mut call: impl FnMut(&mut F)) {
+        self.prune();
+        for (_, callback) in self.entries.iter_mut() {
+            call(callback);
+        }
+    }
+
+    /// Remove the callbacks whose [`Subscription`] has been dropped.
+    pub(crate) fn prune(&mut self) {
+        self.entries
+            .retain(|(alive, _)| alive.load(Ordering::Relaxed));
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// The callbacks subscribed to an observable, stored alongside its [`RxObservableData`]. These are
+/// run synchronously during traversal, as soon as the value changes.
+#[derive(Component)]
+pub(crate) struct RxCallbacks<T> {
+    callbacks: Listeners<CallbackFn<T>>,
+}
+
+impl<T: Send + Sync + 'static> RxCallbacks<T> {
+    pub(crate) fn add(
+        rx_world: &mut World,
+        observable: Entity,
+        callback: impl FnMut(&T) + Send + Sync + 'static,
+    ) -> Subscription {
+        match Self::listeners(rx_world, observable) {
+            Some(mut listeners) => listeners.callbacks.add(Box::new(callback)),
+            None => Subscription {
+                alive: Arc::new(AtomicBool::new(false)),
+            },
+        }
+    }
+
+    /// Add a callback that stays subscribed until it returns `false`, its [`Subscription`] is
+    /// dropped, or the observable is disposed.
+    pub(crate) fn add_while(
+        rx_world: &mut World,
+        observable: Entity,
+        mut callback: impl FnMut(&T) -> bool + Send + Sync + 'static,
+    ) -> Subscription {
+        let Some(mut listeners) = Self::listeners(rx_world, observable) else {
+            return Subscription {
+                alive: Arc::new(AtomicBool::new(false)),
+            };
+        };
+        listeners.callbacks.add_with(|alive| {
+            let alive = alive.clone();
+            Box::new(move |value: &T| {
+                if !callback(value) {
+                    alive.store(false, Ordering::Relaxed);
+                }
+            })
+        })
+    }
+
+    /// Remove the callbacks of the `observable` whose [`Subscription`] has been dropped, without
+    /// waiting for its next change.
+    pub(crate) fn prune(rx_world: &mut World, observable: Entity) {
+        let Some(mut listeners) = rx_world.get_mut::<Self>(observable) else {
+            return;
+        };
+        listeners.callbacks.prune();
+        if listeners.callbacks.is_empty() {
+            rx_world.entity_mut(observable).remove::<Self>();
+        }
+    }
+
+    /// The callbacks of the `observable`, added to it if it has none yet. If the observable has
+    /// been disposed, this is reported to the [`RxPanicHook`] and `None` is returned.
+    fn listeners(rx_world: &mut World, observable: Entity) -> Option<Mut<'_, Self>> {
+        let Some(mut entity) = rx_world.get_entity_mut(observable) else {
+            let error = RxError::NodeDisposed(observable);
+            RxPanicHook::report(rx_world, "Failed to add callback", error);
+            return None;
+        };
+        if entity.get::<Self>().is_none() {
+            entity.insert(Self {
+                callbacks: Listeners::default(),
+            });
+        }
+        rx_world.get_mut::<Self>(observable)
+    }
+
+    /// Invoke every live callback with the current value of the `observable`. Callbacks whose
+    /// [`Subscription`] has been dropped are removed.
+    pub(crate) fn notify(rx_world: &mut World, observable: Entity) {
+        if rx_world.get::<Self>(observable).is_none() {
+            return;
+        }
+        let Some(mut callbacks) = rx_world.entity_mut(observable).take::<Self>() else {
+            return;
+        };
+        if let Some(data) = rx_world.get::<RxObservableData<T>>(observable) {
+            callbacks.callbacks.notify(|callback| callback(data.data()));
+        }
+        if !callbacks.callbacks.is_empty() {
+            rx_world.entity_mut(observable).insert(callbacks);
+        }
+    }
+}
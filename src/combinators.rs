@@ -0,0 +1,212 @@
+//! Named constructors for common memos, built on [`ReactiveContext::new_memo`].
+
+use bevy_ecs::prelude::*;
+
+use crate::{
+    memo::{Memo, RxMemo},
+    observable::{Observable, RxObservableData, RxSubscribers},
+    ReactiveContext,
+};
+
+impl<S> ReactiveContext<S> {
+    /// Combine two observables into a memo holding a tuple of their values, which is recomputed
+    /// when either of them changes.
+    pub fn zip<A, B>(&mut self, a: A, b: B) -> Memo<(A::DataType, B::DataType)>
+    where
+        A: Observable,
+        B: Observable,
+        A::DataType: Clone,
+        B::DataType: Clone,
+    {
+        self.new_memo((a, b), |(a, b)| (a.clone(), b.clone()))
+    }
+
+    /// Combine three observables into a memo holding a tuple of their values. See
+    /// [`ReactiveContext::zip`].
+    pub fn zip3<A, B, C>(
+        &mut self,
+        a: A,
+        b: B,
+        c: C,
+    ) -> Memo<(A::DataType, B::DataType, C::DataType)>
+    where
+        A: Observable,
+        B: Observable,
+        C: Observable,
+        A::DataType: Clone,
+        B::DataType: Clone,
+        C::DataType: Clone,
+    {
+        self.new_memo((a, b, c), |(a, b, c)| (a.clone(), b.clone(), c.clone()))
+    }
+
+    /// Combine four observables into a memo holding a tuple of their values. See
+    /// [`ReactiveContext::zip`].
+    #[allow(clippy::type_complexity)]
+    pub fn zip4<A, B, C, D>(
+        &mut self,
+        a: A,
+        b: B,
+        c: C,
+        d: D,
+    ) -> Memo<(A::DataType, B::DataType, C::DataType, D::DataType)>
+    where
+        A: Observable,
+        B: Observable,
+        C: Observable,
+        D: Observable,
+        A::DataType: Clone,
+        B::DataType: Clone,
+        C::DataType: Clone,
+        D::DataType: Clone,
+    {
+        self.new_memo((a, b, c, d), |(a, b, c, d)| {
+            (a.clone(), b.clone(), c.clone(), d.clone())
+        })
+    }
+
+    /// Split an observable holding a pair into a memo for each half, the inverse of
+    /// [`ReactiveContext::zip`]. Each memo only notifies its subscribers when its own half changes,
+    /// so subscribers that use only one half are not recomputed when the other half changes.
+    pub fn split<O, A, B>(&mut self, pair: O) -> (Memo<A>, Memo<B>)
+    where
+        O: Observable<DataType = (A, B)>,
+        A: Clone + PartialEq + Send + Sync + 'static,
+        B: Clone + PartialEq + Send + Sync + 'static,
+    {
+        let a = self.new_memo((pair,), |((a, _),)| a.clone());
+        let b = self.new_memo((pair,), |((_, b),)| b.clone());
+        (a, b)
+    }
+
+    /// A memo holding the value of an optional observable, or `default` while it is `None`.
+    pub fn unwrap_or<O, T>(&mut self, optional: O, default: T) -> Memo<T>
+    where
+        O: Observable<DataType = Option<T>>,
+        T: Clone + PartialEq + Send + Sync + 'static,
+    {
+        self.new_memo((optional,), move |(value,)| {
+            value.clone().unwrap_or_else(|| default.clone())
+        })
+    }
+
+    /// A memo that follows an optional observable, but only while it holds a value that passes
+    /// `predicate`. Otherwise the memo keeps its last value, so subscribers are not notified of
+    /// `None`s or rejected values. The memo is `None` until the first value passes.
+    pub fn filter<O, T>(
+        &mut self,
+        optional: O,
+        predicate: impl Fn(&T) -> bool + Send + Sync + 'static,
+    ) -> Memo<Option<T>>
+    where
+        O: Observable<DataType = Option<T>>,
+        T: Clone + PartialEq + Send + Sync + 'static,
+    {
+        let input = optional.reactive_entity();
+        Memo::from_calculation(self, |entity| {
+            RxMemo::from_fn(move |world: &mut World, stack: &mut Vec<Entity>| {
+                RxSubscribers::add(world, input, entity);
+                let Some(input) = world.get::<RxObservableData<Option<T>>>(input) else {
+                    return;
+                };
+                let value = match input.data() {
+                    Some(value) if predicate(value) => Some(value.clone()),
+                    // Latch the last value that passed, starting from `None`.
+                    _ if world.get::<RxObservableData<Option<T>>>(entity).is_some() => return,
+                    _ => None,
+                };
+                RxObservableData::update_value(world, stack, entity, value);
+            })
+        })
+    }
+
+    /// A memo holding the value of `value` as of the last time `trigger` changed, e.g. to capture
+    /// the cursor position when a click happens. Changes of `value` alone don't update the memo,
+    /// because it is read without subscribing to it. The memo starts with the current value.
+    pub fn sample<O>(&mut self, value: O, trigger: impl Observable) -> Memo<O::DataType>
+    where
+        O: Observable,
+        O::DataType: Clone,
+    {
+        let (value, trigger) = (value.reactive_entity(), trigger.reactive_entity());
+        Memo::from_calculation(self, |entity| {
+            RxMemo::from_fn(move |world: &mut World, stack: &mut Vec<Entity>| {
+                RxSubscribers::add(world, trigger, entity);
+                let Some(value) = world.get::<RxObservableData<O::DataType>>(value) else {
+                    return;
+                };
+                let value = value.data().clone();
+                RxObservableData::update_value(world, stack, entity, value);
+            })
+        })
+    }
+
+    /// A memo holding the value of whichever of the `sources` changed most recently, e.g. to
+    /// combine keyboard, gamepad, and network inputs that each set the same value. Sources that
+    /// change in the same traversal are ordered by their change ticks, see
+    /// [`ReactiveContext::last_changed_tick`].
+    pub fn merge<O>(&mut self, sources: &[O]) -> Memo<O::DataType>
+    where
+        O: Observable,
+        O::DataType: Clone,
+    {
+        let inputs: Vec<Entity> = sources.iter().map(O::reactive_entity).collect();
+        Memo::from_calculation(self, |entity| {
+            RxMemo::from_fn(move |world: &mut World, stack: &mut Vec<Entity>| {
+                for input in &inputs {
+                    RxSubscribers::add(world, *input, entity);
+                }
+                let latest = inputs
+                    .iter()
+                    .filter_map(|input| world.get::<RxObservableData<O::DataType>>(*input))
+                    .max_by_key(|input| input.changed_tick);
+                let Some(latest) = latest else {
+                    return;
+                };
+                let value = latest.data().clone();
+                RxObservableData::update_value(world, stack, entity, value);
+            })
+        })
+    }
+
+    /// A memo that is `true` when every observable in `observables` is `true`.
+    ///
+    /// Inputs are read in order, stopping at the first `false` one. Inputs after it are not
+    /// subscribed to, because they can't change the result until that input becomes `true`.
+    pub fn all<O: Observable<DataType = bool>>(&mut self, observables: &[O]) -> Memo<bool> {
+        self.short_circuit(observables, false)
+    }
+
+    /// A memo that is `true` when any observable in `observables` is `true`.
+    ///
+    /// Inputs are read in order, stopping at the first `true` one. Inputs after it are not
+    /// subscribed to, because they can't change the result until that input becomes `false`.
+    pub fn any<O: Observable<DataType = bool>>(&mut self, observables: &[O]) -> Memo<bool> {
+        self.short_circuit(observables, true)
+    }
+
+    /// Evaluates to `stop_on` as soon as any input is equal to it, otherwise `!stop_on`.
+    fn short_circuit<O: Observable<DataType = bool>>(
+        &mut self,
+        observables: &[O],
+        stop_on: bool,
+    ) -> Memo<bool> {
+        let inputs: Vec<Entity> = observables.iter().map(O::reactive_entity).collect();
+        Memo::from_calculation(self, |entity| {
+            RxMemo::from_fn(move |world: &mut World, stack: &mut Vec<Entity>| {
+                let mut value = !stop_on;
+                for input in &inputs {
+                    RxSubscribers::add(world, *input, entity);
+                    let Some(input) = world.get::<RxObservableData<bool>>(*input) else {
+                        return;
+                    };
+                    if *input.data() == stop_on {
+                        value = stop_on;
+                        break;
+                    }
+                }
+                RxObservableData::update_value(world, stack, entity, value);
+            })
+        })
+    }
+}
@@ -0,0 +1,66 @@
+use bevy_ecs::prelude::*;
+use bevy_utils::HashMap;
+
+use crate::{
+    observable::{Observable, RxObservableData},
+    signal::Signal,
+    ReactiveContext,
+};
+
+/// Sends the extracted value of a component to a signal.
+type ExtractFn<C> = dyn Fn(&C, &mut World) + Send + Sync;
+
+/// The signals driven by components of type `C` in the main world, keyed by the main world entity.
+#[derive(Resource)]
+pub(crate) struct RxTrackedComponents<C: Component> {
+    tracked: HashMap<Entity, Vec<Box<ExtractFn<C>>>>,
+}
+
+impl<C: Component> Default for RxTrackedComponents<C> {
+    fn default() -> Self {
+        Self {
+            tracked: HashMap::default(),
+        }
+    }
+}
+
+impl<C: Component> RxTrackedComponents<C> {
+    /// Create a signal holding the value extracted from the `C` component on `entity` in the main
+    /// world, or `None` until the component has been seen.
+    pub(crate) fn track<S, T: Clone + PartialEq + Send + Sync + 'static>(
+        rctx: &mut ReactiveContext<S>,
+        entity: Entity,
+        extract: impl Fn(&C) -> T + Send + Sync + 'static,
+    ) -> Signal<Option<T>> {
+        let signal = rctx.new_signal(None);
+        let signal_entity = signal.reactive_entity();
+        let send = move |component: &C, rx_world: &mut World| {
+            RxObservableData::send_signal(rx_world, signal_entity, Some(extract(component)));
+        };
+        rctx.reactive_state
+            .get_resource_or_insert_with(Self::default)
+            .tracked
+            .entry(entity)
+            .or_default()
+            .push(Box::new(send));
+        signal
+    }
+
+    /// Send the values of every tracked component that changed since this system last ran.
+    #[cfg(feature = "bevy_app")]
+    pub(crate) fn send_changes(
+        changed: Query<(Entity, &C), Changed<C>>,
+        mut rctx: ResMut<ReactiveContext<World>>,
+    ) {
+        let rx_world = &mut rctx.reactive_state;
+        let Some(tracked) = rx_world.remove_resource::<Self>() else {
+            return;
+        };
+        for (entity, component) in &changed {
+            for send in tracked.tracked.get(&entity).into_iter().flatten() {
+                send(component, rx_world);
+            }
+        }
+        rx_world.insert_resource(tracked);
+    }
+}
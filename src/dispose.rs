@@ -0,0 +1,82 @@
+//! Removing nodes from the reactive graph, and how memos react when one of their inputs is removed.
+
+use bevy_ecs::prelude::*;
+use bevy_utils::tracing::warn;
+
+use crate::{
+    observable::{RxPersistentSources, RxSubscribers},
+    traversal,
+};
+
+/// What a memo does when it is recomputed and one of its inputs has been disposed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DisposedInputs {
+    /// Keep the last computed value, and log a warning.
+    #[default]
+    KeepLastValue,
+    /// Dispose of the memo too, which in turn cascades to the memos that depend on it.
+    Cascade,
+}
+
+/// The [`DisposedInputs`] policy of a memo, if it is not the default.
+#[derive(Component)]
+pub(crate) struct RxDisposedInputs(pub(crate) DisposedInputs);
+
+/// Despawn the `node`, and recompute its subscribers so they can react to it being disposed.
+/// Returns `false` if the node was already disposed.
+pub(crate) fn dispose(rx_world: &mut World, node: Entity) -> bool {
+    dispose_all(rx_world, vec![node]) == 1
+}
+
+/// Despawn every node in `nodes` at once, and recompute the subscribers that were not disposed
+/// along with them. Returns the number of nodes that were not already disposed.
+pub(crate) fn dispose_all(rx_world: &mut World, mut nodes: Vec<Entity>) -> usize {
+    nodes.retain(|node| rx_world.get_entity(*node).is_some());
+    let count = nodes.len();
+    if count == 0 {
+        return 0;
+    }
+    #[cfg(feature = "local")]
+    crate::local::RxLocalValues::remove(rx_world, &nodes);
+    traversal::send(rx_world, move |world, stack| {
+        for node in nodes {
+            let Some(mut entity) = world.get_entity_mut(node) else {
+                continue;
+            };
+            #[cfg(feature = "tracking")]
+            let len = stack.len();
+            if let Some(mut subscribers) = entity.get_mut::<RxSubscribers>() {
+                subscribers.drain_into(stack);
+            }
+            let sources = entity.take::<RxPersistentSources>().unwrap_or_default();
+            entity.despawn();
+            #[cfg(feature = "tracking")]
+            crate::tracker::RecomputeTracker::scheduled(world, node, &stack[len..]);
+            for source in sources.0 {
+                if let Some(mut subscribers) = world.get_mut::<RxSubscribers>(source) {
+                    subscribers.unsubscribe(node);
+                }
+            }
+        }
+        // Subscribers that were disposed too are skipped when the stack is traversed.
+    });
+    count
+}
+
+/// Called when `memo` tried to subscribe to an `input` that has been disposed.
+pub(crate) fn input_disposed(rx_world: &mut World, memo: Entity, input: Entity) {
+    let policy = rx_world
+        .get::<RxDisposedInputs>(memo)
+        .map(|policy| policy.0)
+        .unwrap_or_default();
+    match policy {
+        DisposedInputs::KeepLastValue => {
+            warn!("Memo {memo:?} depends on disposed node {input:?}, keeping its last value");
+        }
+        // This happens while the graph is being traversed, so the memo is disposed once the
+        // traversal has settled.
+        DisposedInputs::Cascade => {
+            dispose(rx_world, memo);
+        }
+    }
+}
@@ -1,7 +1,39 @@
-use bevy_ecs::{prelude::*, system::BoxedSystem};
+//! Side effects that run against the main world when reactive values change.
+//!
+//! By default, effects run one at a time in the order they were queued. Every effect reads the
+//! value it reacts to from an [`EffectData<T>`] resource, which is inserted just before the effect
+//! runs, so two queued effects observing the same type need different values in the same resource.
+//! An effect may also be queued more than once in a flush, and [`EffectCommands::PerEffect`] lets
+//! each effect see the commands of the ones before it. [`EffectExecutor::Parallel`] opts into
+//! running effects that don't conflict at the same time, in waves that keep the queue order
+//! between effects that observe the same type or access the same data. Bevy's `Schedule` isn't
+//! used for this, as it takes ownership of its systems, while effects keep their initialized
+//! systems between flushes.
+use std::{
+    any::{type_name, TypeId},
+    borrow::Cow,
+    collections::VecDeque,
+    fmt::Debug,
+    marker::PhantomData,
+    sync::{Arc, Mutex},
+};
+
+use bevy_ecs::{
+    prelude::*,
+    system::{BoxedSystem, SystemParam},
+};
+use bevy_tasks::{ComputeTaskPool, TaskPool};
+use bevy_utils::{
+    tracing::{error, warn},
+    HashMap, HashSet,
+};
 
 use crate::{
-    observable::{Observable, RxObservableData},
+    error::RxError,
+    memo::RxMemoNode,
+    observable::{Observable, ObservableTuple, RxObservableData},
+    signal::Signal,
+    traversal::{self, PendingWrite},
     ReactiveContext,
 };
 
@@ -13,15 +45,196 @@ pub struct Effect {
 }
 
 impl Effect {
-    pub fn new_deferred<M, S>(
+    pub fn new_deferred<M, S, O: Observable>(
+        rctx: &mut ReactiveContext<S>,
+        observable: O,
+        effect_system: impl IntoSystem<(), (), M>,
+    ) -> Self
+    where
+        O::DataType: Clone,
+    {
+        let reactor_entity = observable.reactive_entity();
+        rctx.reactive_state
+            .entity_mut(reactor_entity)
+            .insert(RxDeferredEffect::new::<O::DataType, M>(effect_system));
+
+        Self { reactor_entity }
+    }
+
+    /// Create an effect that also runs once on creation, at the next flush, with the current value
+    /// of the `observable`, e.g. to initialize the main world state the effect keeps up to date.
+    /// Afterwards, it runs on every change like [`Effect::new_deferred`]. If the observable has no
+    /// value yet, the effect first runs once it has one.
+    pub fn new_mounted<M, S, O: Observable>(
         rctx: &mut ReactiveContext<S>,
-        observable: impl Observable,
+        observable: O,
+        effect_system: impl IntoSystem<(), (), M>,
+    ) -> Self
+    where
+        O::DataType: Clone,
+    {
+        let effect = Self::new_deferred(rctx, observable, effect_system);
+        let entity = effect.reactor_entity;
+        if RxObservableData::<O::DataType>::read(&rctx.reactive_state, entity).is_ok() {
+            RxDeferredEffects::enqueue::<O::DataType>(&mut rctx.reactive_state, entity);
+        }
+        effect
+    }
+
+    /// Create an effect from a closure that receives the observed value and the main world
+    /// directly, instead of from a system. Unlike a system, the closure can freely capture and
+    /// mutate its own state. Use [`Effect::new_deferred`] when the effect needs system params.
+    pub fn new_fn<S, O: Observable>(
+        rctx: &mut ReactiveContext<S>,
+        observable: O,
+        mut effect: impl FnMut(&O::DataType, &mut World) + Send + Sync + 'static,
+    ) -> Self
+    where
+        O::DataType: Clone,
+    {
+        let effect_system = move |world: &mut World| {
+            let Some(data) = world.remove_resource::<EffectData<O::DataType>>() else {
+                return;
+            };
+            effect(&data.value, world);
+            world.insert_resource(data);
+        };
+        Self::new_deferred(rctx, observable, effect_system)
+    }
+
+    /// Create an effect that reads the observed value in place through the [`EffectValue`] system
+    /// param, instead of receiving a clone of it in [`EffectData`]. Use this for effects over large
+    /// values, like meshes or buffers, that would be expensive to clone every time they change.
+    pub fn new_borrowed<M, S, O: Observable>(
+        rctx: &mut ReactiveContext<S>,
+        observable: O,
         effect_system: impl IntoSystem<(), (), M>,
     ) -> Self {
         let reactor_entity = observable.reactive_entity();
+        let effect = RxDeferredEffect::with_builder(
+            effect_system,
+            RxDeferredEffects::borrowed::<O::DataType>,
+        );
         rctx.reactive_state
             .entity_mut(reactor_entity)
-            .insert(RxDeferredEffect::new(effect_system));
+            .insert(effect);
+
+        Self { reactor_entity }
+    }
+
+    /// Create an effect that is rate limited across frames according to `timing`. See
+    /// [`EffectTiming`].
+    pub fn new_scheduled<M, S, O: Observable>(
+        rctx: &mut ReactiveContext<S>,
+        observable: O,
+        timing: EffectTiming,
+        effect_system: impl IntoSystem<(), (), M>,
+    ) -> Self
+    where
+        O::DataType: Clone,
+    {
+        let reactor_entity = observable.reactive_entity();
+        rctx.reactive_state
+            .entity_mut(reactor_entity)
+            .insert(RxDeferredEffect::new::<O::DataType, M>(effect_system).with_timing(timing));
+
+        Self { reactor_entity }
+    }
+
+    /// Create an effect that only runs when `is_significant(last, new)` returns `true`, where
+    /// `last` is the value the effect was last queued with, and `new` is the changed value.
+    pub fn new_significant<M, S, O: Observable>(
+        rctx: &mut ReactiveContext<S>,
+        observable: O,
+        is_significant: impl Fn(&O::DataType, &O::DataType) -> bool + Send + Sync + 'static,
+        effect_system: impl IntoSystem<(), (), M>,
+    ) -> Self
+    where
+        O::DataType: Clone,
+    {
+        let reactor_entity = observable.reactive_entity();
+        let baseline = rctx
+            .reactive_state
+            .get::<RxObservableData<O::DataType>>(reactor_entity)
+            .map(|data| data.data().clone());
+        rctx.reactive_state.entity_mut(reactor_entity).insert((
+            RxDeferredEffect::new::<O::DataType, M>(effect_system),
+            RxSignificance {
+                baseline,
+                is_significant: Box::new(is_significant),
+            },
+        ));
+
+        Self { reactor_entity }
+    }
+
+    /// Create an effect whose system returns a `Result`. Errors are passed to the handler set with
+    /// [`ReactiveContext::set_effect_error_handler`], which logs them by default.
+    pub fn new_fallible<M, S, O: Observable, E: Into<EffectError> + 'static>(
+        rctx: &mut ReactiveContext<S>,
+        observable: O,
+        effect_system: impl IntoSystem<(), Result<(), E>, M>,
+    ) -> Self
+    where
+        O::DataType: Clone,
+    {
+        let reactor_entity = observable.reactive_entity();
+        let error = ErrorSlot::default();
+        let slot = error.clone();
+        let effect_system = effect_system.map(move |result: Result<(), E>| {
+            if let Err(error) = result {
+                *slot.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(error.into());
+            }
+        });
+        let mut effect = RxDeferredEffect::new::<O::DataType, _>(effect_system);
+        effect.error = Some(error);
+        rctx.reactive_state
+            .entity_mut(reactor_entity)
+            .insert(effect);
+
+        Self { reactor_entity }
+    }
+
+    /// Create an effect that observes several `inputs`, and runs when any of them changes. The
+    /// effect reads the values of every input as a tuple from [`EffectData`], along with the
+    /// indices of the inputs that changed since it last ran, see [`EffectData::changed`].
+    ///
+    /// The effect runs at most once per flush, no matter how many of its inputs changed.
+    pub fn new_multi<M, S, D: ObservableTuple + Send + Sync + 'static>(
+        rctx: &mut ReactiveContext<S>,
+        inputs: D,
+        effect_system: impl IntoSystem<(), (), M>,
+    ) -> Self
+    where
+        D::Values: Send + Sync + 'static,
+    {
+        let input_entities = inputs.reactive_entities();
+        let reactor_entity = rctx
+            .reactive_state
+            .spawn((
+                RxDeferredEffect::with_builder(effect_system, RxDeferredEffects::multi::<D>),
+                RxMultiEffect {
+                    inputs: input_entities.clone(),
+                    changed: Vec::new(),
+                    queued: false,
+                    queue: RxDeferredEffects::push_multi::<D>,
+                },
+                RxMultiEffectInputs { inputs },
+            ))
+            .id();
+        for input in input_entities {
+            let Some(mut input) = rctx.reactive_state.get_entity_mut(input) else {
+                continue;
+            };
+            match input.get_mut::<RxEffectWatchers>() {
+                Some(mut watchers) => watchers.effects.push(reactor_entity),
+                None => {
+                    input.insert(RxEffectWatchers {
+                        effects: vec![reactor_entity],
+                    });
+                }
+            }
+        }
 
         Self { reactor_entity }
     }
@@ -31,8 +244,7 @@ impl Effect {
         rctx: &'r mut ReactiveContext<S>,
     ) -> Option<&'r dyn System<In = (), Out = ()>> {
         rctx.reactive_state
-            .get::<RxDeferredEffect>(self.reactor_entity)
-            .unwrap()
+            .get::<RxDeferredEffect>(self.reactor_entity)?
             .system()
     }
 }
@@ -40,63 +252,571 @@ impl Effect {
 /// A function used to run effects via dependency injection.
 pub type EffectFn = dyn FnOnce(&mut World, &mut World) + Send + Sync;
 
+/// An error returned by a fallible effect, see [`Effect::new_fallible`].
+pub type EffectError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Receives the errors returned by fallible effects, see
+/// [`ReactiveContext::set_effect_error_handler`].
+pub(crate) type EffectErrorHandler = dyn FnMut(Effect, EffectError) + Send + Sync;
+
+/// Sees, and can reorder, the effects about to run before each pass of a flush, see
+/// [`ReactiveContext::set_pre_flush_hook`].
+pub(crate) type PreFlushHook = dyn FnMut(&mut Vec<PendingEffect>) + Send + Sync;
+
+/// Where a fallible effect's system stores the error it returned, until it is handled.
+type ErrorSlot = Arc<Mutex<Option<EffectError>>>;
+
+/// Builds the [`EffectFn`] for the effect on an entity, which only respects the effect's enabled
+/// state and timing if the flag is `true`.
+type EffectBuilder = fn(Entity, bool) -> Box<EffectFn>;
+
 /// A stack of side effects that is gathered while systems run and update reactive data. This allows
 /// effects to be gathered during normal (non-exclusive) system execution in the user's main world.
 /// Once the user wants to execute the side effects, the plugin will need an exclusive system to run
 /// the effects in a big batch. This is the "deferred" part of the name.
-#[derive(Resource, Default)]
+#[derive(Resource)]
 pub(crate) struct RxDeferredEffects {
-    pub(crate) stack: Vec<Box<EffectFn>>,
+    /// The queued effects, with the entity of the effect each one runs.
+    pub(crate) stack: Vec<(Entity, Box<EffectFn>)>,
+    /// Effects that were not allowed to run by their [`EffectTiming`] during the current flush,
+    /// queued again once it completes.
+    pub(crate) held_back: Vec<(Entity, Box<EffectFn>)>,
+    /// The maximum number of passes a flush makes over effects queued by other effects, see
+    /// [`crate::ReactiveContext::set_max_effect_passes`].
+    pub(crate) max_passes: usize,
+    /// The number of times the deferred effects have been flushed, used as the frame counter for
+    /// [`EffectTiming`].
+    pub(crate) frame: u64,
+    pub(crate) commands: EffectCommands,
+    pub(crate) executor: EffectExecutor,
+    /// Set while [`EffectExecutor::Parallel`] collects the effects of a pass into `prepared`,
+    /// instead of running them as they are reached.
+    collecting: bool,
+    prepared: Vec<PreparedEffect>,
+    /// Effects that ran during the current flush, whose commands are applied once every effect has
+    /// run when using [`EffectCommands::Batched`].
+    pub(crate) ran: Vec<Entity>,
+    /// Effects that ran during the last flush.
+    pub(crate) fired: Vec<FiredEffect>,
+    /// Receives the errors returned by fallible effects. Errors are logged if this is `None`.
+    pub(crate) error_handler: Option<Box<EffectErrorHandler>>,
+    /// Sees the effects about to run before each pass of a flush.
+    pub(crate) pre_flush_hook: Option<Box<PreFlushHook>>,
+    /// Set once the buildup of unflushed effects has been warned about.
+    pub(crate) warned_unflushed: bool,
+    /// Set while a [`crate::hydrate::HydrateEffects::Suppress`] pass recomputes the graph, so no
+    /// effects are queued and no callbacks run.
+    pub(crate) suppressed: bool,
+}
+
+impl Default for RxDeferredEffects {
+    fn default() -> Self {
+        Self {
+            stack: Vec::new(),
+            held_back: Vec::new(),
+            max_passes: DEFAULT_MAX_EFFECT_PASSES,
+            frame: 0,
+            commands: EffectCommands::default(),
+            executor: EffectExecutor::default(),
+            collecting: false,
+            prepared: Vec::new(),
+            ran: Vec::new(),
+            fired: Vec::new(),
+            error_handler: None,
+            pre_flush_hook: None,
+            warned_unflushed: false,
+            suppressed: false,
+        }
+    }
+}
+
+/// The number of queued effects at which a warning is logged, because the effects are likely never
+/// flushed.
+const UNFLUSHED_EFFECTS_WARNING: usize = 10_000;
+
+/// The default for [`crate::ReactiveContext::max_effect_passes`].
+const DEFAULT_MAX_EFFECT_PASSES: usize = 16;
+
+/// A type erased record of an effect that ran, for generic tooling like debug overlays. See
+/// [`crate::ReactiveContext::fired_effects`].
+#[derive(Debug, Clone)]
+pub struct FiredEffect {
+    pub effect: Effect,
+    /// The type of the value the effect observed.
+    pub type_id: TypeId,
+    pub type_name: &'static str,
+    /// The observed value formatted with [`Debug`], if enabled with
+    /// [`crate::ReactiveContext::debug_values`].
+    pub value: Option<String>,
+}
+
+/// An effect that is about to run, shown to the hook set with
+/// [`crate::ReactiveContext::set_pre_flush_hook`].
+#[derive(Debug, Clone)]
+pub struct PendingEffect {
+    pub effect: Effect,
+    /// The name of the effect's system, if it has one.
+    pub name: Option<Cow<'static, str>>,
+}
+
+/// Formats the data of an observable with [`Debug`], without knowing its type.
+type DebugFn = fn(&World, Entity) -> Option<String>;
+
+/// Allows the value of an observable to be formatted without knowing its type.
+#[derive(Component)]
+pub(crate) struct RxDebugValue {
+    format: DebugFn,
+}
+
+impl RxDebugValue {
+    pub(crate) fn new<T: Debug + Send + Sync + 'static>() -> Self {
+        Self {
+            format: |rx_world, entity| {
+                let data = rx_world.get::<RxObservableData<T>>(entity)?;
+                Some(format!("{:?}", data.data()))
+            },
+        }
+    }
+
+    pub(crate) fn format(rx_world: &World, entity: Entity) -> Option<String> {
+        (rx_world.get::<Self>(entity)?.format)(rx_world, entity)
+    }
+}
+
+/// Controls when the [`Commands`] issued by effects are applied to the main world.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EffectCommands {
+    /// Apply the commands of each effect as soon as it has run. Effects run in the order they were
+    /// queued, so an effect sees entities spawned by effects that ran before it in the same flush,
+    /// but not those that run after it.
+    #[default]
+    PerEffect,
+    /// Run every effect, then apply all of their commands together, like the systems in a bevy
+    /// schedule. No effect sees the commands of any other effect in the same flush.
+    Batched,
+}
+
+/// Controls whether the effects of a flush may run at the same time.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EffectExecutor {
+    /// Run effects one at a time, in the order they were queued.
+    #[default]
+    Sequential,
+    /// Run the effects of each pass in waves on bevy's `ComputeTaskPool`. A wave is the longest run
+    /// of queued effects that don't conflict: their systems have compatible world access, and
+    /// observe different types, as each reads its own [`EffectData<T>`] resource. Exclusive and
+    /// non-`Send` systems run alone, and an effect queued twice in a pass ends the wave before it.
+    ///
+    /// The data of each effect is taken before the wave runs, so an effect doesn't see the
+    /// [`ReactiveWriter`] writes of the effects before it in the same pass, and with
+    /// [`EffectCommands::PerEffect`] the commands of a wave are applied in queue order once the
+    /// whole wave has run.
+    Parallel,
 }
 
 impl RxDeferredEffects {
-    pub fn push<T: Clone + PartialEq + Send + Sync + 'static>(&mut self, observable: Entity) {
-        let effect = Box::new(move |main_world: &mut World, rx_world: &mut World| {
-            let Some(value) = rx_world
-                .entity_mut(observable)
-                .take::<RxObservableData<T>>()
-            else {
+    /// Discard every effect that has been queued but not run, see [`ReactiveContext::clear`].
+    pub(crate) fn clear(&mut self) {
+        self.stack.clear();
+        self.held_back.clear();
+        self.prepared.clear();
+        self.ran.clear();
+    }
+
+    /// Queue the effect on the `observable` entity, respecting the effect's [`EffectTiming`].
+    pub(crate) fn enqueue<T: Clone + PartialEq + Send + Sync + 'static>(
+        rx_world: &mut World,
+        observable: Entity,
+    ) {
+        if !RxSignificance::<T>::check(rx_world, observable) {
+            return;
+        }
+        let frame = rx_world.resource::<RxDeferredEffects>().frame;
+        let Some(mut effect) = rx_world.get_mut::<RxDeferredEffect>(observable) else {
+            return;
+        };
+        if effect.enabled && effect.schedule.notify(frame) {
+            let build = effect.build;
+            rx_world
+                .resource_mut::<RxDeferredEffects>()
+                .enqueue_fn(observable, build(observable, true));
+        }
+    }
+
+    fn enqueue_fn(&mut self, effect: Entity, run: Box<EffectFn>) {
+        self.stack.push((effect, run));
+        if self.stack.len() >= UNFLUSHED_EFFECTS_WARNING && !self.warned_unflushed {
+            self.warned_unflushed = true;
+            warn!(
+                "{} effects are waiting to run. Effects only run when the context is flushed, \
+                either by `ReactiveExtensionsPlugin`, or by calling \
+                `ReactiveContext::flush_effects` when using the context on its own.",
+                self.stack.len()
+            );
+        }
+    }
+
+    /// Build the function that runs the effect on the `observable` entity with its current value.
+    /// If `scheduled`, the effect only runs if it is enabled and allowed to by its timing.
+    fn single<T: Clone + PartialEq + Send + Sync + 'static>(
+        observable: Entity,
+        scheduled: bool,
+    ) -> Box<EffectFn> {
+        let requeue = scheduled.then_some(Self::single::<T> as EffectBuilder);
+        Self::effect_fn(observable, requeue, |rx_world, observable| {
+            // The value is cloned rather than taken out of the reactive world, so the observable
+            // keeps its data while the effect runs, and the entity doesn't change archetypes.
+            let value = rx_world
+                .get::<RxObservableData<T>>(observable)?
+                .data()
+                .clone();
+            Some(EffectData {
+                value,
+                changed: vec![0],
+            })
+        })
+    }
+
+    /// Build the function that runs the borrowing effect on the `observable` entity, see
+    /// [`Effect::new_borrowed`]. Only the entity is handed to the effect, which reads the value in
+    /// place through [`EffectValue`].
+    fn borrowed<T: Send + Sync + 'static>(observable: Entity, scheduled: bool) -> Box<EffectFn> {
+        let requeue = scheduled.then_some(Self::borrowed::<T> as EffectBuilder);
+        Self::effect_fn(observable, requeue, |rx_world, observable| {
+            rx_world.get::<RxObservableData<T>>(observable)?;
+            Some(EffectData {
+                value: BorrowedValue {
+                    observable,
+                    p: PhantomData::<T>,
+                },
+                changed: vec![0],
+            })
+        })
+    }
+
+    /// Build the function that runs the effect on the `observable` entity, which reads the data it
+    /// runs with using `data`. If the effect isn't allowed to run yet, it is built again with
+    /// `requeue` and held back for the next flush. Without `requeue`, the effect runs
+    /// unconditionally.
+    fn effect_fn<T: Send + Sync + 'static>(
+        observable: Entity,
+        requeue: Option<EffectBuilder>,
+        data: impl FnOnce(&mut World, Entity) -> Option<EffectData<T>> + Send + Sync + 'static,
+    ) -> Box<EffectFn> {
+        Box::new(move |main_world: &mut World, rx_world: &mut World| {
+            if rx_world.get_entity(observable).is_none() {
+                warn!(
+                    "Skipped effect {observable:?}, its observable was disposed before the effects \
+                    were flushed."
+                );
+                return;
+            }
+            if !rx_world.entity(observable).contains::<RxDeferredEffect>() {
+                warn!(
+                    "Skipped effect {observable:?}, it was removed before the effects were \
+                    flushed."
+                );
+                return;
+            }
+            let frame = rx_world.resource::<RxDeferredEffects>().frame;
+            if let Some(requeue) = requeue {
+                let Some(mut effect) = rx_world.get_mut::<RxDeferredEffect>(observable) else {
+                    return;
+                };
+                if !effect.enabled {
+                    RxMultiEffect::dequeue(rx_world, observable);
+                    return; // Disabled after it was queued.
+                }
+                if !effect.schedule.ready(frame) {
+                    // Not allowed to run this frame, try again during the next flush.
+                    rx_world
+                        .resource_mut::<RxDeferredEffects>()
+                        .held_back
+                        .push((observable, requeue(observable, true)));
+                    return;
+                }
+            }
+
+            let Some(data) = data(rx_world, observable) else {
+                warn!(
+                    "Skipped effect {observable:?}, the `{}` it observes has no value.",
+                    type_name::<T>()
+                );
                 return;
             };
 
-            let Some(mut effect) = rx_world.entity_mut(observable).take::<RxDeferredEffect>()
-            else {
-                rx_world.entity_mut(observable).insert(value);
+            let Some(effect) = rx_world.entity_mut(observable).take::<RxDeferredEffect>() else {
                 return;
             };
 
-            let RxObservableData { data, subscribers } = value;
-            main_world.insert_resource(EffectData { value: data });
+            let mut deferred = rx_world.resource_mut::<RxDeferredEffects>();
+            let apply_commands = match deferred.commands {
+                EffectCommands::PerEffect => true,
+                EffectCommands::Batched => {
+                    deferred.ran.push(observable);
+                    false
+                }
+            };
+            let prepared = PreparedEffect::new(observable, effect, data, apply_commands);
+            if deferred.collecting {
+                deferred.prepared.push(prepared);
+            } else {
+                prepared.run(main_world, rx_world);
+            }
+        })
+    }
+
+    /// Queue the multi-input effect on the `effect` entity, see [`Effect::new_multi`].
+    fn push_multi<D: ObservableTuple + Send + Sync + 'static>(&mut self, effect: Entity)
+    where
+        D::Values: Send + Sync + 'static,
+    {
+        self.enqueue_fn(effect, Self::multi::<D>(effect, true));
+    }
 
-            effect.run(main_world);
+    /// Build the function that runs the multi-input `effect`, see [`RxDeferredEffects::single`].
+    fn multi<D: ObservableTuple + Send + Sync + 'static>(
+        effect: Entity,
+        scheduled: bool,
+    ) -> Box<EffectFn>
+    where
+        D::Values: Send + Sync + 'static,
+    {
+        let requeue = scheduled.then_some(Self::multi::<D> as EffectBuilder);
+        Self::effect_fn(effect, requeue, move |rx_world, effect| {
+            if scheduled {
+                RxMultiEffect::dequeue(rx_world, effect);
+            }
+            let inputs = rx_world.get::<RxMultiEffectInputs<D>>(effect)?.inputs;
+            let value = inputs.read_cloned(rx_world).ok()?;
+            let mut multi = rx_world.get_mut::<RxMultiEffect>(effect)?;
+            // An unscheduled run leaves the changes for the run that is already queued.
+            let changed = match scheduled {
+                true => std::mem::take(&mut multi.changed),
+                false => multi.changed.clone(),
+            };
+            Some(EffectData { value, changed })
+        })
+    }
 
-            // Return the observable data back into its original component:
-            let data = main_world
-                .remove_resource::<EffectData<T>>()
-                .expect("EffectData does not exist after running effect. Did you remove it?")
-                .value;
-            rx_world
-                .entity_mut(observable)
-                .insert(RxObservableData { data, subscribers });
+    /// Run the effect on the `effect` entity immediately, regardless of whether its observable
+    /// changed, or the effect is enabled. Returns `false` if there is no effect on the entity.
+    pub(crate) fn run_now(main_world: &mut World, rx_world: &mut World, effect: Entity) -> bool {
+        let Some(build) = rx_world
+            .get::<RxDeferredEffect>(effect)
+            .map(|effect| effect.build)
+        else {
+            return false;
+        };
+        build(effect, false)(main_world, rx_world);
+        // Outside of a flush, the only batched commands are those of this effect.
+        Self::apply_batched_commands(main_world, rx_world);
+        true
+    }
 
-            // Return the effect system back to its original component:
-            rx_world.entity_mut(observable).insert(effect);
-        });
-        self.stack.push(effect);
+    /// Pass the `error` returned by the fallible `effect` to the error handler.
+    fn handle_error(rx_world: &mut World, effect: Entity, error: EffectError) {
+        let effect = Effect {
+            reactor_entity: effect,
+        };
+        let mut deferred = rx_world.resource_mut::<RxDeferredEffects>();
+        match deferred.error_handler.as_mut() {
+            Some(handler) => handler(effect, error),
+            None => error!("Effect {:?} failed: {error}", effect.reactor_entity),
+        }
+    }
+
+    /// Run the queued effects, then any effects they queued in turn, until none are left or the
+    /// maximum number of passes is reached. Effects left over after the last pass, and effects held
+    /// back by their timing, run during the next flush.
+    pub(crate) fn run_queued(main_world: &mut World, rx_world: &mut World) {
+        main_world.init_resource::<RxEffectWrites>();
+        RxEffectWrites::apply(main_world, rx_world);
+        let mut passes = 0;
+        loop {
+            let mut deferred = rx_world.resource_mut::<RxDeferredEffects>();
+            if deferred.stack.is_empty() {
+                break;
+            }
+            if passes == deferred.max_passes {
+                warn!(
+                    "Effects were still queueing more effects after {passes} passes, the remaining \
+                    {} effects run during the next flush.",
+                    deferred.stack.len()
+                );
+                break;
+            }
+            passes += 1;
+            let mut effects = std::mem::take(&mut deferred.stack);
+            let parallel = deferred.executor == EffectExecutor::Parallel;
+            Self::run_pre_flush_hook(rx_world, &mut effects);
+            if parallel {
+                Self::run_parallel(main_world, rx_world, effects);
+                continue;
+            }
+            for (_, effect) in effects.drain(..) {
+                effect(main_world, rx_world);
+                RxEffectWrites::apply(main_world, rx_world);
+            }
+        }
+        let mut deferred = rx_world.resource_mut::<RxDeferredEffects>();
+        let held_back = std::mem::take(&mut deferred.held_back);
+        deferred.stack.splice(0..0, held_back);
+    }
+
+    /// Run the effects of a pass with [`EffectExecutor::Parallel`]. Effects are prepared in order,
+    /// and the prepared batch runs whenever an effect that is already in it is reached again.
+    fn run_parallel(
+        main_world: &mut World,
+        rx_world: &mut World,
+        effects: Vec<(Entity, Box<EffectFn>)>,
+    ) {
+        let mut batched = HashSet::new();
+        for (entity, effect) in effects {
+            if !batched.insert(entity) {
+                Self::run_prepared(main_world, rx_world);
+                batched.clear();
+                batched.insert(entity);
+            }
+            rx_world.resource_mut::<RxDeferredEffects>().collecting = true;
+            effect(main_world, rx_world);
+            rx_world.resource_mut::<RxDeferredEffects>().collecting = false;
+        }
+        Self::run_prepared(main_world, rx_world);
+    }
+
+    /// Run the prepared effects in waves of effects that don't conflict, in the order they were
+    /// queued.
+    fn run_prepared(main_world: &mut World, rx_world: &mut World) {
+        let prepared = std::mem::take(&mut rx_world.resource_mut::<RxDeferredEffects>().prepared);
+        let mut wave = Vec::new();
+        for mut effect in prepared {
+            let fits = effect.prepare_parallel(main_world)
+                && wave.iter().all(|other| effect.is_compatible(other));
+            if !fits {
+                PreparedEffect::run_wave(&mut wave, main_world, rx_world);
+            }
+            wave.push(effect);
+        }
+        PreparedEffect::run_wave(&mut wave, main_world, rx_world);
+    }
+
+    /// Show the effects about to run in this pass to the pre-flush hook, if there is one, and run
+    /// them in the order the hook left them in. Effects the hook removed run after the others.
+    fn run_pre_flush_hook(rx_world: &mut World, effects: &mut Vec<(Entity, Box<EffectFn>)>) {
+        if rx_world
+            .resource::<RxDeferredEffects>()
+            .pre_flush_hook
+            .is_none()
+        {
+            return;
+        }
+        let mut pending: Vec<_> = effects
+            .iter()
+            .map(|&(effect, _)| PendingEffect {
+                effect: Effect {
+                    reactor_entity: effect,
+                },
+                name: rx_world
+                    .get::<RxDeferredEffect>(effect)
+                    .and_then(RxDeferredEffect::system)
+                    .map(|system| system.name()),
+            })
+            .collect();
+        let mut deferred = rx_world.resource_mut::<RxDeferredEffects>();
+        if let Some(hook) = deferred.pre_flush_hook.as_mut() {
+            hook(&mut pending);
+        }
+        // An effect can be queued more than once, so each entity maps to its queued runs in order.
+        let mut remaining: Vec<_> = effects.drain(..).map(Some).collect();
+        let mut positions = HashMap::<Entity, VecDeque<usize>>::default();
+        for (i, effect) in remaining.iter().flatten().enumerate() {
+            positions.entry(effect.0).or_default().push_back(i);
+        }
+        for pending in pending {
+            let entity = pending.effect.reactor_entity;
+            let next = positions.get_mut(&entity).and_then(VecDeque::pop_front);
+            if let Some(effect) = next.and_then(|i| remaining[i].take()) {
+                effects.push(effect);
+            }
+        }
+        effects.extend(remaining.into_iter().flatten());
+    }
+
+    /// Apply the commands of every effect that ran during this flush, in the order they ran.
+    pub(crate) fn apply_batched_commands(main_world: &mut World, rx_world: &mut World) {
+        let mut ran = std::mem::take(&mut rx_world.resource_mut::<RxDeferredEffects>().ran);
+        for observable in ran.drain(..) {
+            if let Some(mut effect) = rx_world.get_mut::<RxDeferredEffect>(observable) {
+                effect.system.apply_deferred(main_world);
+            }
+        }
+    }
+}
+
+/// Decides whether a change is significant enough to run an effect, given the last value the effect
+/// was queued with and the new value.
+type SignificanceFn<T> = dyn Fn(&T, &T) -> bool + Send + Sync;
+
+/// An optional filter stored alongside an [`RxDeferredEffect`], so the effect can ignore changes
+/// that subscribers still need to see, like tiny changes to a float.
+#[derive(Component)]
+pub(crate) struct RxSignificance<T> {
+    baseline: Option<T>,
+    is_significant: Box<SignificanceFn<T>>,
+}
+
+impl<T: Clone + Send + Sync + 'static> RxSignificance<T> {
+    /// Returns `true` if the effect on `observable` should be queued for its current value,
+    /// updating the baseline if so. Effects without a filter are always queued.
+    fn check(rx_world: &mut World, observable: Entity) -> bool {
+        if rx_world.get::<Self>(observable).is_none() {
+            return true;
+        }
+        let Some(value) = rx_world
+            .get::<RxObservableData<T>>(observable)
+            .map(|data| data.data().clone())
+        else {
+            return false;
+        };
+        let Some(mut significance) = rx_world.get_mut::<Self>(observable) else {
+            return true;
+        };
+        let significant = match &significance.baseline {
+            Some(baseline) => (significance.is_significant)(baseline, &value),
+            None => true,
+        };
+        if significant {
+            significance.baseline = Some(value);
+        }
+        significant
     }
 }
 
 /// A resource that exists solely to allow [`Effect`]s to gain access to the data they are reacting
-/// to, without requiring that data implement `Clone`.
+/// to. It holds a copy of the observed value, taken when the effect runs.
 #[derive(Resource)]
 pub struct EffectData<T> {
     value: T,
+    changed: Vec<usize>,
 }
 
 impl<T> EffectData<T> {
     pub fn value(&self) -> &T {
         &self.value
     }
+
+    /// The indices of the inputs that changed since the effect last ran, in the order they changed.
+    /// For an effect on a single observable, this is always `[0]`. See [`Effect::new_multi`].
+    pub fn changed(&self) -> &[usize] {
+        &self.changed
+    }
+
+    /// Returns `true` if the input at `index` changed since the effect last ran, e.g. to only
+    /// re-render the parts of the UI that depend on it.
+    pub fn input_changed(&self, index: usize) -> bool {
+        self.changed.contains(&index)
+    }
 }
 
 impl<T> std::ops::Deref for EffectData<T> {
@@ -107,6 +827,194 @@ impl<T> std::ops::Deref for EffectData<T> {
     }
 }
 
+/// The node whose value a borrowing effect reads in place, see [`Effect::new_borrowed`].
+pub struct BorrowedValue<T> {
+    observable: Entity,
+    p: PhantomData<T>,
+}
+
+/// A system param to read the value observed by an effect created with [`Effect::new_borrowed`].
+/// The value is borrowed in place from the reactive world, instead of being cloned, so the effect
+/// can observe large values cheaply. The node keeps its value while the effect runs.
+#[derive(SystemParam)]
+pub struct EffectValue<'w, T: Send + Sync + 'static> {
+    world: Res<'w, RxReaderWorld>,
+    data: Res<'w, EffectData<BorrowedValue<T>>>,
+}
+
+impl<T: Send + Sync + 'static> EffectValue<'_, T> {
+    pub fn value(&self) -> &T {
+        RxObservableData::<T>::read(&self.world.0, self.data.value().observable)
+            .unwrap_or_else(|error| panic!("Failed to read observable: {error}"))
+    }
+}
+
+impl<T: Send + Sync + 'static> std::ops::Deref for EffectValue<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.value()
+    }
+}
+
+/// The multi-input effects that observe an input node, see [`Effect::new_multi`].
+#[derive(Component)]
+pub(crate) struct RxEffectWatchers {
+    effects: Vec<Entity>,
+}
+
+impl RxEffectWatchers {
+    /// Record that the `input` changed in every multi-input effect watching it, and queue the
+    /// effects that were not already queued.
+    pub(crate) fn input_changed(rx_world: &mut World, input: Entity) {
+        let Some(watchers) = rx_world.get::<Self>(input) else {
+            return;
+        };
+        let frame = rx_world.resource::<RxDeferredEffects>().frame;
+        for effect in watchers.effects.clone() {
+            let Some(mut multi) = rx_world.get_mut::<RxMultiEffect>(effect) else {
+                continue; // Disposed.
+            };
+            let queued = multi.queued;
+            let multi = &mut *multi;
+            for index in (0..multi.inputs.len()).filter(|i| multi.inputs[*i] == input) {
+                if !multi.changed.contains(&index) {
+                    multi.changed.push(index);
+                }
+            }
+            let queue = multi.queue;
+            if queued {
+                continue;
+            }
+            let Some(mut deferred_effect) = rx_world.get_mut::<RxDeferredEffect>(effect) else {
+                continue;
+            };
+            if deferred_effect.enabled && deferred_effect.schedule.notify(frame) {
+                queue(&mut rx_world.resource_mut::<RxDeferredEffects>(), effect);
+                if let Some(mut multi) = rx_world.get_mut::<RxMultiEffect>(effect) {
+                    multi.queued = true;
+                }
+            }
+        }
+    }
+}
+
+/// The state of an effect that observes several inputs, stored on the effect's own node.
+#[derive(Component)]
+pub(crate) struct RxMultiEffect {
+    inputs: Vec<Entity>,
+    /// The indices of the inputs that changed since the effect last ran.
+    changed: Vec<usize>,
+    /// Set while the effect is waiting to run, so it is only queued once per flush.
+    queued: bool,
+    queue: fn(&mut RxDeferredEffects, Entity),
+}
+
+impl RxMultiEffect {
+    /// Record that the queued run of the `effect` is running, or was dropped, if it is a
+    /// multi-input effect, so its next change queues it again.
+    fn dequeue(rx_world: &mut World, effect: Entity) {
+        if let Some(mut multi) = rx_world.get_mut::<Self>(effect) {
+            multi.queued = false;
+        }
+    }
+}
+
+/// The typed handles of the inputs of a multi-input effect, used to read their values.
+#[derive(Component)]
+struct RxMultiEffectInputs<D: Send + Sync + 'static> {
+    inputs: D,
+}
+
+/// The reactive world, lent to the main world while an effect runs, so it can be read through a
+/// [`ReactiveReader`]. Outside of effects, this holds an empty world.
+#[derive(Resource, Default)]
+pub(crate) struct RxReaderWorld(World);
+
+impl RxReaderWorld {
+    /// Move the `rx_world` into the `main_world` while `run` runs. The worlds are swapped rather
+    /// than taken, so no new world is allocated for every effect.
+    fn lend(main_world: &mut World, rx_world: &mut World, run: impl FnOnce(&mut World)) {
+        std::mem::swap(
+            &mut main_world.get_resource_or_insert_with(Self::default).0,
+            rx_world,
+        );
+        run(main_world);
+        std::mem::swap(&mut main_world.resource_mut::<Self>().0, rx_world);
+    }
+}
+
+/// A system param to read the current value of any observable from inside an effect, e.g. to
+/// combine the value the effect reacts to with other reactive state.
+///
+/// Reading is only possible while an effect runs. Elsewhere, every read fails with
+/// [`RxError::NodeDisposed`]. To send signals from an effect, see [`ReactiveWriter`].
+#[derive(SystemParam)]
+pub struct ReactiveReader<'w> {
+    world: Res<'w, RxReaderWorld>,
+}
+
+impl ReactiveReader<'_> {
+    /// See [`ReactiveContext::read`].
+    pub fn read<O: Observable>(&self, observable: O) -> &O::DataType {
+        self.try_read(observable)
+            .unwrap_or_else(|error| panic!("Failed to read observable: {error}"))
+    }
+
+    /// See [`ReactiveContext::try_read`].
+    pub fn try_read<O: Observable>(&self, observable: O) -> Result<&O::DataType, RxError> {
+        RxObservableData::read(&self.world.0, observable.reactive_entity())
+    }
+}
+
+/// Signal writes made by effects through a [`ReactiveWriter`], stored in the main world until the
+/// effect has run.
+#[derive(Resource, Default)]
+pub(crate) struct RxEffectWrites(Vec<Box<PendingWrite>>);
+
+impl RxEffectWrites {
+    /// Apply the writes made by the effect that just ran. The effects they queue run in the next
+    /// pass of the flush.
+    fn apply(main_world: &mut World, rx_world: &mut World) {
+        let Some(mut writes) = main_world.get_resource_mut::<Self>() else {
+            return;
+        };
+        for write in std::mem::take(&mut writes.0) {
+            traversal::send(rx_world, write);
+        }
+    }
+}
+
+/// A system param to send signals from inside an effect, e.g. to start the next stage of a
+/// reaction that spans several effects.
+///
+/// Writes are applied once the effect has run, and the effects they queue run in the next pass of
+/// the same flush, see [`ReactiveContext::set_max_effect_passes`]. Writes made outside of an
+/// effect are applied at the start of the next flush.
+#[derive(SystemParam)]
+pub struct ReactiveWriter<'w> {
+    writes: ResMut<'w, RxEffectWrites>,
+}
+
+impl ReactiveWriter<'_> {
+    /// See [`ReactiveContext::send_signal`]. The write is skipped with a warning if the signal has
+    /// been connected to a memo, see [`ReactiveContext::connect`].
+    pub fn send<T: Clone + PartialEq + Send + Sync + 'static>(
+        &mut self,
+        signal: Signal<T>,
+        value: T,
+    ) {
+        let signal = signal.reactive_entity();
+        self.writes.0.push(Box::new(move |world, stack| {
+            if let Err(error) = RxMemoNode::check_write(world, signal) {
+                warn!("Skipped a signal write from an effect: {error}");
+                return;
+            }
+            RxObservableData::update_value(world, stack, signal, value);
+        }));
+    }
+}
+
 /// A side effect applied to the main world at a deferred sync point, as a reaction to some value
 /// changing.
 ///
@@ -115,17 +1023,47 @@ impl<T> std::ops::Deref for EffectData<T> {
 #[derive(Debug, Component)]
 pub(crate) struct RxDeferredEffect {
     pub(crate) system: EffectSystem,
+    /// Builds the function that runs this effect with the data it observes.
+    build: EffectBuilder,
+    pub(crate) schedule: EffectSchedule,
+    /// Disabled effects are not run when their observable changes.
+    pub(crate) enabled: bool,
+    /// Set for fallible effects, see [`Effect::new_fallible`].
+    error: Option<ErrorSlot>,
 }
 
 impl RxDeferredEffect {
-    pub(crate) fn new<M>(system: impl IntoSystem<(), (), M>) -> Self {
+    pub(crate) fn new<T: Clone + PartialEq + Send + Sync + 'static, M>(
+        system: impl IntoSystem<(), (), M>,
+    ) -> Self {
+        Self::with_builder(system, RxDeferredEffects::single::<T>)
+    }
+
+    fn with_builder<M>(system: impl IntoSystem<(), (), M>, build: EffectBuilder) -> Self {
         Self {
             system: EffectSystem::new(system),
+            build,
+            schedule: EffectSchedule::default(),
+            enabled: true,
+            error: None,
         }
     }
 
-    pub(crate) fn run(&mut self, main_world: &mut World) {
-        self.system.run(main_world);
+    pub(crate) fn with_timing(mut self, timing: EffectTiming) -> Self {
+        self.schedule.timing = timing;
+        self
+    }
+
+    pub(crate) fn run(&mut self, main_world: &mut World, apply_commands: bool) {
+        self.system.run(main_world, apply_commands);
+    }
+
+    /// The error returned by the last run of a fallible effect.
+    fn take_error(&self) -> Option<EffectError> {
+        let slot = self.error.as_ref()?;
+        slot.lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .take()
     }
 
     pub fn system(&self) -> Option<&dyn System<In = (), Out = ()>> {
@@ -136,6 +1074,212 @@ impl RxDeferredEffect {
     }
 }
 
+/// Controls how often an effect is allowed to run when the value it observes changes rapidly.
+/// Frames are counted as flushes of the deferred effects, which the plugin runs once per frame.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EffectTiming {
+    /// Run at the next flush after every change.
+    #[default]
+    Immediate,
+    /// Run only once the observed value has stopped changing for this many frames.
+    Debounce(u32),
+    /// Run at most once every this many frames, while the observed value keeps changing.
+    Throttle(u32),
+}
+
+/// Tracks when an effect was last triggered and run, to implement [`EffectTiming`].
+#[derive(Default, Debug)]
+pub(crate) struct EffectSchedule {
+    timing: EffectTiming,
+    queued: bool,
+    last_changed: u64,
+    last_run: Option<u64>,
+}
+
+impl EffectSchedule {
+    /// Record that the observed value changed, returning `true` if the effect needs to be queued.
+    fn notify(&mut self, frame: u64) -> bool {
+        self.last_changed = frame;
+        match self.timing {
+            EffectTiming::Immediate => true,
+            // Rate limited effects are queued once, and read the latest value when they run.
+            EffectTiming::Debounce(_) | EffectTiming::Throttle(_) => {
+                !std::mem::replace(&mut self.queued, true)
+            }
+        }
+    }
+
+    /// Returns `true` if a queued effect should run during the flush of this `frame`.
+    fn ready(&mut self, frame: u64) -> bool {
+        let ready = match self.timing {
+            EffectTiming::Immediate => true,
+            EffectTiming::Debounce(frames) => frame - self.last_changed > frames as u64,
+            EffectTiming::Throttle(frames) => self
+                .last_run
+                .map_or(true, |last_run| frame - last_run >= frames as u64),
+        };
+        if ready {
+            self.queued = false;
+            self.last_run = Some(frame);
+        }
+        ready
+    }
+}
+
+type InsertData = dyn FnOnce(&mut World) + Send + Sync;
+
+/// An effect that is ready to run, holding the data it observes. With
+/// [`EffectExecutor::Parallel`], the effects of a pass are prepared first and run together.
+pub(crate) struct PreparedEffect {
+    observable: Entity,
+    effect: RxDeferredEffect,
+    apply_commands: bool,
+    /// The type of the [`EffectData`] the effect reads, only one of each can be inserted at once.
+    data_type: TypeId,
+    data_type_name: &'static str,
+    /// Inserts the [`EffectData`] into the main world, taken once it has been inserted.
+    insert_data: Option<Box<InsertData>>,
+    remove_data: fn(&mut World),
+    /// Set if the system can run alongside others, see [`PreparedEffect::prepare_parallel`].
+    parallel: bool,
+}
+
+impl PreparedEffect {
+    fn new<T: Send + Sync + 'static>(
+        observable: Entity,
+        effect: RxDeferredEffect,
+        data: EffectData<T>,
+        apply_commands: bool,
+    ) -> Self {
+        Self {
+            observable,
+            effect,
+            apply_commands,
+            data_type: TypeId::of::<T>(),
+            data_type_name: type_name::<T>(),
+            insert_data: Some(Box::new(move |main_world| main_world.insert_resource(data))),
+            remove_data: |main_world| {
+                main_world.remove_resource::<EffectData<T>>();
+            },
+            parallel: false,
+        }
+    }
+
+    fn insert_data(&mut self, main_world: &mut World) {
+        if let Some(insert) = self.insert_data.take() {
+            insert(main_world);
+        }
+    }
+
+    /// Run the effect on its own.
+    fn run(mut self, main_world: &mut World, rx_world: &mut World) {
+        self.insert_data(main_world);
+        RxReaderWorld::lend(main_world, rx_world, |main_world| {
+            self.effect.run(main_world, self.apply_commands);
+        });
+        self.finish(main_world, rx_world);
+    }
+
+    /// Initialize the system and update its access, returning `true` if it can run alongside other
+    /// systems.
+    fn prepare_parallel(&mut self, main_world: &mut World) -> bool {
+        self.parallel = match self.effect.system.initialized(main_world) {
+            Some(system) if !system.is_exclusive() && system.is_send() => {
+                system.update_archetype_component_access(main_world.as_unsafe_world_cell());
+                true
+            }
+            _ => false,
+        };
+        self.parallel
+    }
+
+    /// Returns `true` if both effects can run at the same time, see [`Self::prepare_parallel`].
+    fn is_compatible(&self, other: &PreparedEffect) -> bool {
+        let (Some(system), Some(other_system)) =
+            (self.effect.system.get(), other.effect.system.get())
+        else {
+            return false;
+        };
+        self.parallel
+            && other.parallel
+            && self.data_type != other.data_type
+            && system
+                .archetype_component_access()
+                .is_compatible(other_system.archetype_component_access())
+    }
+
+    /// Remove the effect's data, return its system to the reactive world, and record that it ran.
+    fn finish(self, main_world: &mut World, rx_world: &mut World) {
+        let Self {
+            observable,
+            effect,
+            data_type,
+            data_type_name,
+            remove_data,
+            ..
+        } = self;
+        remove_data(main_world);
+        let error = effect.take_error();
+
+        // Return the effect system back to its original component:
+        rx_world.entity_mut(observable).insert(effect);
+        if let Some(error) = error {
+            RxDeferredEffects::handle_error(rx_world, observable, error);
+        }
+        #[cfg(feature = "tracking")]
+        crate::tracker::RecomputeTracker::effect_ran(rx_world, observable);
+
+        let value = RxDebugValue::format(rx_world, observable);
+        rx_world
+            .resource_mut::<RxDeferredEffects>()
+            .fired
+            .push(FiredEffect {
+                effect: Effect {
+                    reactor_entity: observable,
+                },
+                type_id: data_type,
+                type_name: data_type_name,
+                value,
+            });
+    }
+
+    /// Run a wave of mutually compatible effects at the same time on the [`ComputeTaskPool`],
+    /// then apply their commands and writes in the order they were queued.
+    fn run_wave(wave: &mut Vec<PreparedEffect>, main_world: &mut World, rx_world: &mut World) {
+        if wave.len() <= 1 {
+            if let Some(effect) = wave.pop() {
+                effect.run(main_world, rx_world);
+                RxEffectWrites::apply(main_world, rx_world);
+            }
+            return;
+        }
+        for effect in wave.iter_mut() {
+            effect.insert_data(main_world);
+        }
+        RxReaderWorld::lend(main_world, rx_world, |main_world| {
+            let world = main_world.as_unsafe_world_cell();
+            ComputeTaskPool::get_or_init(TaskPool::default).scope(|scope| {
+                for effect in wave.iter_mut() {
+                    let Some(system) = effect.effect.system.get_mut() else {
+                        continue;
+                    };
+                    // SAFETY: every system in the wave is `Send` and not exclusive, their access
+                    // was updated for this world and is compatible with the others in the wave,
+                    // and nothing else accesses the world until the scope ends.
+                    scope.spawn(async move { unsafe { system.run_unsafe((), world) } });
+                }
+            });
+        });
+        for mut effect in wave.drain(..) {
+            if effect.apply_commands {
+                effect.effect.system.apply_deferred(main_world);
+            }
+            effect.finish(main_world, rx_world);
+        }
+        RxEffectWrites::apply(main_world, rx_world);
+    }
+}
+
 #[derive(Default, Debug)]
 pub(crate) enum EffectSystem {
     #[default]
@@ -149,17 +1293,48 @@ impl EffectSystem {
         Self::New(Box::new(IntoSystem::into_system(system)))
     }
 
-    pub(crate) fn run(&mut self, world: &mut World) {
-        let mut system = match std::mem::take(self) {
-            EffectSystem::Empty => return,
-            EffectSystem::New(mut system) => {
-                system.initialize(world);
-                system
-            }
-            EffectSystem::Initialized(system) => system,
+    /// Run the system, applying its commands immediately if `apply_commands` is set.
+    pub(crate) fn run(&mut self, world: &mut World, apply_commands: bool) {
+        let Some(system) = self.initialized(world) else {
+            return;
         };
         system.run((), world);
-        system.apply_deferred(world);
-        *self = EffectSystem::Initialized(system);
+        if apply_commands {
+            system.apply_deferred(world);
+        }
+    }
+
+    /// The system, initialized on first use.
+    fn initialized(&mut self, world: &mut World) -> Option<&mut BoxedSystem> {
+        if let EffectSystem::New(_) = self {
+            let EffectSystem::New(mut system) = std::mem::take(self) else {
+                unreachable!()
+            };
+            system.initialize(world);
+            *self = EffectSystem::Initialized(system);
+        }
+        self.get_mut()
+    }
+
+    /// The system, if it has been initialized.
+    fn get(&self) -> Option<&BoxedSystem> {
+        match self {
+            EffectSystem::Initialized(system) => Some(system),
+            _ => None,
+        }
+    }
+
+    fn get_mut(&mut self) -> Option<&mut BoxedSystem> {
+        match self {
+            EffectSystem::Initialized(system) => Some(system),
+            _ => None,
+        }
+    }
+
+    /// Apply commands that were issued by the system, but not applied when it ran.
+    pub(crate) fn apply_deferred(&mut self, world: &mut World) {
+        if let EffectSystem::Initialized(system) = self {
+            system.apply_deferred(world);
+        }
     }
 }
@@ -0,0 +1,77 @@
+use std::fmt;
+
+use bevy_ecs::{entity::Entity, system::Resource, world::World};
+
+use crate::node_id::NodeId;
+
+/// Errors that can occur when accessing reactive data through the
+/// [`ReactiveContext`](crate::ReactiveContext).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RxError {
+    /// The reactive node no longer exists, e.g. after [`crate::ReactiveContext::clear`].
+    NodeDisposed(Entity),
+    /// The memo exists, but its calculation has never produced a value.
+    NeverComputed(Entity),
+    /// A memo was sent a value from outside of its calculation, desyncing it from its inputs.
+    MemoWrite(Entity),
+    /// The operation would make the memo depend on itself, directly or through other nodes.
+    Cycle(Entity),
+    /// The node holds data of a different type than the one it was accessed with.
+    TypeMismatch {
+        entity: Entity,
+        expected: &'static str,
+        found: &'static str,
+    },
+    /// No node has been assigned this id with [`crate::ReactiveContext::assign_id`].
+    UnknownId(NodeId),
+}
+
+impl fmt::Display for RxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RxError::NodeDisposed(entity) => {
+                write!(f, "reactive node {entity:?} has been disposed")
+            }
+            RxError::NeverComputed(entity) => write!(
+                f,
+                "memo {entity:?} has never computed a value, because one of its inputs had no value"
+            ),
+            RxError::MemoWrite(entity) => write!(
+                f,
+                "memo {entity:?} can only be written by its own calculation, not sent a value"
+            ),
+            RxError::Cycle(entity) => write!(f, "memo {entity:?} would depend on itself"),
+            RxError::TypeMismatch {
+                entity,
+                expected,
+                found,
+            } => write!(
+                f,
+                "reactive node {entity:?} holds `{found}`, but was accessed as `{expected}`"
+            ),
+            RxError::UnknownId(id) => write!(f, "no reactive node has been assigned {id:?}"),
+        }
+    }
+}
+
+impl std::error::Error for RxError {}
+
+/// Receives the errors that would otherwise panic while changes propagate, see
+/// [`crate::ReactiveContext::set_panic_hook`].
+#[derive(Resource)]
+pub(crate) struct RxPanicHook(Box<dyn Fn(RxError) + Send + Sync>);
+
+impl RxPanicHook {
+    pub(crate) fn new(hook: impl Fn(RxError) + Send + Sync + 'static) -> Self {
+        Self(Box::new(hook))
+    }
+
+    /// Report an `error` the graph can't continue from. Without a hook this panics with the
+    /// `message` and the error, otherwise the hook is called and the caller skips the failed work.
+    pub(crate) fn report(rx_world: &World, message: &str, error: RxError) {
+        match rx_world.get_resource::<Self>() {
+            Some(hook) => (hook.0)(error),
+            None => panic!("{message}: {error}"),
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use bevy_ecs::prelude::*;
+
+use crate::{signal::Signal, ReactiveContext};
+
+/// The signal that bevy events of type `E` are forwarded to.
+#[derive(Resource)]
+pub(crate) struct RxEventSignal<E: Event>(pub(crate) Signal<Option<E>>);
+
+impl<E: Event + Clone + PartialEq> RxEventSignal<E> {
+    /// Get the signal for events of type `E`, creating it if it doesn't exist yet. Events are
+    /// forwarded to it by `ReactiveAppExt::add_event_signal`.
+    pub(crate) fn get_or_create<S>(rctx: &mut ReactiveContext<S>) -> Signal<Option<E>> {
+        if let Some(signal) = rctx.reactive_state.get_resource::<Self>() {
+            return signal.0;
+        }
+        let signal = rctx.new_signal(None);
+        rctx.reactive_state.insert_resource(Self(signal));
+        signal
+    }
+
+    /// Send the last event of type `E` received this frame to its signal, if one was created.
+    #[cfg(feature = "bevy_app")]
+    pub(crate) fn forward_events(
+        mut events: EventReader<E>,
+        mut rctx: ResMut<ReactiveContext<World>>,
+    ) {
+        let Some(event) = events.read().last().cloned() else {
+            return;
+        };
+        let Some(signal) = rctx.reactive_state.get_resource::<Self>().map(|s| s.0) else {
+            return;
+        };
+        rctx.send_signal(signal, Some(event));
+    }
+}
@@ -0,0 +1,85 @@
+use std::collections::VecDeque;
+
+use bevy_ecs::prelude::*;
+
+use crate::observable::RxObservableData;
+
+/// A bounded undo/redo history of the values of a signal, stored alongside its
+/// [`RxObservableData`]. Values are only recorded when a write actually changes the signal.
+#[derive(Component)]
+pub(crate) struct RxHistory<T> {
+    past: VecDeque<T>,
+    future: Vec<T>,
+    capacity: usize,
+}
+
+impl<T: Clone + PartialEq + Send + Sync + 'static> RxHistory<T> {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            past: VecDeque::with_capacity(capacity),
+            future: Vec::new(),
+            capacity,
+        }
+    }
+
+    /// Record the value that was just replaced by a new write.
+    pub(crate) fn record(&mut self, previous: T) {
+        self.push_past(previous);
+        // A new write starts a new branch of history, which can't be redone into.
+        self.future.clear();
+    }
+
+    fn push_past(&mut self, value: T) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.past.len() == self.capacity {
+            self.past.pop_front();
+        }
+        self.past.push_back(value);
+    }
+
+    /// Step the signal on `entity` one value back in its history, returning `false` if there is
+    /// nothing to undo.
+    pub(crate) fn undo(rx_world: &mut World, entity: Entity) -> bool {
+        Self::step(rx_world, entity, |history, current| {
+            let previous = history.past.pop_back()?;
+            history.future.push(current);
+            Some(previous)
+        })
+    }
+
+    /// Step the signal on `entity` one value forward in its history, returning `false` if there is
+    /// nothing to redo.
+    pub(crate) fn redo(rx_world: &mut World, entity: Entity) -> bool {
+        Self::step(rx_world, entity, |history, current| {
+            let next = history.future.pop()?;
+            history.push_past(current);
+            Some(next)
+        })
+    }
+
+    fn step(
+        rx_world: &mut World,
+        entity: Entity,
+        step_fn: impl FnOnce(&mut Self, T) -> Option<T>,
+    ) -> bool {
+        let Some(current) = rx_world
+            .get::<RxObservableData<T>>(entity)
+            .map(|observable| observable.data().clone())
+        else {
+            return false;
+        };
+        // The history is removed while the value is sent, so the write is not recorded.
+        let Some(mut history) = rx_world.entity_mut(entity).take::<Self>() else {
+            return false;
+        };
+        let value = step_fn(&mut history, current);
+        let stepped = value.is_some();
+        if let Some(value) = value {
+            RxObservableData::send_signal_unmapped(rx_world, entity, value);
+        }
+        rx_world.entity_mut(entity).insert(history);
+        stepped
+    }
+}
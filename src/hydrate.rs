@@ -0,0 +1,121 @@
+//! Setting many signals to authoritative values at once, e.g. when loading a save or syncing with
+//! a server, see [`ReactiveContext::hydrate`].
+
+use bevy_ecs::prelude::*;
+use bevy_utils::HashSet;
+
+use crate::{
+    effect::RxDeferredEffects,
+    error::RxError,
+    memo::RxMemoNode,
+    observable::{Observable, RxObservableData},
+    signal::Signal,
+    traversal, ReactiveContext,
+};
+
+/// What happens to the effects and callbacks triggered by a [`ReactiveContext::hydrate`] pass.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HydrateEffects {
+    /// No effects are queued and no callbacks run, so the hydrated values are never sent back out,
+    /// e.g. as network messages.
+    #[default]
+    Suppress,
+    /// Effects are queued as usual, and run at the next flush, and callbacks run as the graph is
+    /// recomputed.
+    Queue,
+}
+
+/// Notifies the subscribers, callbacks and effects of a node after its value changed.
+type NotifyFn = fn(&mut World, &mut Vec<Entity>, Entity);
+
+/// The signals written during a [`ReactiveContext::hydrate`] pass. Writes update the signal
+/// immediately, but nothing that depends on it is recomputed until the pass ends.
+pub struct Hydration<'a> {
+    rx_world: &'a mut World,
+    changed: Vec<(Entity, NotifyFn)>,
+}
+
+impl Hydration<'_> {
+    /// Set the value of `signal`. Returns `true` if the value changed.
+    pub fn set<T: Clone + Send + Sync + PartialEq + 'static>(
+        &mut self,
+        signal: Signal<T>,
+        value: T,
+    ) -> bool {
+        let entity = signal.reactive_entity();
+        RxMemoNode::assert_write(self.rx_world, entity);
+        let changed = RxObservableData::set_silent(self.rx_world, entity, value);
+        if changed && !self.changed.iter().any(|(e, _)| *e == entity) {
+            self.changed
+                .push((entity, RxObservableData::<T>::notify_changed));
+        }
+        changed
+    }
+
+    /// Returns a reference to the current value of `observable`. Signals already reflect the values
+    /// set in this pass, but memos are not recomputed until it ends.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the observable has no value.
+    pub fn read<T: Send + Sync + PartialEq + 'static, O: Observable<DataType = T>>(
+        &self,
+        observable: O,
+    ) -> &T {
+        self.try_read(observable)
+            .unwrap_or_else(|error| panic!("Failed to read observable: {error}"))
+    }
+
+    /// Returns a reference to the current value of `observable`, or an error if it has no value.
+    /// See [`Hydration::read`].
+    pub fn try_read<T: Send + Sync + PartialEq + 'static, O: Observable<DataType = T>>(
+        &self,
+        observable: O,
+    ) -> Result<&T, RxError> {
+        RxObservableData::read(self.rx_world, observable.reactive_entity())
+    }
+
+    /// Recompute everything that depends on the changed signals in a single traversal.
+    fn finish(self, effects: HydrateEffects) {
+        let Hydration { rx_world, changed } = self;
+        if changed.is_empty() {
+            return;
+        }
+        let suppress = effects == HydrateEffects::Suppress;
+        let previous = std::mem::replace(
+            &mut rx_world.resource_mut::<RxDeferredEffects>().suppressed,
+            suppress,
+        );
+        traversal::send(rx_world, move |world, stack| {
+            for (entity, notify) in changed {
+                notify(world, stack, entity);
+            }
+            // A node that depends on several of the signals is only recomputed once.
+            let mut seen = HashSet::default();
+            stack.retain(|subscriber| seen.insert(*subscriber));
+        });
+        rx_world.resource_mut::<RxDeferredEffects>().suppressed = previous;
+    }
+}
+
+impl<S> ReactiveContext<S> {
+    /// Set many signals to authoritative values, like those loaded from a save or received from a
+    /// server, and recompute the graph once at the end. Signals set with [`Hydration::set`] are
+    /// updated silently, and when `hydrate` returns, every node that depends on them is recomputed
+    /// in a single traversal.
+    ///
+    /// With [`HydrateEffects::Suppress`], no effects or callbacks fire for the whole pass.
+    pub fn hydrate<R>(
+        &mut self,
+        effects: HydrateEffects,
+        hydrate: impl FnOnce(&mut Hydration) -> R,
+    ) -> R {
+        let mut hydration = Hydration {
+            rx_world: &mut self.reactive_state,
+            changed: Vec::new(),
+        };
+        let result = hydrate(&mut hydration);
+        hydration.finish(effects);
+        result
+    }
+}
@@ -0,0 +1,67 @@
+//! Viewing reactive values in reflection based tools, like `bevy-inspector-egui`.
+//!
+//! The reactive graph lives in its own world, which inspectors can't see. Spawning an [`Inspected`]
+//! component in the main world mirrors the value of an observable into it every frame, once
+//! [`ReactiveAppExt::inspect`](crate::ReactiveAppExt::inspect) has been added to the app for its
+//! type.
+
+use bevy_ecs::prelude::*;
+use bevy_reflect::{FromReflect, GetTypeRegistration, Reflect, TypePath};
+
+use crate::{
+    observable::{Observable, RxObservableData},
+    ReactiveContext,
+};
+
+/// A read-only, reflected copy of the value of an observable, updated once per frame in
+/// `PostUpdate`. Edits made to the copy, e.g. in an inspector, are overwritten on the next update.
+#[derive(Component, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Component)]
+pub struct Inspected<T: Reflect + FromReflect + TypePath + Clone + PartialEq> {
+    /// The node of the observable in the reactive world.
+    node: Entity,
+    /// The value of the observable, or `None` if it has no value or has been disposed.
+    value: Option<T>,
+}
+
+/// Required to reflect the component. The default doesn't point at any node, so it stays `None`.
+impl<T: Reflect + FromReflect + TypePath + Clone + PartialEq> Default for Inspected<T> {
+    fn default() -> Self {
+        Self {
+            node: Entity::PLACEHOLDER,
+            value: None,
+        }
+    }
+}
+
+impl<T> Inspected<T>
+where
+    T: Reflect + FromReflect + TypePath + GetTypeRegistration + Clone + PartialEq,
+{
+    /// Mirror the value of `observable`. The value is `None` until the first update.
+    pub fn new(observable: impl Observable<DataType = T>) -> Self {
+        Self {
+            node: observable.reactive_entity(),
+            value: None,
+        }
+    }
+
+    /// The value of the observable as of the last update.
+    pub fn value(&self) -> Option<&T> {
+        self.value.as_ref()
+    }
+
+    /// Copy the current value of every inspected observable, only touching the components whose
+    /// value changed, so inspectors don't see a change every frame.
+    pub(crate) fn mirror(
+        rctx: Res<ReactiveContext<World>>,
+        mut inspected: Query<&mut Inspected<T>>,
+    ) {
+        for mut inspected in &mut inspected {
+            let value = RxObservableData::<T>::read(&rctx.reactive_state, inspected.node).ok();
+            if inspected.value.as_ref() != value {
+                inspected.value = value.cloned();
+            }
+        }
+    }
+}
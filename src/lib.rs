@@ -4,77 +4,102 @@
 /// relationships, and execute them synchronously. This makes it possible to (effectively) mutate
 /// reactive components in the bevy world, even when you don't have mutable access to them. We
 /// aren't breaking rust's aliasing rules because the components aren't actually being mutated -
-/// [`Signal`], [`Derived`] - are actually lightweight handles to data inside the resource.
+/// [`Signal`], [`Memo`] - are actually lightweight handles to data inside the resource.
 ///
 /// The only way to access the data is through the context, or using the slightly less verbose
-/// [`Reactor`] system param.
+/// [`Reactor`] system param, which derefs to the context so every method is available through it.
+/// Constructors are named after what they create, e.g. [`ReactiveContext::new_signal`],
+/// [`ReactiveContext::new_memo`], and [`ReactiveContext::new_deferred_effect`].
 ///
 /// This makes it possible to define a complex network of signals, derived values, and effects, and
 /// execute them reactively to completion without worrying about frame delays seen with event
 /// propagation or component mutation.
-use std::{
-    marker::PhantomData,
-    ops::{Deref, DerefMut},
-};
+///
+/// Every new value is diffed against the previous one. When a signal is sent a value equal to its
+/// current one, or a memo recomputes to an unchanged value, propagation stops at that node: none of
+/// its subscribers are recomputed, and none of its callbacks or effects run. This cutoff is
+/// guaranteed, and can be measured with [`ReactiveContext::propagation_counters`].
+use std::{hash::Hash, marker::PhantomData};
 
-use bevy_app::PostUpdate;
-use bevy_ecs::{prelude::*, system::SystemParam};
-use effect::{Effect, RxDeferredEffect, RxDeferredEffects};
-use memo::MemoQuery;
-use observable::{Observable, RxObservableData};
+use async_effect::RxAsyncWrites;
+use bevy_ecs::prelude::*;
+use builder::GraphBuilder;
+use callback::{RxCallbacks, Subscription};
+use component::RxTrackedComponents;
+use dispose::{DisposedInputs, RxDisposedInputs};
+use effect::{
+    Effect, EffectCommands, EffectError, EffectExecutor, EffectTiming, FiredEffect, PendingEffect,
+    RxDebugValue, RxDeferredEffect, RxDeferredEffects,
+};
+use error::{RxError, RxPanicHook};
+use events::RxEventSignal;
+use history::RxHistory;
+use lifecycle::RxLifecycle;
+use memo::{MemoOutputs, MemoQuery, RxMemo, RxMemoNode, RxScheduledMemos};
+use node_id::{NodeId, RxNodeIds};
+use observable::{Freshness, Hashed, Observable, ObservableTuple, RxObservableData, RxSubscribers};
 use prelude::Memo;
-use signal::Signal;
+use receiver::{ReceiverMode, RxReceivers};
+use scope::{RxScopes, ScopeId};
+use signal::{Pulse, RxCoalescedWrites, RxSignalReset, Signal};
+use snapshot::{RxSerializers, Snapshot};
+use stats::{GraphViolation, PropagationCounters, ReactiveStats};
+use system_memo::RxSystemMemo;
+use time::RxTimeSignals;
+use traversal::{RxTraversal, Traversal};
 
+mod async_effect;
+pub mod builder;
+pub mod callback;
+mod combinators;
+mod component;
+pub mod dispose;
 pub mod effect;
+pub mod error;
+mod events;
+mod history;
+pub mod hydrate;
+#[cfg(feature = "reflect")]
+pub mod inspect;
+mod lifecycle;
+pub mod list;
+#[cfg(feature = "local")]
+pub mod local;
 pub mod memo;
+pub mod node_id;
 pub mod observable;
+#[cfg(feature = "bevy_app")]
+mod plugin;
+#[cfg(feature = "profiling")]
+pub mod profiler;
+pub mod receiver;
+pub mod scope;
 pub mod signal;
+pub mod snapshot;
+pub mod stats;
+mod system_memo;
+mod time;
+#[cfg(feature = "tracking")]
+pub mod tracker;
+pub mod traversal;
 
-pub mod prelude {
-    pub use crate::{
-        memo::Memo, signal::Signal, ReactiveContext, ReactiveExtensionsPlugin, Reactor,
-    };
-}
-
-pub struct ReactiveExtensionsPlugin;
+pub use bevy_rx_macros::{rx, MemoQuery, Reactive};
 
-impl ReactiveExtensionsPlugin {
-    fn apply_deferred_effects(world: &mut World) {
-        world.resource_scope::<ReactiveContext<World>, _>(|world, mut rctx| {
-            let mut effects: Vec<_> = std::mem::take(
-                rctx.reactive_state
-                    .resource_mut::<RxDeferredEffects>()
-                    .stack
-                    .as_mut(),
-            );
-            for effect in effects.drain(..) {
-                effect(world, &mut rctx.reactive_state)
-            }
-        })
-    }
+/// Used by the code generated by the derive macros. Not part of the public API.
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::memo::{read_input as read, subscribe_input as subscribe};
+    pub use bevy_ecs::{entity::Entity, world::World};
 }
+#[cfg(feature = "bevy_app")]
+pub use plugin::{ReactiveAppExt, ReactiveExtensionsPlugin, Reactor};
 
-impl bevy_app::Plugin for ReactiveExtensionsPlugin {
-    fn build(&self, app: &mut bevy_app::App) {
-        app.init_resource::<ReactiveContext<World>>()
-            .add_systems(PostUpdate, Self::apply_deferred_effects);
-    }
-}
-
-/// A system param to make accessing the [`ReactiveContext`] less verbose.
-#[derive(SystemParam)]
-pub struct Reactor<'w>(ResMut<'w, ReactiveContext<World>>);
-impl<'w> Deref for Reactor<'w> {
-    type Target = ReactiveContext<World>;
-
-    fn deref(&self) -> &Self::Target {
-        &self.0
-    }
-}
-impl<'w> DerefMut for Reactor<'w> {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
-    }
+pub mod prelude {
+    pub use crate::{
+        error::RxError, memo::Memo, rx, signal::Signal, MemoQuery, Reactive, ReactiveContext,
+    };
+    #[cfg(feature = "bevy_app")]
+    pub use crate::{ReactiveAppExt, ReactiveExtensionsPlugin, Reactor};
 }
 
 /// Contains all reactive state. A bevy world is used because it makes it easy to store statically
@@ -89,6 +114,11 @@ impl<S> Default for ReactiveContext<S> {
     fn default() -> Self {
         let mut world = World::default();
         world.init_resource::<RxDeferredEffects>();
+        world.init_resource::<RxTraversal>();
+        world.init_resource::<RxCoalescedWrites>();
+        world.init_resource::<RxAsyncWrites>();
+        world.init_resource::<RxLifecycle>();
+        world.init_resource::<RxScheduledMemos>();
         Self {
             reactive_state: world,
             outside_state: PhantomData,
@@ -98,146 +128,4737 @@ impl<S> Default for ReactiveContext<S> {
 
 impl<S> ReactiveContext<S> {
     /// Returns a reference to the current value of the provided observable. The observable is any
-    /// reactive handle that has a value, like a [`Signal`] or a [`Derived`].
+    /// reactive handle that has a value, like a [`Signal`] or a [`Memo`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the observable has no value, see [`ReactiveContext::try_read`].
     pub fn read<T: Send + Sync + PartialEq + 'static, O: Observable<DataType = T>>(
         &mut self,
         observable: O,
     ) -> &T {
-        // get the obs data from the world
-        // add the reader to the obs data's subs
-        self.reactive_state
-            .get::<RxObservableData<T>>(observable.reactive_entity())
-            .unwrap()
-            .data()
+        self.try_read(observable)
+            .unwrap_or_else(|error| panic!("Failed to read observable: {error}"))
     }
 
-    /// Send a signal, and run the reaction graph to completion.
+    /// Returns a reference to the current value of the provided observable, or an error if the
+    /// observable has been disposed, e.g. after [`ReactiveContext::clear`], or is a memo that has
+    /// never computed a value. Stale lazy memos are computed first, see [`Memo::new_lazy`].
+    pub fn try_read<T: Send + Sync + PartialEq + 'static, O: Observable<DataType = T>>(
+        &mut self,
+        observable: O,
+    ) -> Result<&T, RxError> {
+        self.pull(observable.reactive_entity());
+        RxObservableData::read(&self.reactive_state, observable.reactive_entity())
+    }
+
+    /// Compute the `node` if it is a stale lazy memo, so it can be read.
+    fn pull(&mut self, node: Entity) {
+        RxMemo::pull(&mut self.reactive_state, node);
+    }
+
+    /// Returns a reference to the current value of the provided observable, and whether it changed
+    /// since it was last read with this method, e.g. to skip non-reactive work downstream when it
+    /// didn't. Other reads don't affect the status.
     ///
-    /// Potentially expensive operation that will write a value to this [`Signal`]`. This will cause
-    /// all reactive subscribers of this observable to recompute their own values, which can cause
-    /// all of its subscribers to recompute, etc.
-    pub fn send_signal<T: Clone + Send + Sync + PartialEq + 'static>(
+    /// # Panics
+    ///
+    /// Panics if the observable has no value, see [`ReactiveContext::try_read`].
+    pub fn read_with_status<T: Send + Sync + PartialEq + 'static, O: Observable<DataType = T>>(
         &mut self,
-        signal: Signal<T>,
-        value: T,
-    ) {
-        RxObservableData::send_signal(&mut self.reactive_state, signal.reactive_entity(), value)
+        observable: O,
+    ) -> (&T, Freshness) {
+        self.try_read_with_status(observable)
+            .unwrap_or_else(|error| panic!("Failed to read observable: {error}"))
     }
 
-    pub fn new_signal<T: Clone + Send + Sync + PartialEq + 'static>(
+    /// Returns the current value of the provided observable and whether it changed since it was
+    /// last read, or an error if it has no value. See [`ReactiveContext::read_with_status`].
+    pub fn try_read_with_status<
+        T: Send + Sync + PartialEq + 'static,
+        O: Observable<DataType = T>,
+    >(
         &mut self,
-        initial_value: T,
-    ) -> Signal<T> {
-        Signal::new(self, initial_value)
+        observable: O,
+    ) -> Result<(&T, Freshness), RxError> {
+        let entity = observable.reactive_entity();
+        self.pull(entity);
+        RxObservableData::<T>::read(&self.reactive_state, entity)?;
+        let changed_tick = self.last_changed_tick(observable).unwrap_or_default();
+        let freshness = Freshness::observe(&mut self.reactive_state, entity, changed_tick);
+        let value = RxObservableData::read(&self.reactive_state, entity)?;
+        Ok((value, freshness))
     }
 
-    pub fn new_memo<T: Clone + Send + Sync + PartialEq + 'static, C: MemoQuery<T> + 'static>(
+    /// Returns a clone of the current value of the provided observable, so the value can be used
+    /// without borrowing the context.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the observable has no value, see [`ReactiveContext::try_read`].
+    pub fn read_cloned<
+        T: Clone + Send + Sync + PartialEq + 'static,
+        O: Observable<DataType = T>,
+    >(
         &mut self,
-        calculation_query: C,
-        derive_fn: (impl Fn(C::Query<'_>) -> T + Send + Sync + Clone + 'static),
-    ) -> Memo<T> {
-        Memo::new(self, calculation_query, derive_fn)
+        observable: O,
+    ) -> T {
+        self.read(observable).clone()
     }
 
-    pub fn new_deferred_effect<M>(
+    /// Returns a clone of the current value of the provided observable, or an error if it has no
+    /// value. See [`ReactiveContext::try_read`].
+    pub fn try_read_cloned<
+        T: Clone + Send + Sync + PartialEq + 'static,
+        O: Observable<DataType = T>,
+    >(
         &mut self,
-        observable: impl Observable,
-        effect_system: impl IntoSystem<(), (), M>,
-    ) -> Effect {
-        Effect::new_deferred(self, observable, effect_system)
+        observable: O,
+    ) -> Result<T, RxError> {
+        self.try_read(observable).cloned()
     }
 
-    pub fn effect_system(&self, effect: Effect) -> Option<&dyn System<In = (), Out = ()>> {
-        self.reactive_state
-            .get::<RxDeferredEffect>(effect.reactor_entity)
-            .and_then(|effect| effect.system())
+    /// Returns `projection` applied to the current value of the observable, e.g. to read one field
+    /// of a large value. Unlike a memo, this creates no node and subscribes to nothing, so it is
+    /// meant for one-off reads that don't need to be cached or react to changes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the observable has no value, see [`ReactiveContext::try_read`].
+    pub fn projected_read<O: Observable, U>(
+        &self,
+        observable: O,
+        projection: impl FnOnce(&O::DataType) -> U,
+    ) -> U {
+        projection(self.read_ref(observable))
     }
-}
 
-mod test {
-    #[test]
-    fn basic() {
-        #[derive(Debug, Clone, PartialEq)]
-        struct Button {
-            active: bool,
-        }
-        impl Button {
-            pub const OFF: Self = Button { active: false };
-            pub const ON: Self = Button { active: true };
-        }
+    /// Returns `projection` applied to the current value of the observable, or an error if it has
+    /// no value. See [`ReactiveContext::projected_read`].
+    pub fn try_projected_read<O: Observable, U>(
+        &self,
+        observable: O,
+        projection: impl FnOnce(&O::DataType) -> U,
+    ) -> Result<U, RxError> {
+        self.try_read_ref(observable).map(projection)
+    }
 
-        #[derive(Debug, Clone, PartialEq)]
-        struct Lock {
-            unlocked: bool,
+    /// Returns references to the current values of two observables at once, which can't be done
+    /// with two calls to [`ReactiveContext::read`] because each borrows the context.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either observable has no value, see [`ReactiveContext::try_read`].
+    pub fn read_pair<A: Observable, B: Observable>(
+        &mut self,
+        a: A,
+        b: B,
+    ) -> (&A::DataType, &B::DataType) {
+        self.pull(a.reactive_entity());
+        self.pull(b.reactive_entity());
+        (self.read_ref(a), self.read_ref(b))
+    }
+
+    /// Returns references to the current values of two observables at once, or an error if either
+    /// has no value. See [`ReactiveContext::read_pair`].
+    pub fn try_read_pair<A: Observable, B: Observable>(
+        &mut self,
+        a: A,
+        b: B,
+    ) -> Result<(&A::DataType, &B::DataType), RxError> {
+        self.pull(a.reactive_entity());
+        self.pull(b.reactive_entity());
+        Ok((self.try_read_ref(a)?, self.try_read_ref(b)?))
+    }
+
+    /// Returns references to the current values of three observables at once. See
+    /// [`ReactiveContext::read_pair`].
+    pub fn read3<A: Observable, B: Observable, C: Observable>(
+        &mut self,
+        a: A,
+        b: B,
+        c: C,
+    ) -> (&A::DataType, &B::DataType, &C::DataType) {
+        for node in [
+            a.reactive_entity(),
+            b.reactive_entity(),
+            c.reactive_entity(),
+        ] {
+            self.pull(node);
         }
+        (self.read_ref(a), self.read_ref(b), self.read_ref(c))
+    }
 
-        impl Lock {
-            /// A lock will only unlock if both of its buttons are active
-            fn two_buttons(buttons: (&Button, &Button)) -> Self {
-                let unlocked = buttons.0.active && buttons.1.active;
-                println!("Recomputing lock. Unlocked: {unlocked}");
-                Self { unlocked }
-            }
+    /// Returns references to the current values of three observables at once, or an error if any
+    /// of them has no value. See [`ReactiveContext::read_pair`].
+    #[allow(clippy::type_complexity)]
+    pub fn try_read3<A: Observable, B: Observable, C: Observable>(
+        &mut self,
+        a: A,
+        b: B,
+        c: C,
+    ) -> Result<(&A::DataType, &B::DataType, &C::DataType), RxError> {
+        for node in [
+            a.reactive_entity(),
+            b.reactive_entity(),
+            c.reactive_entity(),
+        ] {
+            self.pull(node);
         }
+        Ok((
+            self.try_read_ref(a)?,
+            self.try_read_ref(b)?,
+            self.try_read_ref(c)?,
+        ))
+    }
 
-        let mut reactor = crate::ReactiveContext::<()>::default();
+    fn read_ref<O: Observable>(&self, observable: O) -> &O::DataType {
+        self.try_read_ref(observable)
+            .unwrap_or_else(|error| panic!("Failed to read observable: {error}"))
+    }
 
-        let button1 = reactor.new_signal(Button::OFF);
-        let button2 = reactor.new_signal(Button::OFF);
-        let lock1 = reactor.new_memo((button1, button2), &Lock::two_buttons);
-        assert!(!reactor.read(lock1).unlocked);
+    fn try_read_ref<O: Observable>(&self, observable: O) -> Result<&O::DataType, RxError> {
+        RxObservableData::read(&self.reactive_state, observable.reactive_entity())
+    }
 
-        let button3 = reactor.new_signal(Button::OFF);
-        let lock2 = reactor.new_memo((button1, button3), &Lock::two_buttons);
-        assert!(!reactor.read(lock2).unlocked);
+    /// Returns a clone of the current value of every observable in the tuple, e.g.
+    /// `let (a, b) = reactor.read_many((signal, memo));`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the observables have no value, see [`ReactiveContext::try_read`].
+    pub fn read_many<O: ObservableTuple>(&mut self, observables: O) -> O::Values {
+        self.try_read_many(observables)
+            .unwrap_or_else(|error| panic!("Failed to read observable: {error}"))
+    }
 
-        reactor.send_signal(button1, Button::ON); // Automatically recomputes lock1 & lock2!
-        reactor.send_signal(button2, Button::ON);
-        for _ in 0..1000 {
-            button1.send(&mut reactor, Button::ON); // diffing prevents triggering a recompute
+    /// Returns a clone of the current value of every observable in the tuple, or an error if any of
+    /// them has no value. See [`ReactiveContext::read_many`].
+    pub fn try_read_many<O: ObservableTuple>(
+        &mut self,
+        observables: O,
+    ) -> Result<O::Values, RxError> {
+        for node in observables.reactive_entities() {
+            self.pull(node);
         }
-        assert!(lock1.read(&mut reactor).unlocked);
+        observables.read_cloned(&self.reactive_state)
+    }
 
-        reactor.send_signal(button3, Button::ON);
-        assert!(reactor.read(lock2).unlocked);
+    /// Iterate over the reactive entity and current value of every observable (signals and memos)
+    /// holding data of type `T`.
+    pub fn iter_observables<T: Send + Sync + 'static>(&self) -> impl Iterator<Item = (Entity, &T)> {
+        self.reactive_state.iter_entities().filter_map(|entity| {
+            entity
+                .get::<RxObservableData<T>>()
+                .map(|observable| (entity.id(), observable.data()))
+        })
     }
 
-    #[test]
-    fn nested_derive() {
-        let mut reactor = crate::ReactiveContext::<()>::default();
+    /// A signal holding the most recent bevy event of type `E`, or `None` if no event has been
+    /// received yet. Calling this again returns the same signal.
+    ///
+    /// Events are only forwarded once `ReactiveAppExt::add_event_signal` has been added to the
+    /// app. If several events are received in one frame, only the last one is sent.
+    pub fn signal_from_events<E: Event + Clone + PartialEq>(&mut self) -> Signal<Option<E>> {
+        RxEventSignal::<E>::get_or_create(self)
+    }
 
-        let add = |n: (&f32, &f32)| n.0 + n.1;
+    /// A signal holding the most recent item received from a channel, or `None` until the first
+    /// item arrives, to bridge external event sources like websockets into the graph. `mode`
+    /// controls whether every item is propagated, or only the latest one since the last poll.
+    ///
+    /// The channel is polled once per frame in `PreUpdate` by the `ReactiveExtensionsPlugin`, or
+    /// by calling [`ReactiveContext::poll_receivers`]. It is dropped once its sender disconnects.
+    pub fn signal_from_receiver<T: Clone + Send + Sync + PartialEq + 'static>(
+        &mut self,
+        receiver: std::sync::mpsc::Receiver<T>,
+        mode: ReceiverMode,
+    ) -> Signal<Option<T>> {
+        RxReceivers::add(self, receiver, mode)
+    }
 
-        let n1 = reactor.new_signal(1.0);
-        let n2 = reactor.new_signal(10.0);
-        let n3 = reactor.new_signal(100.0);
+    /// Send the items waiting in the channels of [`ReactiveContext::signal_from_receiver`] to their
+    /// signals. This is called once per frame by the `ReactiveExtensionsPlugin`.
+    pub fn poll_receivers(&mut self) {
+        RxReceivers::poll(&mut self.reactive_state);
+    }
 
-        // The following derives use signals as inputs
-        let d1 = reactor.new_memo((n1, n2), add); // 1 + 10
-        let d2 = reactor.new_memo((n3, n2), add); // 100 + 10
+    /// Connect a subgraph that was built separately, like one returned by a library, to the rest
+    /// of the graph, by making its `input` signal follow the value of `source`. The input becomes a
+    /// memo of `source`, keeping the same node, so every memo and effect already derived from it
+    /// stays connected, and is updated in the same traversal as `source`. The returned memo is the
+    /// input's new handle, and the input can no longer be sent values.
+    ///
+    /// Nodes can't be moved between contexts. To follow an observable of another context, see
+    /// [`ReactiveContext::import`].
+    ///
+    /// Returns [`RxError::MemoWrite`] if the input has already been connected,
+    /// [`RxError::NodeDisposed`] if it has been disposed, and [`RxError::Cycle`] if `source`
+    /// depends on the input.
+    pub fn connect<T, O>(&mut self, source: O, input: Signal<T>) -> Result<Memo<T>, RxError>
+    where
+        T: Clone + PartialEq + Send + Sync + 'static,
+        O: Observable<DataType = T>,
+    {
+        Memo::connect(self, source, input)
+    }
 
-        // This derive uses other derives as inputs
-        let d3 = reactor.new_memo((d1, d2), add); // 11 + 110
-        assert_eq!(*reactor.read(d3), 121.0);
+    /// A signal in this context that follows the `observable` of another `source` context, so
+    /// memos and effects here can derive from it, e.g. a shared settings context feeding several
+    /// independent feature contexts.
+    ///
+    /// The signal starts with the current value of the observable. Changes are sent over a channel
+    /// as they propagate in `source`, and reach this context when its receivers are polled, see
+    /// [`ReactiveContext::poll_receivers`]. The source stops sending once the signal is disposed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the observable has no value, see [`ReactiveContext::try_read`].
+    pub fn import<S2, O: Observable>(
+        &mut self,
+        source: &mut ReactiveContext<S2>,
+        observable: O,
+    ) -> Signal<O::DataType>
+    where
+        O::DataType: Clone,
+    {
+        RxReceivers::import(self, source, observable)
     }
 
-    #[test]
-    fn many_types() {
-        #[derive(Debug, Clone, PartialEq)]
-        struct Foo(f32);
-        #[derive(Debug, Clone, PartialEq)]
-        struct Bar(f32);
-        #[derive(Debug, Clone, PartialEq)]
-        struct Baz(f32);
+    /// A signal holding the value extracted from the `C` component on `entity` in the main world,
+    /// or `None` until the component has been seen. Calling this again creates another signal.
+    ///
+    /// Changes are detected with bevy's change detection, once `ReactiveAppExt::track_components`
+    /// has been added to the app for `C`. Writes that don't change the extracted value are
+    /// suppressed by diffing, like any other signal.
+    pub fn track_component<C: Component, T: Clone + Send + Sync + PartialEq + 'static>(
+        &mut self,
+        entity: Entity,
+        extract: impl Fn(&C) -> T + Send + Sync + 'static,
+    ) -> Signal<Option<T>> {
+        RxTrackedComponents::<C>::track(self, entity, extract)
+    }
 
-        let mut reactor = crate::ReactiveContext::<()>::default();
+    /// A signal holding the time elapsed since the app started, updated once per frame in
+    /// `PreUpdate` by the `ReactiveExtensionsPlugin`. Memos that depend on it are recomputed every
+    /// frame while time advances, e.g. to animate values within the graph.
+    ///
+    /// Requires bevy's `Time` resource. Calling this again returns the same signal.
+    pub fn time_signal(&mut self) -> Signal<std::time::Duration> {
+        RxTimeSignals::elapsed(self)
+    }
 
-        let foo = reactor.new_signal(Foo(1.0));
-        let bar = reactor.new_signal(Bar(1.0));
+    /// A signal holding the number of frames since the app started, updated once per frame in
+    /// `PreUpdate` by the `ReactiveExtensionsPlugin`. Calling this again returns the same signal.
+    pub fn frame_signal(&mut self) -> Signal<u64> {
+        RxTimeSignals::frame(self)
+    }
 
-        let baz = reactor.new_memo((foo, bar), |(foo, bar)| Baz(foo.0 + bar.0));
+    /// The order in which subscribers are recomputed when a signal is sent.
+    pub fn traversal(&self) -> Traversal {
+        self.reactive_state.resource::<RxTraversal>().mode
+    }
 
-        assert_eq!(reactor.read(baz), &Baz(2.0));
+    /// Set the order in which subscribers are recomputed when a signal is sent. See [`Traversal`]
+    /// for the guarantees of each mode.
+    pub fn set_traversal(&mut self, mode: Traversal) {
+        self.reactive_state.resource_mut::<RxTraversal>().mode = mode;
+    }
+
+    /// Whether subscribers are recomputed in a deterministic order, see
+    /// [`ReactiveContext::set_deterministic`].
+    pub fn deterministic(&self) -> bool {
+        self.reactive_state.resource::<RxTraversal>().deterministic
+    }
+
+    /// Recompute the subscribers invalidated by each change in the order their nodes were created,
+    /// instead of the order they happen to be stored in, which depends on the history of the graph.
+    /// Two graphs built and updated the same way then recompute, and queue effects, in the same
+    /// order, making side effects and test output reproducible across runs.
+    ///
+    /// This sorts the subscribers of every changed node, which costs `O(n log n)` in the number of
+    /// subscribers, so it is off by default.
+    pub fn set_deterministic(&mut self, deterministic: bool) {
+        self.reactive_state
+            .resource_mut::<RxTraversal>()
+            .deterministic = deterministic;
+    }
+
+    /// Whether the graph is checked after every change, see [`ReactiveContext::set_strict`].
+    pub fn strict(&self) -> bool {
+        self.reactive_state.resource::<RxTraversal>().strict
+    }
+
+    /// Check the graph after every change and every new memo, panicking if it is broken, to catch
+    /// bugs during development. This runs [`ReactiveContext::debug_validate`] each time, which is
+    /// linear in the size of the graph, so it is off by default.
+    ///
+    /// Dangling subscribers and missing inputs are left behind by disposed nodes until their
+    /// inputs next change, so they are not reported. Memos created without a value panic instead of
+    /// logging a warning. Reading an observable as the wrong type already returns
+    /// [`RxError::TypeMismatch`], so reads are not checked again.
+    pub fn set_strict(&mut self, strict: bool) {
+        self.reactive_state.resource_mut::<RxTraversal>().strict = strict;
+    }
+
+    /// The maximum number of subscribers a single signal is allowed to recompute, or `None` if it
+    /// is unlimited, which is the default.
+    pub fn max_propagation_steps(&self) -> Option<usize> {
+        self.reactive_state.resource::<RxTraversal>().max_steps
+    }
+
+    /// Limit the number of subscribers a single signal is allowed to recompute. If a signal exceeds
+    /// this, propagation is stopped and a warning naming the next node is logged. The rest of the
+    /// graph is left stale, and is recomputed the next time a signal is sent. This protects against
+    /// runaway updates caused by a bug in a calculation.
+    pub fn set_max_propagation_steps(&mut self, max_steps: Option<usize>) {
+        self.reactive_state.resource_mut::<RxTraversal>().max_steps = max_steps;
+    }
+
+    /// List the memos that no other node or effect depends on. Memos that are only read through
+    /// their handle, or observed with [`ReactiveContext::on_change`], are included, so these are
+    /// candidates to check for a subtree that was created but never wired up, not necessarily bugs.
+    ///
+    /// Memos are resubscribed when recomputed, so a memo whose subscribers stopped reading it is
+    /// only reported once it has been recomputed.
+    pub fn dead_nodes(&self) -> Vec<Entity> {
+        stats::dead_nodes(&self.reactive_state)
+    }
+
+    /// Remove a signal or memo from the graph, along with any effect on it. Memos that depend on it
+    /// are recomputed, and react according to their [`DisposedInputs`] policy. Returns `false` if
+    /// the node was already disposed.
+    ///
+    /// The handle, and any copies of it, can no longer be read.
+    pub fn dispose(&mut self, observable: impl observable::AnyObservable) -> bool {
+        dispose::dispose(&mut self.reactive_state, observable.reactive_entity())
+    }
+
+    /// Run `build`, and tag every signal, memo, and effect it creates with a new scope, so they can
+    /// all be disposed of at once with [`ReactiveContext::dispose_scope`], e.g. when a UI screen is
+    /// closed. Scopes can be nested, and disposing a scope disposes the scopes created inside it.
+    ///
+    /// Nodes are found by comparing the graph before and after `build`, which is linear in the
+    /// size of the graph, so scopes are meant to group many nodes, not to wrap every one.
+    pub fn scope<R>(&mut self, build: impl FnOnce(&mut Self) -> R) -> (ScopeId, R) {
+        RxScopes::run(self, build)
+    }
+
+    /// Dispose of every node created in the `scope`, like [`ReactiveContext::dispose`], and remove
+    /// the effects it added to nodes outside of it. Returns the number of nodes disposed.
+    pub fn dispose_scope(&mut self, scope: ScopeId) -> usize {
+        RxScopes::dispose(&mut self.reactive_state, scope)
+    }
+
+    /// Set what `memo` does when one of its inputs is disposed. See [`DisposedInputs`].
+    pub fn set_disposed_inputs<T: Send + Sync + 'static>(
+        &mut self,
+        memo: Memo<T>,
+        policy: DisposedInputs,
+    ) {
+        self.reactive_state
+            .entity_mut(memo.reactor_entity)
+            .insert(RxDisposedInputs(policy));
+    }
+
+    /// Attach a component of your own to the `observable`'s node, e.g. a tag or metadata used to
+    /// build grouping or ownership tools on top of the graph. The component is removed along with
+    /// the node. Returns `false` if the node has been disposed.
+    ///
+    /// The crate's own components are private, so they can't be replaced this way.
+    pub fn insert_meta<C: Component>(
+        &mut self,
+        observable: impl observable::AnyObservable,
+        component: C,
+    ) -> bool {
+        let Some(mut entity) = self
+            .reactive_state
+            .get_entity_mut(observable.reactive_entity())
+        else {
+            return false;
+        };
+        entity.insert(component);
+        true
+    }
+
+    /// The component of type `C` attached to the `observable` with
+    /// [`ReactiveContext::insert_meta`], if any.
+    pub fn meta<C: Component>(&self, observable: impl observable::AnyObservable) -> Option<&C> {
+        self.reactive_state.get::<C>(observable.reactive_entity())
+    }
+
+    /// Remove the component of type `C` attached with [`ReactiveContext::insert_meta`], returning
+    /// it.
+    pub fn remove_meta<C: Component>(
+        &mut self,
+        observable: impl observable::AnyObservable,
+    ) -> Option<C> {
+        self.reactive_state
+            .get_entity_mut(observable.reactive_entity())?
+            .take::<C>()
+    }
+
+    /// Iterate over the entity of every node with a component of type `C` attached with
+    /// [`ReactiveContext::insert_meta`], along with the component.
+    pub fn iter_meta<C: Component>(&self) -> impl Iterator<Item = (Entity, &C)> {
+        self.reactive_state
+            .iter_entities()
+            .filter_map(|entity| entity.get::<C>().map(|meta| (entity.id(), meta)))
+    }
+
+    /// Returns `true` if anything reacts to changes of the `observable`: a memo that depends on it,
+    /// a callback, or an effect. This can be used to skip expensive work, like fetching data, that
+    /// nobody would see.
+    ///
+    /// Memos only subscribe to the inputs they read on their last recompute, so a memo that
+    /// branches on its inputs only observes the inputs of the branch it took.
+    pub fn is_observed<O: Observable>(&self, observable: O) -> bool {
+        stats::is_observed::<O::DataType>(&self.reactive_state, observable.reactive_entity())
+    }
+
+    /// Returns `true` if an effect runs when the `observable` changes, because the effect observes
+    /// it directly, or observes a memo downstream of it. See [`ReactiveContext::is_observed`].
+    pub fn is_observed_by_effect(&self, observable: impl observable::AnyObservable) -> bool {
+        stats::is_observed_by_effect(&self.reactive_state, observable.reactive_entity())
+    }
+
+    /// The number of reactive nodes (signals and memos) in the graph.
+    pub fn node_count(&self) -> usize {
+        self.stats().nodes
+    }
+
+    /// Check the reactive graph for broken invariants, like subscribers that no longer exist, or
+    /// memos whose inputs have been disposed, returning every violation found. An empty list means
+    /// the graph is consistent.
+    ///
+    /// This walks the entire graph, so it is meant for tests and debug builds, e.g. with
+    /// `debug_assert!(rctx.debug_validate().is_empty())` after building a graph.
+    pub fn debug_validate(&self) -> Vec<GraphViolation> {
+        stats::validate(&self.reactive_state)
+    }
+
+    /// Every node downstream of the `observable`, nearest first, i.e. everything that may be
+    /// recomputed when it changes. Walks the current subscriber edges, visiting each node once, so
+    /// it terminates even if the graph has a cycle.
+    pub fn transitive_subscribers(
+        &self,
+        observable: impl observable::AnyObservable,
+    ) -> Vec<Entity> {
+        stats::transitive_subscribers(&self.reactive_state, observable.reactive_entity())
+    }
+
+    /// Every node upstream of the `observable`, nearest first, i.e. everything whose changes can
+    /// reach it. See [`ReactiveContext::transitive_subscribers`]. This is linear in the size of the
+    /// graph.
+    pub fn transitive_dependencies(
+        &self,
+        observable: impl observable::AnyObservable,
+    ) -> Vec<Entity> {
+        stats::transitive_dependencies(&self.reactive_state, observable.reactive_entity())
+    }
+
+    /// Find memos that compute the same thing from the same inputs, so they could be replaced by
+    /// a single shared memo. Each group holds the entities of memos that are duplicates of each
+    /// other. This walks the entire graph, so it is linear in the number of nodes.
+    ///
+    /// Memos are compared by their inputs, in order, and their calculation function, which is
+    /// only comparable if it captures nothing. Calculations that capture state can be compared by
+    /// giving them a key with [`Memo::set_identity_key`]. Memos with other kinds of calculations,
+    /// like folds, are never reported. To avoid building duplicates in the first place, see
+    /// [`GraphBuilder`].
+    pub fn find_duplicate_memos(&self) -> Vec<Vec<Entity>> {
+        stats::duplicate_memos(&self.reactive_state)
+    }
+
+    /// The world holding the reactive graph, for libraries building their own abstractions on top
+    /// of the context. Every node is an entity, whose id is [`Observable::reactive_entity`].
+    pub fn reactive_world(&self) -> &World {
+        &self.reactive_state
+    }
+
+    /// Mutable access to the world holding the reactive graph, e.g. to attach components of your
+    /// own to nodes, or to store resources alongside the graph. See
+    /// [`ReactiveContext::reactive_world`].
+    ///
+    /// The components of this crate on node entities are private, but they can still be removed or
+    /// despawned through the world. To keep the graph consistent:
+    /// - Don't despawn nodes, or remove components from them. Use [`ReactiveContext::dispose`]
+    ///   instead.
+    /// - Don't clear or replace the resources of the world.
+    pub fn reactive_world_mut(&mut self) -> &mut World {
+        &mut self.reactive_state
+    }
+
+    /// Compute counters describing the size and shape of the reactive graph, e.g. for a debug
+    /// overlay. This walks the entire graph, so it is linear in the number of nodes.
+    pub fn stats(&self) -> ReactiveStats {
+        ReactiveStats::collect(&self.reactive_state)
+    }
+
+    /// The change tick at which the value of the `observable` last changed, or `None` if it has no
+    /// value. Ticks come from a counter that is incremented every time any observable in this
+    /// context changes, so comparing them tells which of two observables changed more recently.
+    pub fn last_changed_tick<O: Observable>(&self, observable: O) -> Option<u64> {
+        self.reactive_state
+            .get::<RxObservableData<O::DataType>>(observable.reactive_entity())
+            .map(|data| data.changed_tick)
+    }
+
+    /// The current change tick, which is the tick of the most recent change to any observable in
+    /// this context. Store it to later check which observables changed since, by comparing it with
+    /// [`ReactiveContext::last_changed_tick`].
+    pub fn change_tick(&self) -> u64 {
+        self.reactive_state.resource::<RxTraversal>().change_tick
+    }
+
+    /// The number of times a change has been propagated through the graph. It is incremented once
+    /// per signal write, or per batch of writes applied together, like coalesced writes, whether
+    /// or not any value changed. Writes made while a change is propagating, e.g. by callbacks, are
+    /// part of the same generation. Use it to correlate logs from a single update, or to run
+    /// something at most once per update.
+    pub fn generation(&self) -> u64 {
+        self.reactive_state.resource::<RxTraversal>().generation
+    }
+
+    /// Running totals of the memos recomputed, and the changes cut off because a value was
+    /// unchanged, since the context was created or [`ReactiveContext::reset_propagation_counters`]
+    /// was last called. Use this to verify that diffing stops propagation in your own graphs.
+    pub fn propagation_counters(&self) -> PropagationCounters {
+        self.reactive_state.resource::<RxTraversal>().counters
+    }
+
+    /// Reset the [`ReactiveContext::propagation_counters`] to zero.
+    pub fn reset_propagation_counters(&mut self) {
+        self.reactive_state.resource_mut::<RxTraversal>().counters = PropagationCounters::default();
+    }
+
+    /// Include signals of type `T` in [`ReactiveContext::snapshot`], using the provided functions
+    /// to convert their values to and from bytes.
+    pub fn register_serializable<T: Clone + Send + Sync + PartialEq + 'static>(
+        &mut self,
+        serialize: impl Fn(&T) -> Vec<u8> + Send + Sync + 'static,
+        deserialize: impl Fn(&[u8]) -> Option<T> + Send + Sync + 'static,
+    ) {
+        self.reactive_state
+            .get_resource_or_insert_with(RxSerializers::default)
+            .register(serialize, deserialize);
+    }
+
+    /// Capture the current value of every signal with a type registered using
+    /// [`ReactiveContext::register_serializable`].
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot::capture(&self.reactive_state)
+    }
+
+    /// Send the values captured in a [`Snapshot`] back to their signals, recomputing the graph.
+    /// Values that fail to deserialize, or whose signal no longer exists, are skipped.
+    pub fn restore(&mut self, snapshot: &Snapshot) {
+        snapshot.restore(&mut self.reactive_state);
+    }
+
+    /// Tear down the entire reactive graph, despawning every signal, memo, and effect, and
+    /// discarding any queued effects, coalesced writes, scheduled memos, and completed async writes
+    /// that have not been applied yet.
+    ///
+    /// All existing handles become stale, and can no longer be read.
+    pub fn clear(&mut self) {
+        // Entities are despawned individually instead of using `World::clear_entities`, which
+        // would reset entity generations and allow stale handles to alias newly created nodes.
+        let entities: Vec<Entity> = self
+            .reactive_state
+            .iter_entities()
+            .map(|entity| entity.id())
+            .collect();
+        for entity in entities {
+            self.reactive_state.despawn(entity);
+        }
+        self.reactive_state
+            .resource_mut::<RxDeferredEffects>()
+            .clear();
+        self.reactive_state
+            .resource_mut::<RxCoalescedWrites>()
+            .clear();
+        self.reactive_state
+            .resource_mut::<RxScheduledMemos>()
+            .clear();
+        self.reactive_state.resource_mut::<RxTraversal>().clear();
+        RxAsyncWrites::clear(&mut self.reactive_state);
+        RxNodeIds::clear(&mut self.reactive_state);
+        #[cfg(feature = "local")]
+        local::RxLocalValues::clear(&mut self.reactive_state);
+        RxLifecycle::settle(&mut self.reactive_state);
+    }
+
+    /// Assign a stable [`NodeId`] to the `observable`'s node, so it can be found again with
+    /// [`ReactiveContext::resolve_signal`] or [`ReactiveContext::resolve_memo`] after the graph has
+    /// been rebuilt. If the id was already assigned to another node, it now refers to this one.
+    /// Returns an error if the node has been disposed.
+    pub fn assign_id(
+        &mut self,
+        observable: impl observable::AnyObservable,
+        id: NodeId,
+    ) -> Result<(), RxError> {
+        RxNodeIds::assign(&mut self.reactive_state, observable.reactive_entity(), id)
+    }
+
+    /// The [`NodeId`] assigned to the `observable`'s node, if any.
+    pub fn node_id(&self, observable: impl observable::AnyObservable) -> Option<NodeId> {
+        RxNodeIds::id_of(&self.reactive_state, observable.reactive_entity())
+    }
+
+    /// The signal the `id` was assigned to. Returns [`RxError::UnknownId`] if the id was never
+    /// assigned, or was cleared by [`ReactiveContext::clear`], [`RxError::NodeDisposed`] if the
+    /// node it was assigned to has been disposed, and an error if the node is not a signal holding
+    /// a `T`.
+    pub fn resolve_signal<T: Send + Sync + 'static>(
+        &self,
+        id: NodeId,
+    ) -> Result<Signal<T>, RxError> {
+        RxNodeIds::resolve_signal(&self.reactive_state, id)
+    }
+
+    /// The memo the `id` was assigned to. See [`ReactiveContext::resolve_signal`].
+    pub fn resolve_memo<T: Send + Sync + 'static>(&self, id: NodeId) -> Result<Memo<T>, RxError> {
+        RxNodeIds::resolve_memo(&self.reactive_state, id)
+    }
+
+    /// Send a signal, and run the reaction graph to completion.
+    ///
+    /// Potentially expensive operation that will write a value to this [`Signal`]. This will cause
+    /// all reactive subscribers of this observable to recompute their own values, which can cause
+    /// all of its subscribers to recompute, etc.
+    ///
+    /// Returns `false` if the value was equal to the current one, so nothing was propagated. A
+    /// signal sent while the graph is being traversed, e.g. from a callback, is applied once the
+    /// traversal has settled, so it is compared to the value at the time it was sent instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the handle points at a memo, see [`ReactiveContext::try_send_signal`].
+    pub fn send_signal<T: Clone + Send + Sync + PartialEq + 'static>(
+        &mut self,
+        signal: Signal<T>,
+        value: T,
+    ) -> bool {
+        self.try_send_signal(signal, value)
+            .unwrap_or_else(|error| panic!("Failed to write signal: {error}"))
+    }
+
+    /// Send a signal like [`ReactiveContext::send_signal`], or return [`RxError::MemoWrite`] if the
+    /// handle points at the node of a memo, whose value may only come from its calculation, and
+    /// [`RxError::NodeDisposed`] if the signal has been disposed.
+    pub fn try_send_signal<T: Clone + Send + Sync + PartialEq + 'static>(
+        &mut self,
+        signal: Signal<T>,
+        value: T,
+    ) -> Result<bool, RxError> {
+        let entity = signal.reactive_entity();
+        if self.reactive_state.get_entity(entity).is_none() {
+            return Err(RxError::NodeDisposed(entity));
+        }
+        RxMemoNode::check_write(&self.reactive_state, entity)?;
+        Ok(RxObservableData::send_signal(
+            &mut self.reactive_state,
+            entity,
+            value,
+        ))
+    }
+
+    /// Send a signal like [`ReactiveContext::send_signal`], but skip the diff for this one call, so
+    /// the signal's subscribers, callbacks, and effects run even if `value` is equal to the current
+    /// value. Memos further down the graph are still diffed as usual.
+    ///
+    /// This causes redundant recomputes, so use it sparingly, e.g. for a "refresh" button that
+    /// deliberately re-triggers effects.
+    pub fn send_signal_force<T: Clone + Send + Sync + PartialEq + 'static>(
+        &mut self,
+        signal: Signal<T>,
+        value: T,
+    ) {
+        signal.send_force(self, value);
+    }
+
+    /// Recompute every subscriber of `observable`, even if its value did not change. This is used
+    /// to bring memos up to date after [`Signal::set_silent`].
+    pub fn recompute_subscribers(&mut self, observable: impl observable::AnyObservable) {
+        RxSubscribers::notify(&mut self.reactive_state, observable.reactive_entity());
+    }
+
+    /// Swap the value of `observable` for `value`, returning the previous value without cloning it.
+    /// This is a low-level escape hatch, e.g. for test setup, or migrating how values are stored.
+    ///
+    /// It bypasses all reactive semantics: the value is not diffed, its change tick, history and
+    /// write map are left alone, and no subscribers, callbacks or effects are run. Memos, including
+    /// the observable itself if it is a memo, can be out of sync with their inputs until they are
+    /// recomputed, e.g. with [`ReactiveContext::recompute_subscribers`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the observable has no value, see [`ReactiveContext::try_replace_value`].
+    pub fn replace_value<T: Send + Sync + PartialEq + 'static, O: Observable<DataType = T>>(
+        &mut self,
+        observable: O,
+        value: T,
+    ) -> T {
+        self.try_replace_value(observable, value)
+            .unwrap_or_else(|error| panic!("Failed to replace value: {error}"))
+    }
+
+    /// Swap the value of `observable` for `value`, returning the previous value, or an error if
+    /// the observable has no value. See [`ReactiveContext::replace_value`].
+    pub fn try_replace_value<T: Send + Sync + PartialEq + 'static, O: Observable<DataType = T>>(
+        &mut self,
+        observable: O,
+        value: T,
+    ) -> Result<T, RxError> {
+        RxObservableData::replace(
+            &mut self.reactive_state,
+            observable.reactive_entity(),
+            value,
+        )
+    }
+
+    /// Subscribe the `subscriber` node to `source`, so it is recomputed the next time `source`
+    /// changes, even though its calculation doesn't read `source`. Returns `false` if `source` is
+    /// not a reactive node. This is an advanced extension point, for wiring up nodes with
+    /// dependency logic that doesn't fit [`ReactiveContext::new_memo`].
+    ///
+    /// The subscriber must be a memo node, other entities are skipped when `source` changes.
+    /// Subscriptions are removed every time they fire, and memos only resubscribe to the inputs
+    /// they read, so a manual edge recomputes the memo once unless it is subscribed again. The
+    /// edge is not checked for cycles.
+    pub fn subscribe(
+        &mut self,
+        source: impl observable::AnyObservable,
+        subscriber: Entity,
+    ) -> bool {
+        RxSubscribers::add(
+            &mut self.reactive_state,
+            source.reactive_entity(),
+            subscriber,
+        )
+    }
+
+    /// Subscribe the `subscriber` node to `source` like [`ReactiveContext::subscribe`], but keep
+    /// the edge when it fires, so the subscriber is recomputed every time `source` changes until it
+    /// is removed with [`ReactiveContext::unsubscribe`], or either node is disposed. Returns
+    /// `false` if `source` or `subscriber` is not a reactive node.
+    pub fn subscribe_persistent(
+        &mut self,
+        source: impl observable::AnyObservable,
+        subscriber: Entity,
+    ) -> bool {
+        RxSubscribers::add_persistent(
+            &mut self.reactive_state,
+            source.reactive_entity(),
+            subscriber,
+        )
+    }
+
+    /// Remove the subscription of the `subscriber` node to `source`, without affecting any other
+    /// subscriber, whether it was added with [`ReactiveContext::subscribe`],
+    /// [`ReactiveContext::subscribe_persistent`], or by the subscriber reading `source`. A memo
+    /// that reads `source` subscribes again when it is next recomputed by another input.
+    pub fn unsubscribe(&mut self, source: impl observable::AnyObservable, subscriber: Entity) {
+        if let Some(mut subscribers) = self
+            .reactive_state
+            .get_mut::<RxSubscribers>(source.reactive_entity())
+        {
+            subscribers.unsubscribe(subscriber);
+        }
+        RxLifecycle::settle(&mut self.reactive_state);
+    }
+
+    /// Write a value to a signal at the next [`ReactiveContext::flush_effects`], instead of
+    /// immediately. Only the latest value written to each signal is kept, and all coalesced writes
+    /// are propagated in a single traversal, so intermediate values are never computed.
+    ///
+    /// This is useful for signals written many times per frame, like animations. Reading the signal
+    /// returns its previous value until the flush.
+    pub fn write_coalesced<T: Clone + Send + Sync + PartialEq + 'static>(
+        &mut self,
+        signal: Signal<T>,
+        value: T,
+    ) {
+        signal.write_coalesced(self, value);
+    }
+
+    pub fn new_signal<T: Clone + Send + Sync + PartialEq + 'static>(
+        &mut self,
+        initial_value: T,
+    ) -> Signal<T> {
+        Signal::new(self, initial_value)
+    }
+
+    /// Create a signal for every value, returning the handles in the same order. The signals are
+    /// spawned in a single batch, which is faster than calling [`ReactiveContext::new_signal`] in
+    /// a loop for thousands of homogeneous signals, like per-tile or per-unit state.
+    pub fn new_signals<T: Clone + Send + Sync + PartialEq + 'static>(
+        &mut self,
+        values: impl IntoIterator<Item = T>,
+    ) -> Vec<Signal<T>> {
+        Signal::new_batch(self, values)
+    }
+
+    /// Create a signal for nodes with a very large number of subscribers, like global settings
+    /// that thousands of memos depend on.
+    ///
+    /// Subscribers of a regular signal are stored in a `Vec`, which is the fastest choice for
+    /// typical fan-outs, but needs a linear scan to skip duplicate subscriptions, until the signal
+    /// has a few dozen subscribers and starts indexing them. This signal indexes its subscribers
+    /// from the start, making subscribing and unsubscribing O(1), at the cost of hashing on every
+    /// subscribe. Run the `high_fanout` example to compare the two.
+    pub fn new_signal_high_fanout<T: Clone + Send + Sync + PartialEq + 'static>(
+        &mut self,
+        initial_value: T,
+    ) -> Signal<T> {
+        Signal::new_high_fanout(self, initial_value)
+    }
+
+    /// Create a signal that detects changes by comparing the hashes of its values, instead of the
+    /// values themselves, e.g. for large structures that are expensive to compare. Values are sent
+    /// wrapped with [`Hashed::new`]. See [`Hashed`] for the risk of collisions.
+    pub fn new_signal_hashed<T: Hash + Clone + Send + Sync + 'static>(
+        &mut self,
+        initial_value: T,
+    ) -> Signal<Hashed<T>> {
+        Signal::new(self, Hashed::new(initial_value))
+    }
+
+    /// Create a signal that carries no data, and notifies its subscribers every time it is sent.
+    /// See [`Pulse`].
+    pub fn new_pulse(&mut self) -> Pulse {
+        Pulse::new(self)
+    }
+
+    /// Create a signal that passes every value written to it through `validate`, e.g. to clamp it
+    /// to a range. The validated value is what gets diffed and stored, so subscribers only ever see
+    /// valid values, and writing a value that validates to the current one does nothing.
+    pub fn new_signal_validated<T: Clone + Send + Sync + PartialEq + 'static>(
+        &mut self,
+        initial_value: T,
+        validate: impl Fn(T) -> T + Send + Sync + 'static,
+    ) -> Signal<T> {
+        Signal::new_mapped(self, initial_value, validate)
+    }
+
+    /// Create a signal that transforms every value written to it with `map` before it is diffed and
+    /// stored, e.g. to trim or lowercase text. Unlike a memo, the signal stays writable.
+    pub fn new_signal_mapped<T: Clone + Send + Sync + PartialEq + 'static>(
+        &mut self,
+        initial_value: T,
+        map: impl Fn(T) -> T + Send + Sync + 'static,
+    ) -> Signal<T> {
+        Signal::new_mapped(self, initial_value, map)
+    }
+
+    /// Start recording the values of `signal`, keeping up to `capacity` previous values that can be
+    /// stepped through with [`ReactiveContext::undo`] and [`ReactiveContext::redo`]. Only writes
+    /// that change the value are recorded.
+    pub fn track_history<T: Clone + Send + Sync + PartialEq + 'static>(
+        &mut self,
+        signal: Signal<T>,
+        capacity: usize,
+    ) {
+        self.reactive_state
+            .entity_mut(signal.reactive_entity())
+            .insert(RxHistory::<T>::new(capacity));
+    }
+
+    /// Send the previous value in the history of `signal`, returning `false` if there is nothing
+    /// to undo, or the history is not tracked. See [`ReactiveContext::track_history`].
+    pub fn undo<T: Clone + Send + Sync + PartialEq + 'static>(
+        &mut self,
+        signal: Signal<T>,
+    ) -> bool {
+        RxHistory::<T>::undo(&mut self.reactive_state, signal.reactive_entity())
+    }
+
+    /// Send the next value in the history of `signal` that was undone, returning `false` if there
+    /// is nothing to redo. See [`ReactiveContext::track_history`].
+    pub fn redo<T: Clone + Send + Sync + PartialEq + 'static>(
+        &mut self,
+        signal: Signal<T>,
+    ) -> bool {
+        RxHistory::<T>::redo(&mut self.reactive_state, signal.reactive_entity())
+    }
+
+    /// Restore every signal to the value it was created with. See [`Signal::reset`].
+    pub fn reset_all(&mut self) {
+        let signals: Vec<Entity> = self
+            .reactive_state
+            .query_filtered::<Entity, With<RxSignalReset>>()
+            .iter(&self.reactive_state)
+            .collect();
+        for signal in signals {
+            RxSignalReset::run(&mut self.reactive_state, signal);
+        }
+    }
+
+    /// Create a memo, returning an error if its calculation could not produce an initial value.
+    /// See [`Memo::try_new`].
+    pub fn try_new_memo<T: Clone + Send + Sync + PartialEq + 'static, C: MemoQuery<T> + 'static>(
+        &mut self,
+        calculation_query: C,
+        derive_fn: impl Fn(C::Query<'_>) -> T + Send + Sync + Clone + 'static,
+    ) -> Result<Memo<T>, RxError> {
+        Memo::try_new(self, calculation_query, derive_fn)
+    }
+
+    pub fn new_memo<T: Clone + Send + Sync + PartialEq + 'static, C: MemoQuery<T> + 'static>(
+        &mut self,
+        calculation_query: C,
+        derive_fn: impl Fn(C::Query<'_>) -> T + Send + Sync + Clone + 'static,
+    ) -> Memo<T> {
+        Memo::new(self, calculation_query, derive_fn)
+    }
+
+    /// Create a memo that detects changes by comparing the hashes of the values it computes,
+    /// instead of the values themselves. See [`ReactiveContext::new_signal_hashed`].
+    pub fn new_memo_hashed<T, C>(
+        &mut self,
+        calculation_query: C,
+        derive_fn: impl Fn(C::Query<'_>) -> T + Send + Sync + Clone + 'static,
+    ) -> Memo<Hashed<T>>
+    where
+        T: Hash + Clone + Send + Sync + 'static,
+        C: MemoQuery<Hashed<T>> + 'static,
+    {
+        Memo::new(self, calculation_query, move |query| {
+            Hashed::new(derive_fn(query))
+        })
+    }
+
+    /// Create a memo from reactive inputs and a constant that is read, but not subscribed to. See
+    /// [`Memo::new_with_const`].
+    pub fn new_memo_with_const<T, K, C>(
+        &mut self,
+        calculation_query: C,
+        constant: K,
+        derive_fn: impl Fn(&K, C::Query<'_>) -> T + Send + Sync + Clone + 'static,
+    ) -> Memo<T>
+    where
+        T: Clone + Send + Sync + PartialEq + 'static,
+        K: Clone + Send + Sync + 'static,
+        C: MemoQuery<T> + 'static,
+    {
+        Memo::new_with_const(self, calculation_query, constant, derive_fn)
+    }
+
+    /// Create a memo that is only computed when it is read, see [`Memo::new_lazy`].
+    pub fn new_lazy_memo<
+        T: Clone + Send + Sync + PartialEq + 'static,
+        C: MemoQuery<T> + 'static,
+    >(
+        &mut self,
+        calculation_query: C,
+        derive_fn: impl Fn(C::Query<'_>) -> T + Send + Sync + Clone + 'static,
+    ) -> Memo<T> {
+        Memo::new_lazy(self, calculation_query, derive_fn)
+    }
+
+    /// Create several memos from the same inputs, computed together in a single pass.
+    /// `derive_fn` returns a tuple with a value for each memo, and a tuple of memos is returned.
+    ///
+    /// The inputs are subscribed to once, and every output is computed from the same input values,
+    /// so intermediate work can be shared between them. Each memo is still diffed on its own, so
+    /// its subscribers are only recomputed when its own value changes.
+    pub fn new_memo_multi<T: MemoOutputs, C: MemoQuery<T> + 'static>(
+        &mut self,
+        calculation_query: C,
+        derive_fn: impl Fn(C::Query<'_>) -> T + Send + Sync + Clone + 'static,
+    ) -> T::Memos {
+        let outputs = self.new_memo(calculation_query, derive_fn);
+        T::split(self, outputs)
+    }
+
+    /// Start building a graph with named and deduplicated nodes. See [`GraphBuilder`].
+    pub fn graph_builder(&mut self) -> GraphBuilder<'_, S> {
+        GraphBuilder::new(self)
+    }
+
+    /// Create a memo that reuses the results it computed for recent combinations of its input
+    /// values, instead of recomputing them, see [`Memo::new_cached`].
+    pub fn new_cached_memo<T, C>(
+        &mut self,
+        input_deps: C,
+        capacity: usize,
+        derive_fn: impl Fn(C::Query<'_>) -> T + Send + Sync + Clone + 'static,
+    ) -> Memo<T>
+    where
+        T: Clone + Send + Sync + PartialEq + 'static,
+        C: MemoQuery<T> + ObservableTuple,
+        C::Values: Eq + Send + Sync + 'static,
+    {
+        Memo::new_cached(self, input_deps, capacity, derive_fn)
+    }
+
+    /// Create a memo that accumulates its own previous value with each change of `input`. See
+    /// [`Memo::new_fold`].
+    pub fn new_fold<T: Clone + Send + Sync + PartialEq + 'static, O: Observable>(
+        &mut self,
+        input: O,
+        initial_state: T,
+        fold_fn: impl Fn(&T, &O::DataType) -> T + Send + Sync + 'static,
+    ) -> Memo<T> {
+        Memo::new_fold(self, input, initial_state, fold_fn)
+    }
+
+    /// Create a source of increasing ids, e.g. for the keys of a
+    /// [`ReactiveList`](list::ReactiveList), which start over from `0` every time `reset` changes.
+    /// See [`IndexSource`](list::IndexSource).
+    pub fn new_index_source(&mut self, reset: impl Observable) -> list::IndexSource {
+        list::IndexSource::new(self, reset)
+    }
+
+    /// Create a memo that tracks the value of the observable held inside another observable. See
+    /// [`Memo::flatten`].
+    pub fn flatten<T: Clone + Send + Sync + PartialEq + 'static, O>(&mut self, outer: O) -> Memo<T>
+    where
+        O: Observable,
+        O::DataType: Observable<DataType = T> + Copy,
+    {
+        Memo::flatten(self, outer)
+    }
+
+    /// Invoke `callback` with the new value every time the `observable` changes, synchronously
+    /// while the change propagates. This is a lightweight alternative to an effect, for glue code
+    /// that doesn't need access to the main world.
+    ///
+    /// The callback is unsubscribed when the returned [`Subscription`] is dropped.
+    pub fn on_change<T: Clone + Send + Sync + PartialEq + 'static, O: Observable<DataType = T>>(
+        &mut self,
+        observable: O,
+        callback: impl FnMut(&T) + Send + Sync + 'static,
+    ) -> Subscription {
+        RxCallbacks::add(
+            &mut self.reactive_state,
+            observable.reactive_entity(),
+            callback,
+        )
+    }
+
+    /// Run the async `task` with the new value every time the `observable` changes, and write its
+    /// output to the `target` signal once it completes. Tasks are spawned on bevy's
+    /// `AsyncComputeTaskPool`, and their results are applied on the next
+    /// [`ReactiveContext::flush_effects`].
+    ///
+    /// A task is spawned for every change, and tasks may complete in any order. No more tasks are
+    /// spawned once the returned [`Subscription`] is dropped, though tasks that are already
+    /// running still write their result.
+    pub fn new_async_effect<T, U, Fut>(
+        &mut self,
+        observable: impl Observable<DataType = T>,
+        target: Signal<U>,
+        task: impl Fn(T) -> Fut + Send + Sync + 'static,
+    ) -> Subscription
+    where
+        T: Clone + Send + Sync + PartialEq + 'static,
+        U: Clone + Send + Sync + PartialEq + 'static,
+        Fut: std::future::Future<Output = U> + Send + 'static,
+    {
+        RxAsyncWrites::spawn(self, observable, target, task)
+    }
+
+    /// Create a memo computed by a system run against the main world, see [`Memo::new_system`].
+    pub fn new_system_memo<T: Clone + Send + Sync + PartialEq + 'static, D: MemoQuery<()>, M>(
+        &mut self,
+        input_deps: D,
+        system: impl IntoSystem<(), T, M>,
+    ) -> Memo<T> {
+        Memo::new_system(self, input_deps, system)
+    }
+
+    /// Create a memo computed by a system run against the main world every frame, see
+    /// [`Memo::new_polled_system`].
+    pub fn new_polled_system_memo<T: Clone + Send + Sync + PartialEq + 'static, M>(
+        &mut self,
+        system: impl IntoSystem<(), T, M>,
+    ) -> Memo<T> {
+        Memo::new_polled_system(self, system)
+    }
+
+    pub fn new_deferred_effect<O: Observable, M>(
+        &mut self,
+        observable: O,
+        effect_system: impl IntoSystem<(), (), M>,
+    ) -> Effect
+    where
+        O::DataType: Clone,
+    {
+        Effect::new_deferred(self, observable, effect_system)
+    }
+
+    /// Create an effect that runs once at the next flush, and then on every change. See
+    /// [`Effect::new_mounted`].
+    pub fn new_mounted_effect<O: Observable, M>(
+        &mut self,
+        observable: O,
+        effect_system: impl IntoSystem<(), (), M>,
+    ) -> Effect
+    where
+        O::DataType: Clone,
+    {
+        Effect::new_mounted(self, observable, effect_system)
+    }
+
+    /// Create an effect from a closure that receives the observed value and the main world, see
+    /// [`Effect::new_fn`].
+    pub fn new_effect_fn<O: Observable>(
+        &mut self,
+        observable: O,
+        effect: impl FnMut(&O::DataType, &mut World) + Send + Sync + 'static,
+    ) -> Effect
+    where
+        O::DataType: Clone,
+    {
+        Effect::new_fn(self, observable, effect)
+    }
+
+    /// Create an effect that borrows the observed value in place instead of cloning it, see
+    /// [`Effect::new_borrowed`].
+    pub fn new_borrowed_effect<O: Observable, M>(
+        &mut self,
+        observable: O,
+        effect_system: impl IntoSystem<(), (), M>,
+    ) -> Effect {
+        Effect::new_borrowed(self, observable, effect_system)
+    }
+
+    /// Create an effect that only runs for changes that `is_significant` accepts, see
+    /// [`Effect::new_significant`]. Subscribers of the observable still see every change.
+    pub fn new_significant_effect<O: Observable, M>(
+        &mut self,
+        observable: O,
+        is_significant: impl Fn(&O::DataType, &O::DataType) -> bool + Send + Sync + 'static,
+        effect_system: impl IntoSystem<(), (), M>,
+    ) -> Effect
+    where
+        O::DataType: Clone,
+    {
+        Effect::new_significant(self, observable, is_significant, effect_system)
+    }
+
+    /// Create an effect whose system returns a `Result`, see [`Effect::new_fallible`].
+    pub fn new_fallible_effect<O: Observable, E: Into<EffectError> + 'static, M>(
+        &mut self,
+        observable: O,
+        effect_system: impl IntoSystem<(), Result<(), E>, M>,
+    ) -> Effect
+    where
+        O::DataType: Clone,
+    {
+        Effect::new_fallible(self, observable, effect_system)
+    }
+
+    /// Handle the errors returned by fallible effects with `handler`, e.g. to collect them, or
+    /// panic. By default, errors are logged.
+    pub fn set_effect_error_handler(
+        &mut self,
+        handler: impl FnMut(Effect, EffectError) + Send + Sync + 'static,
+    ) {
+        self.reactive_state
+            .resource_mut::<RxDeferredEffects>()
+            .error_handler = Some(Box::new(handler));
+    }
+
+    /// Handle the errors that would otherwise panic while changes propagate with `hook`, e.g. to
+    /// log them and dump the graph before deciding whether to abort. The hook receives a memo
+    /// producing a value of a different type than its node holds, and a callback being added to a
+    /// disposed node, e.g. with [`ReactiveContext::on_change`]. When a hook is set, the node that
+    /// failed is left unchanged and propagation continues, instead of panicking.
+    ///
+    /// Methods that panic on misuse, like [`ReactiveContext::read`], still panic. Use their
+    /// fallible counterparts to handle those errors.
+    pub fn set_panic_hook(&mut self, hook: impl Fn(RxError) + Send + Sync + 'static) {
+        self.reactive_state.insert_resource(RxPanicHook::new(hook));
+    }
+
+    /// Show the effects about to run to `hook` before each pass of
+    /// [`ReactiveContext::flush_effects`], e.g. to log what is about to happen this frame. Effects
+    /// queued by other effects run in later passes, and are shown to the hook again.
+    ///
+    /// The effects run in the order the hook leaves them in, so it can also reorder them, e.g. to
+    /// run layout effects before render effects. Effects the hook removes from the list still run,
+    /// after the others.
+    pub fn set_pre_flush_hook(
+        &mut self,
+        hook: impl FnMut(&mut Vec<PendingEffect>) + Send + Sync + 'static,
+    ) {
+        self.reactive_state
+            .resource_mut::<RxDeferredEffects>()
+            .pre_flush_hook = Some(Box::new(hook));
+    }
+
+    /// Create an effect that runs when any of several `inputs` changes, and can tell which of them
+    /// did. See [`Effect::new_multi`].
+    pub fn new_multi_effect<D: ObservableTuple + Send + Sync + 'static, M>(
+        &mut self,
+        inputs: D,
+        effect_system: impl IntoSystem<(), (), M>,
+    ) -> Effect
+    where
+        D::Values: Send + Sync + 'static,
+    {
+        Effect::new_multi(self, inputs, effect_system)
+    }
+
+    /// Run every deferred effect that was queued by a change to its observable, in the order they
+    /// were queued. Effects run against the `main_world`.
+    ///
+    /// Completed async effects and coalesced writes are applied, and system memos and scheduled
+    /// memos that need to be recomputed are run first, so effects see their latest values. See
+    /// [`MemoScheduling`](memo::MemoScheduling).
+    ///
+    /// Commands issued by effects are applied according to [`ReactiveContext::effect_commands`].
+    ///
+    /// Effects queued while the effects run, e.g. by signals sent through a
+    /// [`ReactiveWriter`](effect::ReactiveWriter), are run too, in further passes, so multi-stage
+    /// reactions settle within a single flush. See [`ReactiveContext::set_max_effect_passes`].
+    ///
+    /// Each call counts as one frame for rate limited effects, see [`EffectTiming`]. When using the
+    /// `ReactiveExtensionsPlugin`, this is called once per frame in `PostUpdate`.
+    pub fn flush_effects(&mut self, main_world: &mut World) {
+        RxAsyncWrites::apply(&mut self.reactive_state);
+        RxCoalescedWrites::apply(&mut self.reactive_state);
+        RxSystemMemo::update_all(main_world, &mut self.reactive_state);
+        RxScheduledMemos::run(&mut self.reactive_state);
+        let mut deferred = self.reactive_state.resource_mut::<RxDeferredEffects>();
+        deferred.frame += 1;
+        deferred.fired.clear();
+        deferred.warned_unflushed = false;
+        RxDeferredEffects::run_queued(main_world, &mut self.reactive_state);
+        RxDeferredEffects::apply_batched_commands(main_world, &mut self.reactive_state);
+    }
+
+    /// The maximum number of passes [`ReactiveContext::flush_effects`] makes over the queued
+    /// effects, 16 by default.
+    pub fn max_effect_passes(&self) -> usize {
+        self.reactive_state
+            .resource::<RxDeferredEffects>()
+            .max_passes
+    }
+
+    /// Limit the number of passes [`ReactiveContext::flush_effects`] makes over the queued effects.
+    /// Each pass runs the effects queued by the previous one. If effects are still being queued
+    /// after the last pass, a warning is logged and the remaining effects run during the next
+    /// flush, so effects that keep triggering each other can't stall a frame.
+    pub fn set_max_effect_passes(&mut self, max_passes: usize) {
+        self.reactive_state
+            .resource_mut::<RxDeferredEffects>()
+            .max_passes = max_passes;
+    }
+
+    /// The number of effects queued to run on the next [`ReactiveContext::flush_effects`]. An
+    /// effect is queued every time its observable changes, so when using the context without
+    /// `ReactiveExtensionsPlugin`, this grows until the effects are flushed. A warning is logged
+    /// if it grows very large.
+    pub fn pending_effect_count(&self) -> usize {
+        self.reactive_state
+            .resource::<RxDeferredEffects>()
+            .stack
+            .len()
+    }
+
+    /// The effects that ran during the last [`ReactiveContext::flush_effects`], in the order they
+    /// ran, with the type and, if enabled, the formatted value of what they observed.
+    pub fn fired_effects(&self) -> &[FiredEffect] {
+        &self.reactive_state.resource::<RxDeferredEffects>().fired
+    }
+
+    /// Allow the value of `observable` to be formatted without knowing its type, e.g. in
+    /// [`ReactiveContext::fired_effects`].
+    pub fn debug_values<O: Observable>(&mut self, observable: O)
+    where
+        O::DataType: std::fmt::Debug,
+    {
+        self.reactive_state
+            .entity_mut(observable.reactive_entity())
+            .insert(RxDebugValue::new::<O::DataType>());
+    }
+
+    /// When the commands issued by effects are applied during [`ReactiveContext::flush_effects`].
+    pub fn effect_commands(&self) -> EffectCommands {
+        self.reactive_state.resource::<RxDeferredEffects>().commands
+    }
+
+    /// Set when the commands issued by effects are applied. See [`EffectCommands`].
+    pub fn set_effect_commands(&mut self, commands: EffectCommands) {
+        self.reactive_state
+            .resource_mut::<RxDeferredEffects>()
+            .commands = commands;
+    }
+
+    /// Whether the effects run during [`ReactiveContext::flush_effects`] may run at the same time.
+    pub fn effect_executor(&self) -> EffectExecutor {
+        self.reactive_state.resource::<RxDeferredEffects>().executor
+    }
+
+    /// Set whether effects may run at the same time. See [`EffectExecutor`].
+    pub fn set_effect_executor(&mut self, executor: EffectExecutor) {
+        self.reactive_state
+            .resource_mut::<RxDeferredEffects>()
+            .executor = executor;
+    }
+
+    /// Create an effect that only runs once `observable` has stopped changing for `frames` frames.
+    pub fn new_debounced_effect<O: Observable, M>(
+        &mut self,
+        observable: O,
+        frames: u32,
+        effect_system: impl IntoSystem<(), (), M>,
+    ) -> Effect
+    where
+        O::DataType: Clone,
+    {
+        Effect::new_scheduled(
+            self,
+            observable,
+            EffectTiming::Debounce(frames),
+            effect_system,
+        )
+    }
+
+    /// Create an effect that runs at most once every `frames` frames while `observable` changes.
+    pub fn new_throttled_effect<O: Observable, M>(
+        &mut self,
+        observable: O,
+        frames: u32,
+        effect_system: impl IntoSystem<(), (), M>,
+    ) -> Effect
+    where
+        O::DataType: Clone,
+    {
+        Effect::new_scheduled(
+            self,
+            observable,
+            EffectTiming::Throttle(frames),
+            effect_system,
+        )
+    }
+
+    /// Detach an effect from its observable, leaving the observable and its data intact. Returns
+    /// `false` if the effect was already removed.
+    pub fn remove_effect(&mut self, effect: Effect) -> bool {
+        self.reactive_state
+            .get_entity_mut(effect.reactor_entity)
+            .and_then(|mut entity| entity.take::<RxDeferredEffect>())
+            .is_some()
+    }
+
+    /// Pause or resume an effect. A disabled effect does not run when its observable changes,
+    /// including changes that were queued before it was disabled.
+    pub fn set_effect_enabled(&mut self, effect: Effect, enabled: bool) {
+        if let Some(mut effect) = self
+            .reactive_state
+            .get_mut::<RxDeferredEffect>(effect.reactor_entity)
+        {
+            effect.enabled = enabled;
+        }
+    }
+
+    /// Run an effect immediately against the `main_world`, even though its observable didn't
+    /// change, e.g. to initialize the state it maintains. The effect runs with the current value of
+    /// its observable, regardless of whether it is enabled or rate limited, and its commands are
+    /// applied before this returns. Returns `false` if the effect has been removed.
+    ///
+    /// If the observable has no value yet, the effect is not run.
+    pub fn run_effect_now(&mut self, effect: Effect, main_world: &mut World) -> bool {
+        RxDeferredEffects::run_now(main_world, &mut self.reactive_state, effect.reactor_entity)
+    }
+
+    pub fn effect_system(&self, effect: Effect) -> Option<&dyn System<In = (), Out = ()>> {
+        self.reactive_state
+            .get::<RxDeferredEffect>(effect.reactor_entity)
+            .and_then(|effect| effect.system())
+    }
+}
+
+mod test {
+    #[test]
+    fn basic() {
+        #[derive(Debug, Clone, PartialEq)]
+        struct Button {
+            active: bool,
+        }
+        impl Button {
+            pub const OFF: Self = Button { active: false };
+            pub const ON: Self = Button { active: true };
+        }
+
+        #[derive(Debug, Clone, PartialEq)]
+        struct Lock {
+            unlocked: bool,
+        }
+
+        impl Lock {
+            /// A lock will only unlock if both of its buttons are active
+            fn two_buttons(buttons: (&Button, &Button)) -> Self {
+                let unlocked = buttons.0.active && buttons.1.active;
+                println!("Recomputing lock. Unlocked: {unlocked}");
+                Self { unlocked }
+            }
+        }
+
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let button1 = reactor.new_signal(Button::OFF);
+        let button2 = reactor.new_signal(Button::OFF);
+        let lock1 = reactor.new_memo((button1, button2), &Lock::two_buttons);
+        assert!(!reactor.read(lock1).unlocked);
+
+        let button3 = reactor.new_signal(Button::OFF);
+        let lock2 = reactor.new_memo((button1, button3), &Lock::two_buttons);
+        assert!(!reactor.read(lock2).unlocked);
+
+        reactor.send_signal(button1, Button::ON); // Automatically recomputes lock1 & lock2!
+        reactor.send_signal(button2, Button::ON);
+        for _ in 0..1000 {
+            button1.send(&mut reactor, Button::ON); // diffing prevents triggering a recompute
+        }
+        assert!(lock1.read(&mut reactor).unlocked);
+
+        reactor.send_signal(button3, Button::ON);
+        assert!(reactor.read(lock2).unlocked);
+    }
+
+    #[test]
+    fn nested_derive() {
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let add = |n: (&f32, &f32)| n.0 + n.1;
+
+        let n1 = reactor.new_signal(1.0);
+        let n2 = reactor.new_signal(10.0);
+        let n3 = reactor.new_signal(100.0);
+
+        // The following derives use signals as inputs
+        let d1 = reactor.new_memo((n1, n2), add); // 1 + 10
+        let d2 = reactor.new_memo((n3, n2), add); // 100 + 10
+
+        // This derive uses other derives as inputs
+        let d3 = reactor.new_memo((d1, d2), add); // 11 + 110
+        assert_eq!(*reactor.read(d3), 121.0);
+    }
+
+    #[test]
+    fn many_types() {
+        #[derive(Debug, Clone, PartialEq)]
+        struct Foo(f32);
+        #[derive(Debug, Clone, PartialEq)]
+        struct Bar(f32);
+        #[derive(Debug, Clone, PartialEq)]
+        struct Baz(f32);
+
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let foo = reactor.new_signal(Foo(1.0));
+        let bar = reactor.new_signal(Bar(1.0));
+
+        let baz = reactor.new_memo((foo, bar), |(foo, bar)| Baz(foo.0 + bar.0));
+
+        assert_eq!(reactor.read(baz), &Baz(2.0));
+    }
+
+    #[test]
+    fn fold() {
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let n = reactor.new_signal(1);
+        let total = reactor.new_fold(n, 10, |total, n| total + n);
+        assert_eq!(*reactor.read(total), 11);
+
+        reactor.send_signal(n, 5);
+        reactor.send_signal(n, 5); // diffing prevents accumulating the same value twice
+        reactor.send_signal(n, 2);
+        assert_eq!(*reactor.read(total), 18);
+
+        let samples = reactor.new_fold(total, 0, |count, _| count + 1);
+        reactor.send_signal(n, 3);
+        assert_eq!(*reactor.read(samples), 2);
+    }
+
+    #[test]
+    fn try_new_memo() {
+        use crate::error::RxError;
+
+        let mut reactor = crate::ReactiveContext::<()>::default();
+        let a = reactor.new_signal(1);
+        // Has no value until effects are flushed.
+        let pending = reactor.new_system_memo((a,), || 5);
+        let before = reactor.stats();
+
+        let sum = reactor.try_new_memo((a, pending), |(a, pending)| a + pending);
+        assert!(matches!(sum, Err(RxError::NeverComputed(_))));
+        assert_eq!(reactor.stats(), before);
+
+        let doubled = reactor.try_new_memo((a,), |(a,)| a * 2).unwrap();
+        assert_eq!(*reactor.read(doubled), 2);
+    }
+
+    #[test]
+    fn fold_is_a_memo() {
+        use crate::{error::RxError, node_id::NodeId, signal::Signal};
+        use std::marker::PhantomData;
+
+        let mut reactor = crate::ReactiveContext::<()>::default();
+        let n = reactor.new_signal(1);
+        let total = reactor.new_fold(n, 10, |total, n| total + n);
+        reactor.assign_id(total, NodeId(1)).unwrap();
+        assert_eq!(reactor.resolve_memo::<i32>(NodeId(1)), Ok(total));
+        assert_eq!(
+            reactor.resolve_signal::<i32>(NodeId(1)),
+            Err(RxError::MemoWrite(total.reactor_entity))
+        );
+
+        let signal = Signal::<i32> {
+            reactor_entity: total.reactor_entity,
+            p: PhantomData,
+        };
+        assert_eq!(
+            reactor.try_send_signal(signal, 1000),
+            Err(RxError::MemoWrite(total.reactor_entity))
+        );
+        reactor.send_signal(n, 2);
+        assert_eq!(*reactor.read(total), 13);
+    }
+
+    #[test]
+    fn flatten() {
+        use crate::Observable;
+
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let a = reactor.new_signal(1);
+        let b = reactor.new_signal(2);
+        let selected = reactor.new_signal(a);
+        let value = reactor.flatten(selected);
+        assert_eq!(*reactor.read(value), 1);
+
+        reactor.send_signal(a, 10);
+        assert_eq!(*reactor.read(value), 10);
+
+        reactor.send_signal(selected, b);
+        assert_eq!(*reactor.read(value), 2);
+
+        // The memo switched to `b`, and should no longer be subscribed to `a`.
+        let a_subscribers = reactor
+            .reactive_state
+            .get::<crate::observable::RxSubscribers>(a.reactive_entity())
+            .unwrap();
+        assert!(a_subscribers.subscribers.is_empty());
+
+        reactor.send_signal(b, 20);
+        assert_eq!(*reactor.read(value), 20);
+    }
+
+    #[test]
+    fn debounced_effect() {
+        use bevy_ecs::prelude::*;
+
+        #[derive(Resource, Default)]
+        struct Seen(Vec<i32>);
+
+        let mut world = World::new();
+        world.init_resource::<Seen>();
+        let mut reactor = crate::ReactiveContext::<World>::default();
+        let n = reactor.new_signal(0);
+        reactor.new_debounced_effect(
+            n,
+            1,
+            |data: Res<crate::effect::EffectData<i32>>, mut seen: ResMut<Seen>| seen.0.push(**data),
+        );
+
+        for i in 1..=3 {
+            reactor.send_signal(n, i);
+            reactor.flush_effects(&mut world);
+        }
+        assert!(world.resource::<Seen>().0.is_empty()); // still changing every frame
+
+        reactor.flush_effects(&mut world); // one quiet frame
+        assert_eq!(world.resource::<Seen>().0, vec![3]); // only the latest value is seen
+        reactor.flush_effects(&mut world);
+        assert_eq!(world.resource::<Seen>().0, vec![3]);
+    }
+
+    #[test]
+    fn effect_passes() {
+        use crate::effect::{EffectData, ReactiveWriter};
+        use bevy_ecs::prelude::*;
+
+        #[derive(Resource, Default)]
+        struct Runs(Vec<u32>);
+
+        let mut world = World::new();
+        world.init_resource::<Runs>();
+        let mut reactor = crate::ReactiveContext::<World>::default();
+
+        // Each stage sends the next one, until the last stage is reached.
+        let stages: Vec<_> = (0..4).map(|_| reactor.new_signal(0)).collect();
+        for (i, &stage) in stages.iter().enumerate() {
+            let next = stages.get(i + 1).copied();
+            reactor.new_deferred_effect(
+                stage,
+                move |data: Res<EffectData<u32>>,
+                      mut writer: ReactiveWriter,
+                      mut runs: ResMut<Runs>| {
+                    runs.0.push(**data);
+                    if let Some(next) = next {
+                        writer.send(next, **data);
+                    }
+                },
+            );
+        }
+
+        reactor.send_signal(stages[0], 1);
+        reactor.flush_effects(&mut world);
+        assert_eq!(world.resource::<Runs>().0, vec![1; 4]); // settled within a single flush
+        assert_eq!(*reactor.read(stages[3]), 1);
+
+        reactor.set_max_effect_passes(2);
+        reactor.send_signal(stages[0], 2);
+        reactor.flush_effects(&mut world);
+        assert_eq!(world.resource::<Runs>().0[4..], [2, 2]);
+        assert_eq!(reactor.pending_effect_count(), 1); // left for the next flush
+        reactor.flush_effects(&mut world);
+        assert_eq!(world.resource::<Runs>().0[4..], [2, 2, 2, 2]);
+        assert_eq!(*reactor.read(stages[3]), 2);
+    }
+
+    #[test]
+    fn effect_commands() {
+        use crate::effect::EffectCommands;
+        use bevy_ecs::prelude::*;
+
+        #[derive(Component)]
+        struct Spawned;
+
+        #[derive(Resource, Default)]
+        struct Seen(Vec<usize>);
+
+        for (commands, expected) in [(EffectCommands::PerEffect, 1), (EffectCommands::Batched, 0)] {
+            let mut world = World::new();
+            world.init_resource::<Seen>();
+            let mut reactor = crate::ReactiveContext::<World>::default();
+            reactor.set_effect_commands(commands);
+
+            let spawn = reactor.new_signal(false);
+            let count = reactor.new_signal(false);
+            reactor.new_deferred_effect(spawn, |mut commands: Commands| {
+                commands.spawn(Spawned);
+            });
+            reactor.new_deferred_effect(count, |q: Query<&Spawned>, mut seen: ResMut<Seen>| {
+                seen.0.push(q.iter().count())
+            });
+
+            reactor.send_signal(spawn, true);
+            reactor.send_signal(count, true);
+            reactor.flush_effects(&mut world);
+            assert_eq!(world.resource::<Seen>().0, vec![expected]);
+            assert_eq!(world.query::<&Spawned>().iter(&world).count(), 1);
+        }
+    }
+
+    #[test]
+    fn effect_executor() {
+        use crate::effect::{EffectData, EffectExecutor};
+        use bevy_ecs::prelude::*;
+
+        #[derive(Component)]
+        struct Spawned;
+
+        #[derive(Resource, Default)]
+        struct Seen(Vec<(&'static str, usize)>);
+
+        for (executor, expected) in [
+            (EffectExecutor::Sequential, 1),
+            (EffectExecutor::Parallel, 0),
+        ] {
+            let mut world = World::new();
+            world.init_resource::<Seen>();
+            let mut reactor = crate::ReactiveContext::<World>::default();
+            reactor.set_effect_executor(executor);
+            assert_eq!(reactor.effect_executor(), executor);
+
+            let spawn = reactor.new_signal(false);
+            let count = reactor.new_signal(0);
+            let other = reactor.new_signal(false);
+            let exclusive = reactor.new_signal(0u8);
+            reactor.new_deferred_effect(spawn, |mut commands: Commands| {
+                commands.spawn(Spawned);
+            });
+            // Doesn't conflict with the first effect, so it runs in the same wave when parallel.
+            reactor.new_deferred_effect(count, |q: Query<&Spawned>, mut seen: ResMut<Seen>| {
+                seen.0.push(("count", q.iter().count()))
+            });
+            // Observes the same type as the first effect, so it always runs after its commands.
+            reactor.new_deferred_effect(
+                other,
+                |q: Query<&Spawned>, data: Res<EffectData<bool>>| {
+                    assert!(*data.value());
+                    assert_eq!(q.iter().count(), 1);
+                },
+            );
+            reactor.new_deferred_effect(exclusive, |world: &mut World| {
+                let spawned = world.query::<&Spawned>().iter(world).count();
+                world.resource_mut::<Seen>().0.push(("exclusive", spawned));
+            });
+
+            reactor.send_signal(spawn, true);
+            reactor.send_signal(count, 1);
+            reactor.send_signal(other, true);
+            reactor.send_signal(exclusive, 1);
+            reactor.flush_effects(&mut world);
+            assert_eq!(
+                world.resource::<Seen>().0,
+                vec![("count", expected), ("exclusive", 1)]
+            );
+            assert_eq!(reactor.fired_effects().len(), 4);
+        }
+    }
+
+    #[test]
+    fn fired_effects() {
+        use bevy_ecs::prelude::*;
+
+        let mut world = World::new();
+        let mut reactor = crate::ReactiveContext::<World>::default();
+
+        let score = reactor.new_signal(1);
+        let name = reactor.new_signal("Jane");
+        reactor.debug_values(score);
+        let score_effect = reactor.new_deferred_effect(score, || {});
+        reactor.new_deferred_effect(name, || {});
+
+        reactor.send_signal(score, 2);
+        reactor.send_signal(name, "John");
+        reactor.flush_effects(&mut world);
+
+        let fired = reactor.fired_effects();
+        assert_eq!(fired.len(), 2);
+        assert_eq!(fired[0].effect.reactor_entity, score_effect.reactor_entity);
+        assert_eq!(fired[0].type_name, std::any::type_name::<i32>());
+        assert_eq!(fired[0].value.as_deref(), Some("2"));
+        assert_eq!(fired[1].value, None); // not enabled for `name`
+
+        reactor.flush_effects(&mut world);
+        assert!(reactor.fired_effects().is_empty());
+    }
+
+    #[test]
+    fn effect_fn() {
+        use bevy_ecs::prelude::*;
+
+        #[derive(Resource)]
+        struct Greeting(String);
+
+        let mut world = World::new();
+        let mut reactor = crate::ReactiveContext::<World>::default();
+        let name = reactor.new_signal("Jane");
+        let mut runs = 0;
+        reactor.new_effect_fn(name, move |name, world| {
+            runs += 1;
+            world.insert_resource(Greeting(format!("Hello {name} ({runs})")));
+        });
+
+        reactor.send_signal(name, "John");
+        reactor.flush_effects(&mut world);
+        assert_eq!(world.resource::<Greeting>().0, "Hello John (1)");
+        reactor.send_signal(name, "Alex");
+        reactor.flush_effects(&mut world);
+        assert_eq!(world.resource::<Greeting>().0, "Hello Alex (2)");
+    }
+
+    #[test]
+    fn handle_effect() {
+        use crate::effect::EffectData;
+        use bevy_ecs::prelude::*;
+
+        #[derive(Resource, Default)]
+        struct Seen(Vec<String>);
+
+        let mut world = World::new();
+        world.init_resource::<Seen>();
+        let mut reactor = crate::ReactiveContext::<World>::default();
+        let name = reactor.new_signal("Jane");
+        let greeting = reactor.new_memo((name,), |(name,)| format!("Hi {name}"));
+        name.effect(
+            &mut reactor,
+            |data: Res<EffectData<&'static str>>, mut seen: ResMut<Seen>| {
+                seen.0.push(data.to_string())
+            },
+        );
+        greeting.effect(
+            &mut reactor,
+            |data: Res<EffectData<String>>, mut seen: ResMut<Seen>| seen.0.push(data.clone()),
+        );
+
+        name.send(&mut reactor, "John");
+        reactor.flush_effects(&mut world);
+        assert_eq!(world.resource::<Seen>().0, ["John", "Hi John"]);
+    }
+
+    #[test]
+    fn mounted_effect() {
+        use crate::effect::EffectData;
+        use bevy_ecs::prelude::*;
+
+        #[derive(Resource, Default)]
+        struct Seen(Vec<i32>);
+
+        let mut world = World::new();
+        world.init_resource::<Seen>();
+        let mut reactor = crate::ReactiveContext::<World>::default();
+        let observe = |data: Res<EffectData<i32>>, mut seen: ResMut<Seen>| seen.0.push(**data);
+        let a = reactor.new_signal(1);
+        reactor.new_mounted_effect(a, observe);
+        let b = reactor.new_signal(1);
+        reactor.new_deferred_effect(b, observe);
+
+        reactor.flush_effects(&mut world);
+        assert_eq!(world.resource::<Seen>().0, [1]);
+        reactor.flush_effects(&mut world);
+        reactor.send_signal(a, 2);
+        reactor.flush_effects(&mut world);
+        assert_eq!(world.resource::<Seen>().0, [1, 2]);
+    }
+
+    #[test]
+    fn effect_skipped_before_flush() {
+        use bevy_ecs::prelude::*;
+
+        #[derive(Resource, Default)]
+        struct Runs(usize);
+
+        let mut world = World::new();
+        world.init_resource::<Runs>();
+        let mut reactor = crate::ReactiveContext::<World>::default();
+        let count = |mut runs: ResMut<Runs>| runs.0 += 1;
+
+        // The observable is disposed after its effect was queued.
+        let disposed = reactor.new_signal(0);
+        reactor.new_deferred_effect(disposed, count);
+        reactor.send_signal(disposed, 1);
+        reactor.dispose(disposed);
+        reactor.flush_effects(&mut world);
+        assert_eq!(world.resource::<Runs>().0, 0);
+        assert!(reactor.fired_effects().is_empty());
+
+        // The effect is removed after it was queued.
+        let removed = reactor.new_signal(0);
+        let effect = reactor.new_deferred_effect(removed, count);
+        reactor.send_signal(removed, 1);
+        assert!(reactor.remove_effect(effect));
+        reactor.flush_effects(&mut world);
+        assert_eq!(world.resource::<Runs>().0, 0);
+        assert!(reactor.fired_effects().is_empty());
+        assert_eq!(reactor.pending_effect_count(), 0);
+    }
+
+    #[test]
+    fn remove_and_disable_effect() {
+        use bevy_ecs::prelude::*;
+
+        #[derive(Resource, Default)]
+        struct Seen(Vec<i32>);
+
+        let mut world = World::new();
+        world.init_resource::<Seen>();
+        let mut reactor = crate::ReactiveContext::<World>::default();
+        let record = |data: Res<crate::effect::EffectData<i32>>, mut seen: ResMut<Seen>| {
+            seen.0.push(**data);
+        };
+
+        // A removed effect is never queued again, and its observable keeps working.
+        let removed = reactor.new_signal(0);
+        let doubled = reactor.new_memo((removed,), |(removed,)| removed * 2);
+        let effect = reactor.new_deferred_effect(removed, record);
+        assert!(reactor.remove_effect(effect));
+        assert!(!reactor.remove_effect(effect));
+        reactor.send_signal(removed, 1);
+        assert_eq!(reactor.pending_effect_count(), 0);
+        reactor.flush_effects(&mut world);
+        assert!(world.resource::<Seen>().0.is_empty());
+        assert_eq!(*reactor.read(doubled), 2);
+
+        // Changes made while an effect is disabled are not replayed when it is enabled again.
+        let paused = reactor.new_signal(0);
+        let effect = reactor.new_deferred_effect(paused, record);
+        reactor.set_effect_enabled(effect, false);
+        reactor.send_signal(paused, 1);
+        reactor.set_effect_enabled(effect, true);
+        reactor.flush_effects(&mut world);
+        assert!(world.resource::<Seen>().0.is_empty());
+        reactor.send_signal(paused, 2);
+        reactor.flush_effects(&mut world);
+        assert_eq!(world.resource::<Seen>().0, vec![2]);
+    }
+
+    #[test]
+    fn pre_flush_hook() {
+        use bevy_ecs::prelude::*;
+        use std::sync::{Arc, Mutex};
+
+        let mut world = World::new();
+        let mut reactor = crate::ReactiveContext::<World>::default();
+
+        let batches = Arc::new(Mutex::new(Vec::new()));
+        let seen = batches.clone();
+        reactor.set_pre_flush_hook(move |pending| {
+            assert!(pending.iter().all(|p| p.name.is_some()));
+            let batch: Vec<_> = pending.iter().map(|p| p.effect.reactor_entity).collect();
+            seen.lock().unwrap().push(batch);
+        });
+        let score = reactor.new_signal(1);
+        let name = reactor.new_signal("Jane");
+        let score_effect = reactor.new_deferred_effect(score, || {});
+        let name_effect = reactor.new_deferred_effect(name, || {});
+
+        reactor.flush_effects(&mut world); // nothing to run, the hook isn't called
+        assert!(batches.lock().unwrap().is_empty());
+
+        reactor.send_signal(score, 2);
+        reactor.send_signal(name, "John");
+        reactor.flush_effects(&mut world);
+        assert_eq!(
+            *batches.lock().unwrap(),
+            vec![vec![
+                score_effect.reactor_entity,
+                name_effect.reactor_entity
+            ]]
+        );
+    }
+
+    #[test]
+    fn pre_flush_hook_reorder() {
+        use bevy_ecs::prelude::*;
+        use std::sync::{Arc, Mutex};
+
+        let mut world = World::new();
+        let mut reactor = crate::ReactiveContext::<World>::default();
+        let order = Arc::new(Mutex::new(Vec::new()));
+
+        reactor.set_pre_flush_hook(|pending| pending.reverse());
+        let a = reactor.new_signal(1);
+        let b = reactor.new_signal(1);
+        let log = order.clone();
+        reactor.new_deferred_effect(a, move || log.lock().unwrap().push("a"));
+        let log = order.clone();
+        reactor.new_deferred_effect(b, move || log.lock().unwrap().push("b"));
+
+        reactor.send_signal(a, 2);
+        reactor.send_signal(b, 2);
+        reactor.flush_effects(&mut world);
+        assert_eq!(*order.lock().unwrap(), ["b", "a"]);
+    }
+
+    #[test]
+    fn multi_effect() {
+        use crate::effect::EffectData;
+        use bevy_ecs::prelude::*;
+
+        #[derive(Resource, Default)]
+        struct Renders(Vec<&'static str>);
+
+        let mut world = World::new();
+        world.init_resource::<Renders>();
+        let mut reactor = crate::ReactiveContext::<World>::default();
+
+        let name = reactor.new_signal("Ferris".to_string());
+        let age = reactor.new_signal(7_u32);
+        reactor.new_multi_effect(
+            (name, age),
+            |data: Res<EffectData<(String, u32)>>, mut renders: ResMut<Renders>| {
+                if data.input_changed(0) {
+                    renders.0.push("header");
+                }
+                if data.input_changed(1) {
+                    renders.0.push("badge");
+                }
+            },
+        );
+
+        reactor.send_signal(age, 8);
+        reactor.flush_effects(&mut world);
+        assert_eq!(world.resource::<Renders>().0, ["badge"]);
+
+        reactor.send_signal(name, "Corro".to_string());
+        reactor.send_signal(age, 9);
+        reactor.flush_effects(&mut world);
+        assert_eq!(world.resource::<Renders>().0, ["badge", "header", "badge"]);
+
+        reactor.flush_effects(&mut world);
+        assert_eq!(world.resource::<Renders>().0.len(), 3);
+    }
+
+    #[test]
+    fn multi_effect_reenabled() {
+        use bevy_ecs::prelude::*;
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        };
+
+        let mut world = World::new();
+        let mut reactor = crate::ReactiveContext::<World>::default();
+        let runs = Arc::new(AtomicUsize::new(0));
+        let counter = runs.clone();
+
+        let a = reactor.new_signal(1);
+        let b = reactor.new_signal(1);
+        let effect = reactor.new_multi_effect((a, b), move || {
+            counter.fetch_add(1, Ordering::Relaxed);
+        });
+
+        reactor.set_effect_enabled(effect, false);
+        reactor.send_signal(a, 2);
+        reactor.flush_effects(&mut world);
+        assert_eq!(runs.load(Ordering::Relaxed), 0);
+
+        reactor.set_effect_enabled(effect, true);
+        reactor.send_signal(a, 3);
+        reactor.send_signal(b, 3);
+        reactor.flush_effects(&mut world);
+        assert_eq!(runs.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn pending_effect_count() {
+        use bevy_ecs::prelude::*;
+
+        let mut world = World::new();
+        let mut reactor = crate::ReactiveContext::<World>::default();
+
+        let n = reactor.new_signal(0);
+        reactor.new_deferred_effect(n, || {});
+        for i in 1..=3 {
+            reactor.send_signal(n, i);
+        }
+        assert_eq!(reactor.pending_effect_count(), 3);
+        reactor.flush_effects(&mut world);
+        assert_eq!(reactor.pending_effect_count(), 0);
+    }
+
+    #[test]
+    fn run_effect_now() {
+        use crate::effect::EffectData;
+        use bevy_ecs::prelude::*;
+
+        #[derive(Resource, Default)]
+        struct Title(String);
+
+        let mut world = World::new();
+        world.init_resource::<Title>();
+        let mut reactor = crate::ReactiveContext::<World>::default();
+
+        let name = reactor.new_signal("Untitled".to_string());
+        let effect = reactor.new_deferred_effect(
+            name,
+            |data: Res<EffectData<String>>, mut title: ResMut<Title>| title.0 = data.clone(),
+        );
+        reactor.flush_effects(&mut world);
+        assert_eq!(world.resource::<Title>().0, "");
+
+        assert!(reactor.run_effect_now(effect, &mut world));
+        assert_eq!(world.resource::<Title>().0, "Untitled");
+
+        reactor.remove_effect(effect);
+        assert!(!reactor.run_effect_now(effect, &mut world));
+    }
+
+    #[test]
+    fn reactive_reader() {
+        use crate::effect::{EffectData, ReactiveReader};
+        use bevy_ecs::prelude::*;
+
+        #[derive(Resource, Default)]
+        struct Label(String);
+
+        let mut world = World::new();
+        world.init_resource::<Label>();
+        let mut reactor = crate::ReactiveContext::<World>::default();
+
+        let unit = reactor.new_signal("m");
+        let distance = reactor.new_signal(1.5);
+        reactor.new_deferred_effect(
+            distance,
+            move |data: Res<EffectData<f64>>, reader: ReactiveReader, mut label: ResMut<Label>| {
+                label.0 = format!("{}{}", **data, reader.read(unit));
+            },
+        );
+
+        reactor.send_signal(unit, "km");
+        reactor.send_signal(distance, 2.0);
+        reactor.flush_effects(&mut world);
+        assert_eq!(world.resource::<Label>().0, "2km");
+    }
+
+    #[test]
+    fn fallible_effect() {
+        use crate::effect::EffectData;
+        use bevy_ecs::prelude::*;
+        use std::sync::{Arc, Mutex};
+
+        let mut world = World::new();
+        let mut reactor = crate::ReactiveContext::<World>::default();
+
+        let errors = Arc::new(Mutex::new(Vec::new()));
+        let collected = errors.clone();
+        reactor.set_effect_error_handler(move |_, error| {
+            collected.lock().unwrap().push(error.to_string());
+        });
+        let path = reactor.new_signal(String::from("save.ron"));
+        reactor.new_fallible_effect(path, |path: Res<EffectData<String>>| {
+            match path.ends_with(".ron") {
+                true => Ok(()),
+                false => Err(format!("unsupported file: {}", **path)),
+            }
+        });
+
+        reactor.send_signal(path, "save.json".to_string());
+        reactor.flush_effects(&mut world);
+        reactor.send_signal(path, "backup.ron".to_string());
+        reactor.flush_effects(&mut world);
+        assert_eq!(*errors.lock().unwrap(), ["unsupported file: save.json"]);
+    }
+
+    #[test]
+    fn rx_macro() {
+        let mut reactor = crate::ReactiveContext::<()>::default();
+        let offset = 100;
+        crate::rx! {
+            reactor;
+            let a = signal(1);
+            let b = signal(2);
+            let sum = memo(a + b + offset);
+            let label = lazy_memo(sum => format!("{sum}"));
+            let listed = memo(a, b => a * b);
+            let shadowed = memo([1, 2].iter().map(|b| a * b).sum::<i32>());
+            let scoped = memo({
+                let b = 3;
+                a * b
+            });
+        }
+        assert_eq!(*reactor.read(sum), 103);
+        assert_eq!(reactor.read(label), "103");
+        assert_eq!(*reactor.read(listed), 2);
+        assert_eq!((*reactor.read(shadowed), *reactor.read(scoped)), (3, 3));
+
+        reactor.reset_propagation_counters();
+        reactor.send_signal(b, 5);
+        assert_eq!(reactor.propagation_counters().recomputes, 2); // `sum` and `listed`
+        assert_eq!(reactor.read(label), "106");
+
+        reactor.send_signal(a, 2);
+        assert_eq!((*reactor.read(shadowed), *reactor.read(scoped)), (6, 6));
+    }
+
+    #[test]
+    fn memo_set_derive() {
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let base = reactor.new_signal(10);
+        let bonus = reactor.new_signal(5);
+        let damage = reactor.new_memo((base,), |(base,)| base * 2);
+        let label = reactor.new_memo((damage,), |(damage,)| format!("{damage} dmg"));
+
+        damage.set_derive(&mut reactor, (base, bonus), |(base, bonus)| base + bonus);
+        assert_eq!(*reactor.read(label), "15 dmg");
+
+        reactor.send_signal(bonus, 7);
+        assert_eq!(*reactor.read(label), "17 dmg");
+
+        // The old input is no longer subscribed to.
+        reactor.reset_propagation_counters();
+        damage.set_derive(&mut reactor, (bonus,), |(bonus,)| *bonus);
+        reactor.send_signal(base, 1);
+        assert_eq!(reactor.propagation_counters().recomputes, 2); // `damage`, then `label`
+        assert_eq!(*reactor.read(label), "7 dmg");
+    }
+
+    #[test]
+    fn lazy_memo_set_derive() {
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        };
+
+        let mut reactor = crate::ReactiveContext::<()>::default();
+        let runs = Arc::new(AtomicUsize::new(0));
+
+        let a = reactor.new_signal(1);
+        let doubled = reactor.new_lazy_memo((a,), |(a,)| a * 2);
+        let counter = runs.clone();
+        doubled.set_derive(&mut reactor, (a,), move |(a,)| {
+            counter.fetch_add(1, Ordering::Relaxed);
+            a * 3
+        });
+        reactor.send_signal(a, 2);
+        assert_eq!(runs.load(Ordering::Relaxed), 0); // still lazy
+
+        assert_eq!(*reactor.read(doubled), 6);
+        assert_eq!(runs.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn unchanged_values_queue_no_effects() {
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let n = reactor.new_signal(4);
+        let parity = reactor.new_memo((n,), |(n,)| n % 2);
+        reactor.new_deferred_effect(n, || {});
+        reactor.new_deferred_effect(parity, || {});
+
+        reactor.send_signal(n, 4);
+        assert_eq!(reactor.pending_effect_count(), 0);
+
+        reactor.send_signal(n, 6); // `parity` recomputes to the same value
+        assert_eq!(reactor.pending_effect_count(), 1);
+
+        reactor.send_signal(n, 6);
+        assert_eq!(reactor.pending_effect_count(), 1);
+    }
+
+    #[test]
+    fn projected_read() {
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let party = reactor.new_signal(vec!["ada", "grace", "linus"]);
+        let nodes = reactor.node_count();
+        assert_eq!(reactor.projected_read(party, |party| party.len()), 3);
+        assert_eq!(
+            reactor.projected_read(party, |party| party[1].to_uppercase()),
+            "GRACE"
+        );
+        assert_eq!(reactor.node_count(), nodes);
+    }
+
+    #[test]
+    fn structured_read_errors() {
+        use crate::error::RxError;
+
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let a = reactor.new_signal(1);
+        let b = reactor.new_memo((a,), |(a,)| a * 2);
+        let c = reactor.new_memo((b,), |(b,)| b + 1);
+        assert_eq!(reactor.try_read_many((a, c)), Ok((1, 3)));
+
+        assert_eq!(
+            b.try_set_derive(&mut reactor, (c,), |(c,)| *c),
+            Err(RxError::Cycle(b.reactor_entity))
+        );
+        assert_eq!(*reactor.read(c), 3); // unchanged
+
+        reactor.dispose(b);
+        assert_eq!(
+            reactor.try_read_pair(a, b).err(),
+            Some(RxError::NodeDisposed(b.reactor_entity))
+        );
+        assert!(reactor.try_projected_read(b, |b| b + 1).is_err());
+        assert!(b.try_set_derive(&mut reactor, (a,), |(a,)| *a).is_err());
+    }
+
+    #[test]
+    fn is_observed() {
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let query = reactor.new_signal("rust");
+        let results = reactor.new_memo((query,), |(query,)| query.len());
+        let unused = reactor.new_signal(0);
+        assert!(reactor.is_observed(query));
+        assert!(!reactor.is_observed(results));
+        assert!(!reactor.is_observed(unused));
+        assert!(!reactor.is_observed_by_effect(query));
+
+        let _subscription = reactor.on_change(unused, |_| {});
+        assert!(reactor.is_observed(unused));
+
+        reactor.new_deferred_effect(results, || {});
+        assert!(reactor.is_observed(results));
+        assert!(reactor.is_observed_by_effect(query));
+    }
+
+    #[test]
+    fn node_meta() {
+        use bevy_ecs::prelude::*;
+
+        #[derive(Component, Debug, PartialEq)]
+        struct Owner(&'static str);
+
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let hp = reactor.new_signal(10);
+        let alive = reactor.new_memo((hp,), |(hp,)| *hp > 0);
+        assert!(reactor.insert_meta(hp, Owner("player")));
+        assert!(reactor.insert_meta(alive, Owner("player")));
+        assert_eq!(reactor.meta::<Owner>(hp), Some(&Owner("player")));
+        assert_eq!(reactor.iter_meta::<Owner>().count(), 2);
+
+        assert_eq!(reactor.remove_meta::<Owner>(alive), Some(Owner("player")));
+        reactor.dispose(hp);
+        assert_eq!(reactor.iter_meta::<Owner>().count(), 0);
+        assert!(!reactor.insert_meta(hp, Owner("enemy")));
+    }
+
+    #[test]
+    fn deterministic_traversal() {
+        use std::sync::{Arc, Mutex};
+
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let mut reactor = crate::ReactiveContext::<()>::default();
+        reactor.set_deterministic(true);
+
+        let n = reactor.new_signal(0);
+        let memos: Vec<_> = (0..4)
+            .map(|i| {
+                let order = order.clone();
+                reactor.new_memo((n,), move |(n,)| {
+                    order.lock().unwrap().push(i);
+                    n + i
+                })
+            })
+            .collect();
+        // Recomputing a memo out of band moves it to the end of the signal's subscribers.
+        memos[1].force_recompute(&mut reactor);
+        memos[0].force_recompute(&mut reactor);
+
+        order.lock().unwrap().clear();
+        reactor.send_signal(n, 1);
+        assert_eq!(*order.lock().unwrap(), [0, 1, 2, 3]);
+
+        order.lock().unwrap().clear();
+        reactor.set_traversal(crate::traversal::Traversal::BreadthFirst);
+        reactor.send_signal(n, 2);
+        assert_eq!(*order.lock().unwrap(), [0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn with() {
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let name = reactor.new_signal(String::from("ferris"));
+        let greeting = reactor.new_memo((name,), |(name,)| format!("hello {name}"));
+        let len = name.with(&reactor, |name| name.len());
+        reactor.send_signal(name, "x".repeat(len + 1));
+        assert_eq!(greeting.with(&reactor, |greeting| greeting.len()), 13);
+        assert!(name
+            .read_only()
+            .with(&reactor, |name| name.starts_with('x')));
+    }
+
+    #[test]
+    fn memo_write_rejected() {
+        use crate::{error::RxError, signal::Signal};
+        use std::marker::PhantomData;
+
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let a = reactor.new_signal(2);
+        let double = reactor.new_memo((a,), |(a,)| a * 2);
+        // A handle that aliases the memo's node, which the public API can't construct.
+        let alias = Signal::<i32> {
+            reactor_entity: double.reactor_entity,
+            p: PhantomData,
+        };
+        assert_eq!(
+            reactor.try_send_signal(alias, 100),
+            Err(RxError::MemoWrite(double.reactor_entity))
+        );
+        assert_eq!(*reactor.read(double), 4);
+        assert!(reactor.try_send_signal(a, 3).is_ok());
+        assert_eq!(*reactor.read(double), 6);
+    }
+
+    #[test]
+    fn new_signals() {
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let tiles = reactor.new_signals((0..100).map(|i| i % 7));
+        assert_eq!(tiles.len(), 100);
+        assert_eq!(*reactor.read(tiles[10]), 3);
+
+        let total = reactor.new_memo((tiles[10], tiles[11]), |(a, b)| a + b);
+        reactor.send_signal(tiles[11], 0);
+        assert_eq!(*reactor.read(total), 3);
+        tiles[11].reset(&mut reactor);
+        assert_eq!(*reactor.read(total), 7);
+    }
+
+    #[test]
+    fn scopes() {
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let volume = reactor.new_signal(5);
+        let (screen, (label, inner)) = reactor.scope(|reactor| {
+            let draft = reactor.new_signal(7);
+            let label = reactor.new_memo((volume, draft), |(v, d)| format!("{v}/{d}"));
+            reactor.new_deferred_effect(volume, || {});
+            let (inner, _) = reactor.scope(|reactor| reactor.new_memo((draft,), |(d,)| d + 1));
+            (label, inner)
+        });
+        let outside = reactor.new_memo((volume,), |(v,)| v * 2);
+        let nodes = reactor.node_count();
+
+        assert_eq!(reactor.dispose_scope(screen), 3);
+        assert_eq!(reactor.node_count(), nodes - 3);
+        assert!(reactor.try_read(label).is_err());
+        assert_ne!(screen, inner);
+        assert_eq!(reactor.dispose_scope(inner), 0);
+
+        reactor.send_signal(volume, 6);
+        assert_eq!(*reactor.read(outside), 12);
+        assert_eq!(reactor.pending_effect_count(), 0);
+    }
+
+    #[test]
+    fn memo_then() {
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let text = reactor.new_signal(String::from("12"));
+        let parsed = reactor.new_memo((text,), |(text,)| text.parse::<u32>().ok());
+        let doubled = parsed.and_then(&mut reactor, |n| n.checked_mul(2));
+        let label = doubled.then(&mut reactor, |n| format!("{n:?}"));
+        assert_eq!(*reactor.read(label), "Some(24)");
+
+        reactor.reset_propagation_counters();
+        reactor.send_signal(text, String::from("012")); // parses to the same number
+        assert_eq!(reactor.propagation_counters().recomputes, 1);
+
+        reactor.send_signal(text, String::from("twelve"));
+        assert_eq!(*reactor.read(label), "None");
+    }
+
+    #[test]
+    fn send_signal_force() {
+        use crate::effect::EffectData;
+        use bevy_ecs::prelude::*;
+
+        #[derive(Resource, Default)]
+        struct Fetches(u32);
+
+        let mut world = World::new();
+        world.init_resource::<Fetches>();
+        let mut reactor = crate::ReactiveContext::<World>::default();
+        let url = reactor.new_signal("example.com");
+        let len = reactor.new_memo((url,), |(url,)| url.len());
+        reactor.new_deferred_effect(url, |_: Res<EffectData<&str>>, mut n: ResMut<Fetches>| {
+            n.0 += 1
+        });
+
+        reactor.send_signal(url, "example.com");
+        reactor.flush_effects(&mut world);
+        assert_eq!(world.resource::<Fetches>().0, 0);
+
+        reactor.reset_propagation_counters();
+        url.send_force(&mut reactor, "example.com");
+        reactor.flush_effects(&mut world);
+        assert_eq!(world.resource::<Fetches>().0, 1);
+        assert_eq!(reactor.propagation_counters().recomputes, 1);
+        assert_eq!(*reactor.read(len), 11);
+    }
+
+    #[test]
+    fn signal_from_receiver() {
+        use crate::receiver::ReceiverMode;
+        use std::sync::mpsc;
+
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let (sender, receiver) = mpsc::channel();
+        let latest = reactor.signal_from_receiver(receiver, ReceiverMode::Latest);
+        let (all_sender, all_receiver) = mpsc::channel();
+        let all = reactor.signal_from_receiver(all_receiver, ReceiverMode::All);
+        let sum = reactor.new_fold(all, 0, |sum, item| sum + item.unwrap_or(0));
+        let updates = reactor.new_fold(latest, 0, |updates, _| updates + 1);
+
+        for i in 1..=3 {
+            sender.send(i).unwrap();
+            all_sender.send(i).unwrap();
+        }
+        assert_eq!(*reactor.read(latest), None);
+        reactor.poll_receivers();
+        assert_eq!(*reactor.read(latest), Some(3));
+        assert_eq!(*reactor.read(updates), 2); // the initial value, then only the latest item
+        assert_eq!(*reactor.read(sum), 6);
+
+        drop(sender);
+        reactor.poll_receivers();
+        assert_eq!(*reactor.read(latest), Some(3));
+    }
+
+    #[test]
+    fn connect() {
+        use crate::{
+            error::RxError, memo::Memo, observable::Observable, signal::Signal, ReactiveContext,
+        };
+
+        // A library that builds a subgraph, returning its input and output.
+        fn damage_subgraph<S>(rctx: &mut ReactiveContext<S>) -> (Signal<u32>, Memo<u32>) {
+            let strength = rctx.new_signal(0);
+            let damage = rctx.new_memo((strength,), |(strength,)| strength * 3);
+            (strength, damage)
+        }
+
+        let mut reactor = ReactiveContext::<()>::default();
+        let level = reactor.new_signal(2);
+        let strength = reactor.new_memo((level,), |(level,)| level + 1);
+        let (input, damage) = damage_subgraph(&mut reactor);
+        assert_eq!(
+            reactor.connect(damage, input),
+            Err(RxError::Cycle(input.reactive_entity()))
+        );
+
+        let input = reactor.connect(strength, input).unwrap();
+        assert_eq!(*reactor.read(damage), 9);
+        reactor.send_signal(level, 4);
+        assert_eq!(*reactor.read(input), 5);
+        assert_eq!(*reactor.read(damage), 15);
+
+        // The history and write map of the input stop applying once it follows its source.
+        let tracked = reactor.new_signal_mapped(0, |value: u32| value.min(10));
+        reactor.track_history(tracked, 4);
+        reactor.send_signal(tracked, 1);
+        let tracked = reactor.connect(strength, tracked).unwrap();
+        assert_eq!(*reactor.read(tracked), 5);
+        reactor.send_signal(level, 20);
+        assert_eq!(*reactor.read(tracked), 21);
+        let entity = tracked.reactive_entity();
+        assert!(!crate::history::RxHistory::<u32>::undo(
+            &mut reactor.reactive_state,
+            entity
+        ));
+        assert_eq!(*reactor.read(tracked), 21);
+    }
+
+    #[test]
+    fn import() {
+        let mut settings = crate::ReactiveContext::<()>::default();
+        let mut feature = crate::ReactiveContext::<()>::default();
+
+        let volume = settings.new_signal(5);
+        let muted = settings.new_memo((volume,), |(volume,)| *volume == 0);
+        let imported = feature.import(&mut settings, muted);
+        let label = feature.new_memo((imported,), |(muted,)| if *muted { "off" } else { "on" });
+        assert_eq!(*feature.read(label), "on");
+
+        settings.send_signal(volume, 0);
+        assert_eq!(*feature.read(label), "on"); // not polled yet
+        feature.poll_receivers();
+        assert_eq!(*feature.read(label), "off");
+
+        feature.dispose(imported);
+        feature.poll_receivers(); // drops the receiver
+        settings.send_signal(volume, 3); // fails to send, unsubscribing the sender
+        settings.send_signal(volume, 0);
+        assert!(!settings.is_observed(muted));
+    }
+
+    #[test]
+    fn hydrate() {
+        use crate::{effect::EffectData, hydrate::HydrateEffects};
+        use bevy_ecs::prelude::*;
+
+        #[derive(Resource, Default)]
+        struct Sent(Vec<i32>);
+
+        let mut world = World::new();
+        world.init_resource::<Sent>();
+        let mut reactor = crate::ReactiveContext::<World>::default();
+        let gold = reactor.new_signal(0);
+        let gems = reactor.new_signal(0);
+        let total = reactor.new_memo((gold, gems), |(gold, gems)| gold + gems * 10);
+        reactor.new_deferred_effect(
+            total,
+            |total: Res<EffectData<i32>>, mut sent: ResMut<Sent>| sent.0.push(**total),
+        );
+        reactor.reset_propagation_counters();
+
+        reactor.hydrate(HydrateEffects::Suppress, |hydration| {
+            hydration.set(gold, 5);
+            hydration.set(gems, 2);
+            assert_eq!(*hydration.read(total), 0); // not recomputed until the pass ends
+        });
+        reactor.flush_effects(&mut world);
+        assert_eq!(*reactor.read(total), 25);
+        assert_eq!(reactor.propagation_counters().recomputes, 1);
+        assert!(world.resource::<Sent>().0.is_empty());
+
+        reactor.hydrate(HydrateEffects::Queue, |hydration| hydration.set(gold, 7));
+        reactor.flush_effects(&mut world);
+        assert_eq!(world.resource::<Sent>().0, [27]);
+    }
+
+    #[test]
+    fn significant_effect() {
+        use bevy_ecs::prelude::*;
+
+        #[derive(Resource, Default)]
+        struct Seen(Vec<f32>);
+
+        let mut world = World::new();
+        world.init_resource::<Seen>();
+        let mut reactor = crate::ReactiveContext::<World>::default();
+        let n = reactor.new_signal(0.0_f32);
+        reactor.new_significant_effect(
+            n,
+            |last, new| (new - last).abs() >= 1.0,
+            |data: Res<crate::effect::EffectData<f32>>, mut seen: ResMut<Seen>| seen.0.push(**data),
+        );
+
+        // Small changes accumulate against the last value the effect ran with.
+        for i in 1..=15 {
+            reactor.send_signal(n, i as f32 * 0.1);
+            reactor.flush_effects(&mut world);
+        }
+        assert_eq!(world.resource::<Seen>().0, vec![1.0]);
+    }
+
+    #[test]
+    fn reset() {
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let name = reactor.new_signal("Jane".to_string());
+        let age = reactor.new_signal(45);
+        let greeting = reactor.new_memo((name, age), |(name, age)| format!("{name} {age}"));
+
+        reactor.send_signal(name, "Katie".to_string());
+        reactor.send_signal(age, 30);
+        name.reset(&mut reactor);
+        assert_eq!(reactor.read(greeting), "Jane 30");
+
+        reactor.send_signal(name, "Katie".to_string());
+        reactor.reset_all();
+        assert_eq!(reactor.read(greeting), "Jane 45");
+    }
+
+    #[test]
+    fn clear() {
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let n = reactor.new_signal(1);
+        let double = reactor.new_memo((n,), |(n,)| n * 2);
+        reactor.clear();
+        assert!(reactor.try_read(n).is_err());
+        assert!(reactor.try_read(double).is_err());
+
+        let m = reactor.new_signal(2);
+        assert_eq!(reactor.try_read(m), Ok(&2));
+        assert!(reactor.try_read(n).is_err());
+    }
+
+    #[test]
+    fn clear_queued() {
+        use crate::memo::MemoScheduling;
+        use bevy_ecs::prelude::*;
+
+        #[derive(Resource, Default)]
+        struct Runs(usize);
+
+        let mut world = World::new();
+        world.init_resource::<Runs>();
+        let mut reactor = crate::ReactiveContext::<World>::default();
+        let n = reactor.new_signal(1);
+        let scheduled = reactor.new_memo((n,), |(n,)| n * 2);
+        scheduled.set_scheduling(&mut reactor, MemoScheduling::Scheduled);
+        reactor.new_deferred_effect(n, |mut runs: ResMut<Runs>| runs.0 += 1);
+        let echo = reactor.new_memo((n,), |(n,)| *n);
+        reactor.new_debounced_effect(echo, 2, |mut runs: ResMut<Runs>| runs.0 += 1);
+
+        reactor.send_signal(n, 2);
+        reactor.flush_effects(&mut world);
+        reactor.send_signal(n, 3);
+        assert_eq!(world.resource::<Runs>().0, 1);
+        assert_eq!(reactor.pending_effect_count(), 2); // the new run, and the held back debounce
+
+        reactor.clear();
+        assert_eq!(reactor.pending_effect_count(), 0);
+        reactor.flush_effects(&mut world);
+        assert!(reactor.fired_effects().is_empty());
+        assert_eq!(world.resource::<Runs>().0, 1);
+    }
+
+    #[test]
+    fn clear_stalled() {
+        use crate::traversal::Traversal;
+
+        let mut reactor = crate::ReactiveContext::<()>::default();
+        reactor.set_traversal(Traversal::BreadthFirst);
+        reactor.set_max_propagation_steps(Some(1));
+        let s = reactor.new_signal(1);
+        let a = reactor.new_memo((s,), |(s,)| s + 1);
+        reactor.new_memo((a,), |(a,)| a + 1);
+        reactor.send_signal(s, 2); // stops before recomputing the second memo
+
+        reactor.clear();
+        let s = reactor.new_signal(1);
+        let c = reactor.new_memo((s,), |(s,)| s * 10);
+        // The stalled memo from before the clear doesn't use up the step.
+        reactor.send_signal(s, 2);
+        assert_eq!(*reactor.read(c), 20);
+    }
+
+    #[test]
+    fn iter_observables() {
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let a = reactor.new_signal(1u32);
+        let b = reactor.new_signal(2u32);
+        reactor.new_signal(3i32);
+        reactor.new_memo((a, b), |(a, b)| a + b);
+
+        let mut values: Vec<u32> = reactor.iter_observables::<u32>().map(|(_, v)| *v).collect();
+        values.sort();
+        assert_eq!(values, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn snapshot() {
+        let mut reactor = crate::ReactiveContext::<()>::default();
+        reactor.register_serializable::<u32>(
+            |value| value.to_le_bytes().to_vec(),
+            |bytes| Some(u32::from_le_bytes(bytes.try_into().ok()?)),
+        );
+
+        let a = reactor.new_signal(1u32);
+        let b = reactor.new_signal(2u32);
+        let sum = reactor.new_memo((a, b), |(a, b)| a + b);
+        let snapshot = reactor.snapshot();
+        assert_eq!(snapshot.len(), 2);
+
+        reactor.send_signal(a, 10);
+        reactor.send_signal(b, 20);
+        reactor.restore(&snapshot);
+        assert_eq!(*reactor.read(sum), 3);
+    }
+
+    #[test]
+    fn history() {
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let n = reactor.new_signal(0);
+        let double = reactor.new_memo((n,), |(n,)| n * 2);
+        reactor.track_history(n, 2);
+        for i in 1..=3 {
+            reactor.send_signal(n, i);
+            reactor.send_signal(n, i); // no-op writes are not recorded
+        }
+
+        assert!(reactor.undo(n));
+        assert!(reactor.undo(n));
+        assert!(!reactor.undo(n)); // the oldest value was dropped by the capacity
+        assert_eq!(*reactor.read(double), 2);
+
+        assert!(reactor.redo(n));
+        assert_eq!(*reactor.read(n), 2);
+
+        reactor.send_signal(n, 10);
+        assert!(!reactor.redo(n)); // a new write discards the redo history
+        assert!(reactor.undo(n));
+        assert_eq!(*reactor.read(double), 4);
+    }
+
+    #[test]
+    fn read_many() {
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let name = reactor.new_signal("ferris".to_string());
+        let age = reactor.new_signal(8);
+        let older = reactor.new_memo((age,), |(age,)| age + 1);
+
+        assert_eq!(reactor.read_cloned(older), 9);
+        let (name, age, older) = reactor.read_many((name, age, older));
+        assert_eq!((name.as_str(), age, older), ("ferris", 8, 9));
+    }
+
+    #[test]
+    fn on_change() {
+        use std::sync::mpsc;
+
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let n = reactor.new_signal(1);
+        let double = reactor.new_memo((n,), |(n,)| n * 2);
+        let (sender, receiver) = mpsc::channel();
+        let subscription = reactor.on_change(double, move |value| sender.send(*value).unwrap());
+
+        reactor.send_signal(n, 2);
+        reactor.send_signal(n, 2); // unchanged, so the callback is not invoked
+        reactor.send_signal(n, 3);
+        assert_eq!(receiver.try_iter().collect::<Vec<_>>(), vec![4, 6]);
+
+        drop(subscription);
+        reactor.send_signal(n, 4);
+        assert_eq!(receiver.try_iter().count(), 0);
+    }
+
+    #[test]
+    fn memo_channel() {
+        let mut reactor = crate::ReactiveContext::<()>::default();
+        let n = reactor.new_signal(1);
+        let doubled = reactor.new_memo((n,), |(n,)| n * 2);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        doubled.subscribe_to_channel(&mut reactor, sender);
+
+        reactor.send_signal(n, 2);
+        reactor.send_signal(n, 2);
+        reactor.send_signal(n, 3);
+        assert_eq!(receiver.try_iter().collect::<Vec<_>>(), [4, 6]);
+
+        drop(receiver);
+        reactor.send_signal(n, 4); // fails to send, unsubscribing the sender
+        reactor.send_signal(n, 5);
+        assert!(!reactor.is_observed(doubled));
+    }
+
+    #[test]
+    fn memo_with_const() {
+        use crate::error::RxError;
+
+        let mut reactor = crate::ReactiveContext::<()>::default();
+        let price = reactor.new_signal(100.0);
+        let total = reactor.new_memo_with_const((price,), 0.25, |tax_rate, (price,)| {
+            price * (1.0 + tax_rate)
+        });
+        assert_eq!(*reactor.read(total), 125.0);
+
+        total.set_const(&mut reactor, 0.5).unwrap();
+        assert_eq!(*reactor.read(total), 150.0);
+        assert!(reactor.is_observed(price)); // still subscribed to its input
+
+        reactor.send_signal(price, 200.0);
+        assert_eq!(*reactor.read(total), 300.0);
+
+        let error = total.set_const(&mut reactor, 1_u8).unwrap_err();
+        assert!(matches!(error, RxError::TypeMismatch { .. }));
+        assert_eq!(*reactor.read(total), 300.0);
+    }
+
+    #[test]
+    fn reentrant_signal() {
+        use crate::{
+            memo::{Memo, RxMemo},
+            observable::{Observable, RxObservableData, RxSubscribers},
+        };
+
+        use std::sync::{Arc, Mutex};
+
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let a = reactor.new_signal(0);
+        let b = reactor.new_signal_mapped(0, |b: i32| b.min(5));
+        let (a_entity, b_entity) = (a.reactive_entity(), b.reactive_entity());
+        let b_double = reactor.new_memo((b,), |(b,)| b * 2);
+
+        // Mirrors `a` into `b` by sending a signal while the graph is being traversed.
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let results = sent.clone();
+        let mirror: Memo<i32> = Memo::from_calculation(&mut reactor, |entity| {
+            RxMemo::from_fn(move |world, stack| {
+                RxSubscribers::add(world, a_entity, entity);
+                let value = *world.get::<RxObservableData<i32>>(a_entity).unwrap().data();
+                let changed = RxObservableData::send_signal(world, b_entity, value);
+                results.lock().unwrap().push(changed);
+                RxObservableData::update_value(world, stack, entity, value);
+            })
+        });
+
+        reactor.send_signal(a, 5);
+        assert_eq!(*reactor.read(mirror), 5);
+        assert_eq!(*reactor.read(b_double), 10);
+
+        // The queued write is diffed after it is mapped, so it's reported as unchanged.
+        reactor.send_signal(a, 6);
+        assert_eq!(*reactor.read(b_double), 10);
+        assert_eq!(*sent.lock().unwrap(), [false, true, false]);
+    }
+
+    #[test]
+    fn system_memo() {
+        use bevy_ecs::prelude::*;
+
+        #[derive(Component)]
+        struct Enemy;
+
+        let mut world = World::new();
+        let mut reactor = crate::ReactiveContext::<World>::default();
+
+        let multiplier = reactor.new_signal(1);
+        let count = reactor.new_polled_system_memo(|enemies: Query<&Enemy>| enemies.iter().count());
+        let scaled = reactor.new_system_memo((multiplier,), move |world: &World| {
+            world.entities().len() * 10
+        });
+        let doubled = reactor.new_memo((count,), |(count,)| count * 2);
+        assert!(reactor.try_read(count).is_err());
+
+        world.spawn(Enemy);
+        reactor.flush_effects(&mut world);
+        assert_eq!(*reactor.read(doubled), 2);
+        assert_eq!(*reactor.read(scaled), 10);
+
+        world.spawn(Enemy);
+        reactor.flush_effects(&mut world);
+        assert_eq!(*reactor.read(doubled), 4);
+        assert_eq!(*reactor.read(scaled), 10); // inputs unchanged, so not recomputed
+
+        reactor.send_signal(multiplier, 2);
+        reactor.flush_effects(&mut world);
+        assert_eq!(*reactor.read(scaled), 20);
+    }
+
+    #[test]
+    fn value_cutoff() {
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        };
+
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let c_runs = Arc::new(AtomicUsize::new(0));
+        let counter = c_runs.clone();
+        let a = reactor.new_signal(0);
+        let b = reactor.new_memo((a,), |(a,)| a % 2);
+        let c = reactor.new_memo((b,), move |(b,)| {
+            counter.fetch_add(1, Ordering::Relaxed);
+            b * 10
+        });
+        assert_eq!(c_runs.load(Ordering::Relaxed), 1);
+
+        reactor.reset_propagation_counters();
+        reactor.send_signal(a, 2); // `b` is still 0, so `c` must not run.
+        assert_eq!(c_runs.load(Ordering::Relaxed), 1);
+        let counters = reactor.propagation_counters();
+        assert_eq!((counters.recomputes, counters.cutoffs), (1, 1));
+
+        reactor.send_signal(a, 2); // Unchanged, so not even `b` is recomputed.
+        assert_eq!(reactor.propagation_counters().recomputes, 1);
+
+        reactor.send_signal(a, 3);
+        assert_eq!(c_runs.load(Ordering::Relaxed), 2);
+        assert_eq!(*reactor.read(c), 10);
+    }
+
+    #[cfg(feature = "tracking")]
+    #[test]
+    fn recompute_tracker() {
+        use bevy_ecs::prelude::*;
+
+        let mut world = World::new();
+        let mut reactor = crate::ReactiveContext::<World>::default();
+
+        let a = reactor.new_signal(1);
+        let b = reactor.new_signal(2);
+        let sum = reactor.new_memo((a, b), |(a, b)| a + b);
+        let parity = reactor.new_memo((sum,), |(sum,)| sum % 2);
+        let effect = reactor.new_deferred_effect(parity, || {});
+
+        reactor.track_recomputes().reset();
+        reactor.write_coalesced(a, 2);
+        reactor.write_coalesced(b, 4);
+        reactor.flush_effects(&mut world);
+
+        let tracker = reactor.recompute_tracker().unwrap();
+        // `sum` is invalidated by both writes, but `parity` only changes once.
+        assert_eq!(tracker.count(sum), 2);
+        assert_eq!(tracker.count(parity), 1);
+        assert_eq!(tracker.effect_count(effect), 1);
+    }
+
+    #[cfg(feature = "tracking")]
+    #[test]
+    fn last_trigger() {
+        use crate::observable::Observable;
+
+        let mut reactor = crate::ReactiveContext::<()>::default();
+        let a = reactor.new_signal(1);
+        let b = reactor.new_signal(2);
+        let sum = reactor.new_memo((a, b), |(a, b)| a + b);
+        let double = reactor.new_memo((sum,), |(sum,)| sum * 2);
+        reactor.track_recomputes();
+        assert_eq!(reactor.last_trigger(sum), None);
+
+        reactor.send_signal(b, 3);
+        assert_eq!(reactor.last_trigger(sum), Some(b.reactive_entity()));
+        assert_eq!(reactor.last_trigger(double), Some(sum.reactive_entity()));
+        reactor.send_signal(a, 2);
+        assert_eq!(reactor.last_trigger(sum), Some(a.reactive_entity()));
+        sum.force_recompute(&mut reactor);
+        assert_eq!(reactor.last_trigger(double), Some(sum.reactive_entity()));
+
+        // A memo scheduled before the tracker is reset isn't attributed to that change.
+        let tripled = reactor.new_memo((a,), |(a,)| a * 3);
+        tripled.set_scheduling(&mut reactor, crate::memo::MemoScheduling::Scheduled);
+        reactor.send_signal(a, 3);
+        reactor.track_recomputes().reset();
+        reactor.flush_effects(&mut bevy_ecs::world::World::new());
+        assert_eq!(*reactor.read(tripled), 9);
+        assert_eq!(reactor.last_trigger(tripled), None);
+    }
+
+    #[cfg(feature = "local")]
+    #[test]
+    fn local_values() {
+        use std::{cell::RefCell, rc::Rc};
+
+        let mut reactor = crate::ReactiveContext::<()>::default();
+        let items = reactor.new_local_signal(Rc::new(RefCell::new(vec![1, 2])));
+        let shared = reactor.new_local_memo(items, Rc::clone);
+        let len = reactor.new_memo_from_local(shared, |items| items.borrow().len());
+        let doubled = reactor.new_memo((len,), |(len,)| len * 2);
+        assert_eq!(*reactor.read(doubled), 4);
+
+        reactor.read_local(items).borrow_mut().push(3);
+        assert_eq!(*reactor.read(len), 2); // Mutating in place doesn't notify.
+        reactor.send_local(items, Rc::new(RefCell::new(vec![4, 5, 6, 7])));
+        assert_eq!(*reactor.read(doubled), 8);
+        assert_eq!(*reactor.read_local(shared).borrow(), [4, 5, 6, 7]);
+    }
+
+    #[cfg(feature = "local")]
+    #[test]
+    fn local_values_disposed() {
+        use std::rc::Rc;
+
+        let mut reactor = crate::ReactiveContext::<()>::default();
+        let value = Rc::new(1);
+        let items = reactor.new_local_signal(value.clone());
+        let shared = reactor.new_local_memo(items, Rc::clone);
+        assert_eq!(Rc::strong_count(&value), 3);
+
+        assert!(reactor.dispose(shared));
+        assert_eq!(Rc::strong_count(&value), 2);
+        assert!(reactor.try_read_local(shared).is_err());
+
+        reactor.clear();
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+
+    #[cfg(feature = "profiling")]
+    #[test]
+    fn profile_report() {
+        use crate::observable::Observable;
+
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let n = reactor.new_signal(1u64);
+        let cheap = reactor.new_memo((n,), |(n,)| n + 1);
+        let expensive = reactor.new_memo((n,), |(n,)| {
+            std::thread::sleep(std::time::Duration::from_millis(2));
+            n * 2
+        });
+        reactor.reset_profile();
+        reactor.send_signal(n, 2);
+        reactor.send_signal(n, 3);
+
+        let report = reactor.profile_report();
+        assert_eq!(report.len(), 2);
+        assert_eq!(report[0].node, expensive.reactive_entity());
+        assert_eq!(report[1].node, cheap.reactive_entity());
+        assert_eq!(report[0].recomputes, 2);
+        assert!(report[0].min >= std::time::Duration::from_millis(2));
+    }
+
+    #[test]
+    fn last_changed_tick() {
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let a = reactor.new_signal(1);
+        let b = reactor.new_signal(1);
+        let sum = reactor.new_memo((a, b), |(a, b)| a + b);
+        let since = reactor.change_tick();
+
+        reactor.send_signal(b, 2);
+        reactor.send_signal(a, 1); // unchanged, so its tick is not updated
+        let a_tick = reactor.last_changed_tick(a).unwrap();
+        let b_tick = reactor.last_changed_tick(b).unwrap();
+        let sum_tick = reactor.last_changed_tick(sum).unwrap();
+        assert!(a_tick <= since && b_tick > since);
+        assert!(sum_tick > b_tick);
+        assert_eq!(sum_tick, reactor.change_tick());
+    }
+
+    #[test]
+    fn memo_values_not_cloned() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static CLONES: AtomicUsize = AtomicUsize::new(0);
+
+        #[derive(PartialEq)]
+        struct Mesh(Vec<f32>);
+        impl Clone for Mesh {
+            fn clone(&self) -> Self {
+                CLONES.fetch_add(1, Ordering::Relaxed);
+                Self(self.0.clone())
+            }
+        }
+
+        let mut reactor = crate::ReactiveContext::<()>::default();
+        let resolution = reactor.new_signal(4);
+        let mesh = reactor.new_memo((resolution,), |(n,)| Mesh(vec![0.0; *n]));
+        for n in 5..10 {
+            reactor.send_signal(resolution, n);
+        }
+        assert_eq!(reactor.read(mesh).0.len(), 9);
+        assert_eq!(CLONES.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn borrowed_effect() {
+        use crate::effect::EffectValue;
+        use bevy_ecs::prelude::*;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static CLONES: AtomicUsize = AtomicUsize::new(0);
+
+        #[derive(PartialEq)]
+        struct Mesh(Vec<f32>);
+        impl Clone for Mesh {
+            fn clone(&self) -> Self {
+                CLONES.fetch_add(1, Ordering::Relaxed);
+                Self(self.0.clone())
+            }
+        }
+
+        #[derive(Resource, Default)]
+        struct Uploaded(Vec<usize>);
+
+        let mut world = World::new();
+        world.init_resource::<Uploaded>();
+        let mut reactor = crate::ReactiveContext::<World>::default();
+        let resolution = reactor.new_signal(4);
+        let mesh = reactor.new_memo((resolution,), |(n,)| Mesh(vec![0.0; *n]));
+        reactor.new_borrowed_effect(
+            mesh,
+            |mesh: EffectValue<Mesh>, mut uploaded: ResMut<Uploaded>| uploaded.0.push(mesh.0.len()),
+        );
+        for n in 5..8 {
+            reactor.send_signal(resolution, n);
+            reactor.flush_effects(&mut world);
+        }
+        assert_eq!(world.resource::<Uploaded>().0, [5, 6, 7]);
+        assert_eq!(CLONES.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn duplicate_inputs() {
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let a = reactor.new_signal(2);
+        let b = reactor.new_signal(1);
+        let square = reactor.new_memo((a, a), |(x, y)| x * y);
+        let sum = reactor.new_memo((a, b), |(a, b)| a + b);
+
+        for n in 0..10 {
+            reactor.send_signal(a, n);
+        }
+        assert_eq!(*reactor.read(square), 81);
+        assert_eq!(*reactor.read(sum), 10);
+        // Recomputing `sum` when only `a` changes must not subscribe it to `b` again.
+        assert_eq!(reactor.stats().subscriber_edges, 3);
+    }
+
+    #[test]
+    fn high_fanout() {
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let scale = reactor.new_signal_high_fanout(1);
+        let memos: Vec<_> = (0..100)
+            .map(|i| reactor.new_memo((scale, scale), move |(a, b)| a * b + i))
+            .collect();
+        for n in 0..5 {
+            reactor.send_signal(scale, n);
+        }
+        assert_eq!(*reactor.read(memos[42]), 16 + 42);
+        assert_eq!(reactor.stats().subscriber_edges, 100);
+
+        // Switching away from an indexed signal unsubscribes from it.
+        let other = reactor.new_signal_high_fanout(10);
+        let selected = reactor.new_signal(scale);
+        let flat = reactor.flatten(selected);
+        reactor.send_signal(selected, other);
+        reactor.send_signal(scale, 5);
+        assert_eq!(*reactor.read(flat), 10);
+        reactor.send_signal(other, 11);
+        assert_eq!(*reactor.read(flat), 11);
+        assert_eq!(*reactor.read(memos[0]), 25);
+
+        // A regular signal indexes its subscribers once it has many, still skipping duplicates.
+        let edges = reactor.stats().subscriber_edges;
+        let offset = reactor.new_signal(0);
+        let memos: Vec<_> = (0..100)
+            .map(|i| reactor.new_memo((offset, offset), move |(a, b)| a + b + i))
+            .collect();
+        for n in 0..5 {
+            reactor.send_signal(offset, n);
+        }
+        assert_eq!(*reactor.read(memos[42]), 8 + 42);
+        assert_eq!(reactor.stats().subscriber_edges, edges + 100);
+    }
+
+    #[test]
+    fn cached_memo() {
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        };
+
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let toggle = reactor.new_signal(false);
+        let layout = reactor.new_cached_memo((toggle,), 2, move |(toggle,)| {
+            counter.fetch_add(1, Ordering::Relaxed);
+            if *toggle {
+                "wide"
+            } else {
+                "narrow"
+            }
+        });
+
+        for i in 0..10 {
+            reactor.send_signal(toggle, i % 2 == 0);
+        }
+        assert_eq!(*reactor.read(layout), "narrow");
+        assert_eq!(calls.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn coalesced_writes() {
+        use bevy_ecs::prelude::*;
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        };
+
+        let mut world = World::new();
+        let mut reactor = crate::ReactiveContext::<World>::default();
+
+        let recomputes = Arc::new(AtomicUsize::new(0));
+        let counter = recomputes.clone();
+        let x = reactor.new_signal(0.0);
+        let y = reactor.new_signal(0.0);
+        let length = reactor.new_memo((x, y), move |(x, y)| {
+            counter.fetch_add(1, Ordering::Relaxed);
+            f32::hypot(*x, *y)
+        });
+
+        for i in 0..=100 {
+            reactor.write_coalesced(x, i as f32 * 0.03);
+            reactor.write_coalesced(y, i as f32 * 0.04);
+        }
+        assert_eq!(*reactor.read(length), 0.0);
+
+        reactor.flush_effects(&mut world);
+        assert_eq!(*reactor.read(length), 5.0);
+        // Once on creation, and once per invalidated input during the flush, rather than once per
+        // write.
+        assert_eq!(recomputes.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn wait_for() {
+        let mut reactor = crate::ReactiveContext::<()>::default();
+        let count = reactor.new_signal(0);
+        let doubled = reactor.new_memo((count,), |(count,)| count * 2);
+        let ready = count.wait_for(&mut reactor, |count| *count >= 2);
+        let already = reactor.wait_for(doubled, |doubled| *doubled == 0);
+        let dropped = count.wait_for(&mut reactor, |_| true);
+        drop(dropped);
+
+        count.send(&mut reactor, 1);
+        count.send(&mut reactor, 3);
+        count.send(&mut reactor, 2);
+        let mut ready = Box::pin(ready);
+        assert_eq!(bevy_tasks::block_on(ready.as_mut()), 3);
+        // Polling again after the future resolved returns the same value.
+        assert_eq!(bevy_tasks::block_on(ready.as_mut()), 3);
+        assert_eq!(bevy_tasks::block_on(already), 0);
+
+        // A stale lazy memo is computed before it's checked.
+        let tripled = reactor.new_lazy_memo((count,), |(count,)| count * 3);
+        count.send(&mut reactor, 4);
+        let stale = reactor.wait_for(tripled, |tripled| *tripled == 12);
+        assert_eq!(bevy_tasks::block_on(stale), 12);
+
+        // A dropped future's check is removed without waiting for the observable to change.
+        let quiet = reactor.new_signal(0);
+        let callbacks = |reactor: &crate::ReactiveContext<()>| {
+            reactor
+                .reactive_state
+                .get::<crate::callback::RxCallbacks<i32>>(crate::Observable::reactive_entity(
+                    &quiet,
+                ))
+                .is_some()
+        };
+        drop(quiet.wait_for(&mut reactor, |quiet| *quiet > 0));
+        assert!(callbacks(&reactor));
+        reactor.flush_effects(&mut bevy_ecs::world::World::new());
+        assert!(!callbacks(&reactor));
+    }
+
+    #[test]
+    fn async_effect() {
+        use bevy_ecs::prelude::*;
+        use std::time::{Duration, Instant};
+
+        let mut world = World::new();
+        let mut reactor = crate::ReactiveContext::<World>::default();
+
+        let id = reactor.new_signal(0_u32);
+        let name = reactor.new_signal(String::new());
+        let loading = reactor.new_async_effect(id, name, |id| async move { format!("user {id}") });
+        id.send(&mut reactor, 7);
+        // The result is only written back once the task completes and the context is flushed.
+        assert_eq!(reactor.read(name), "");
+
+        let timeout = Instant::now() + Duration::from_secs(5);
+        while reactor.read(name).is_empty() && Instant::now() < timeout {
+            std::thread::sleep(Duration::from_millis(1));
+            reactor.flush_effects(&mut world);
+        }
+        assert_eq!(reactor.read(name), "user 7");
+
+        drop(loading);
+        id.send(&mut reactor, 8);
+        std::thread::sleep(Duration::from_millis(20));
+        reactor.flush_effects(&mut world);
+        assert_eq!(reactor.read(name), "user 7");
+    }
+
+    #[test]
+    fn max_propagation_steps() {
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let signal = reactor.new_signal(0);
+        let mut memo = reactor.new_memo((signal,), |(n,)| *n);
+        for _ in 0..10 {
+            memo = reactor.new_memo((memo,), |(n,)| n + 1);
+        }
+
+        reactor.set_max_propagation_steps(Some(5));
+        reactor.send_signal(signal, 1);
+        assert_eq!(*reactor.read(memo), 10); // stopped before reaching the end of the chain
+        reactor.send_signal(signal, 2);
+        assert_eq!(*reactor.read(memo), 10);
+
+        reactor.set_max_propagation_steps(None);
+        reactor.send_signal(signal, 3);
+        assert_eq!(*reactor.read(memo), 13);
+    }
+
+    #[test]
+    fn signal_capabilities() {
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let volume = reactor.new_signal(5);
+        let (reader, writer) = (volume.read_only(), volume.write_only());
+        let percent = reactor.new_memo((reader,), |(volume,)| volume * 10);
+
+        writer.send(&mut reactor, 7);
+        assert_eq!(*reader.read(&mut reactor), 7);
+        assert_eq!(*reactor.read(percent), 70);
+        writer.reset(&mut reactor);
+        assert_eq!(*reactor.read(reader), 5);
+    }
+
+    #[test]
+    fn debug_validate() {
+        use crate::{
+            observable::{Observable, RxSubscribers},
+            stats::GraphViolation,
+        };
+
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let a = reactor.new_signal(1);
+        let b = reactor.new_memo((a,), |(a,)| a + 1);
+        let c = reactor.new_memo((a, b), |(a, b)| a + b);
+        assert_eq!(reactor.debug_validate(), Vec::new());
+
+        // Corrupt the graph by hand to check each violation is found.
+        let (a, b, c) = (
+            a.reactive_entity(),
+            b.reactive_entity(),
+            c.reactive_entity(),
+        );
+        let stale = reactor.reactive_state.spawn_empty().id();
+        reactor.reactive_state.despawn(stale);
+        let mut subscribers = reactor.reactive_state.get_mut::<RxSubscribers>(a).unwrap();
+        subscribers.subscribers.extend([b, stale]);
+        let mut subscribers = reactor.reactive_state.get_mut::<RxSubscribers>(c).unwrap();
+        subscribers.subscribers.push(a);
+
+        let violations = reactor.debug_validate();
+        assert!(violations.contains(&GraphViolation::DuplicateSubscriber {
+            node: a,
+            subscriber: b
+        }));
+        assert!(violations.contains(&GraphViolation::DanglingSubscriber {
+            node: a,
+            subscriber: stale
+        }));
+        assert!(violations
+            .iter()
+            .any(|v| matches!(v, GraphViolation::Cycle(nodes) if nodes.contains(&c))));
+
+        reactor.reactive_state.despawn(b);
+        assert!(reactor
+            .debug_validate()
+            .contains(&GraphViolation::MissingInput { memo: c, input: b }));
+    }
+
+    #[test]
+    fn strict() {
+        use crate::observable::{Observable, RxSubscribers};
+
+        let mut reactor = crate::ReactiveContext::<()>::default();
+        reactor.set_strict(true);
+        let a = reactor.new_signal(1);
+        let b = reactor.new_memo((a,), |(a,)| a + 1);
+        let c = reactor.new_memo((a, b), |(a, b)| a + b);
+        reactor.send_signal(a, 2);
+        reactor.dispose(c);
+        reactor.send_signal(a, 3);
+        assert_eq!(*reactor.read(b), 4);
+
+        // Sending any signal checks the whole graph, not just the nodes it changed.
+        let d = reactor.new_signal(1);
+        let e = reactor.new_memo((d,), |(d,)| d * 2);
+        let d = d.reactive_entity();
+        let mut subscribers = reactor.reactive_state.get_mut::<RxSubscribers>(d).unwrap();
+        subscribers.subscribers.push(e.reactive_entity());
+        let duplicate = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            reactor.send_signal(a, 4);
+        }));
+        assert!(duplicate.is_err());
+
+        // Strict mode is only checked when it's enabled.
+        reactor.set_strict(false);
+        reactor.send_signal(a, 5);
+        assert_eq!(*reactor.read(a), 5);
+    }
+
+    #[test]
+    fn validated_signal() {
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let health = reactor.new_signal_validated(150, |health: i32| health.clamp(0, 100));
+        let changes = reactor.new_fold(health, 0, |changes, _| changes + 1);
+        assert_eq!(*reactor.read(health), 100);
+
+        reactor.send_signal(health, 120); // clamps to the current value, so nothing changes
+        assert_eq!(*reactor.read(changes), 1);
+
+        reactor.send_signal(health, -5);
+        assert_eq!(*reactor.read(health), 0);
+        assert_eq!(*reactor.read(changes), 2);
+    }
+
+    #[test]
+    fn type_mismatch() {
+        use crate::{error::RxError, memo::Memo, observable::Observable};
+        use std::marker::PhantomData;
+
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let signal = reactor.new_signal(1_i32);
+        let entity = signal.reactive_entity();
+        let wrong = Memo::<String> {
+            reactor_entity: entity,
+            p: PhantomData,
+        };
+        assert_eq!(
+            reactor.try_read(wrong),
+            Err(RxError::TypeMismatch {
+                entity,
+                expected: std::any::type_name::<String>(),
+                found: std::any::type_name::<i32>(),
+            })
+        );
+    }
+
+    #[test]
+    fn read_pair() {
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let name = reactor.new_signal("Jane".to_string());
+        let tags = reactor.new_signal(vec!["admin".to_string()]);
+        let count = reactor.new_memo((tags,), |(tags,)| tags.len());
+
+        let (name_ref, tags_ref) = reactor.read_pair(name, tags);
+        assert_eq!(
+            format!("{name_ref}: {}", tags_ref.join(", ")),
+            "Jane: admin"
+        );
+        let (name_ref, tags_ref, count_ref) = reactor.read3(name, tags, count);
+        assert_eq!((name_ref.len(), tags_ref.len(), *count_ref), (4, 1, 1));
+    }
+
+    #[test]
+    fn graph_builder() {
+        let mut reactor = crate::ReactiveContext::<()>::default();
+        let mut builder = reactor.graph_builder();
+
+        let add = |(a, b): (&i32, &i32)| a + b;
+        let a = builder.signal(1);
+        let b = builder.signal(2);
+        let sum = builder.memo((a, b), add);
+        builder.name("sum", sum);
+
+        // The same calculation on the same inputs reuses the existing memo.
+        assert_eq!(builder.memo((a, b), add), sum);
+        let offset = 10;
+        assert_ne!(builder.memo((a, b), move |(a, b)| a + b + offset), sum);
+
+        let sum = builder.get::<crate::memo::Memo<i32>>("sum").unwrap();
+        assert!(builder.get::<crate::signal::Signal<i32>>("sum").is_none());
+        let doubled = builder.memo((sum, sum), add);
+        assert_eq!(builder.dependencies().len(), 3);
+
+        assert_eq!(*reactor.read(doubled), 6);
+        assert_eq!(reactor.node_count(), 5);
+    }
+
+    #[test]
+    fn force_recompute() {
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        };
+
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        // Read out-of-band, so the graph doesn't know when it changes.
+        let external = Arc::new(AtomicUsize::new(1));
+        let source = external.clone();
+        let trigger = reactor.new_signal(());
+        let memo = reactor.new_memo((trigger,), move |_| source.load(Ordering::Relaxed));
+        let runs = Arc::new(AtomicUsize::new(0));
+        let counter = runs.clone();
+        let doubled = reactor.new_memo((memo,), move |(n,)| {
+            counter.fetch_add(1, Ordering::Relaxed);
+            n * 2
+        });
+
+        external.store(5, Ordering::Relaxed);
+        memo.force_recompute(&mut reactor);
+        assert_eq!(*reactor.read(doubled), 10);
+
+        memo.force_recompute(&mut reactor); // unchanged, but subscribers still recompute
+        assert_eq!(runs.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn dispose() {
+        use crate::{dispose::DisposedInputs, error::RxError};
+
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let a = reactor.new_signal(1);
+        let b = reactor.new_signal(2);
+        let sum = reactor.new_memo((a, b), |(a, b)| a + b);
+        let kept = reactor.new_memo((a, b), |(a, b)| a * b);
+        let doubled = reactor.new_memo((sum,), |(sum,)| sum * 2);
+        reactor.set_disposed_inputs(sum, DisposedInputs::Cascade);
+        reactor.set_disposed_inputs(doubled, DisposedInputs::Cascade);
+
+        assert!(reactor.dispose(b));
+        assert!(!reactor.dispose(b));
+        assert!(matches!(reactor.try_read(b), Err(RxError::NodeDisposed(_))));
+        assert!(matches!(
+            reactor.try_read(sum),
+            Err(RxError::NodeDisposed(_))
+        ));
+        assert!(matches!(
+            reactor.try_read(doubled),
+            Err(RxError::NodeDisposed(_))
+        ));
+        assert_eq!(*reactor.read(kept), 2);
+
+        reactor.send_signal(a, 5); // still subscribed to `a`, but can't recompute
+        assert_eq!(*reactor.read(kept), 2);
+        assert_eq!(reactor.node_count(), 2);
+    }
+
+    #[test]
+    fn send_disposed() {
+        use crate::error::RxError;
+
+        let mut reactor = crate::ReactiveContext::<()>::default();
+        let a = reactor.new_signal(1);
+        let b = reactor.new_signal(2);
+        let sum = reactor.new_memo((a, b), |(a, b)| a + b);
+
+        reactor.dispose(a);
+        let error = a.try_send(&mut reactor, 5);
+        assert!(matches!(error, Err(RxError::NodeDisposed(_))));
+
+        reactor.send_signal(b, 3);
+        assert!(matches!(reactor.try_read(a), Err(RxError::NodeDisposed(_))));
+        assert_eq!(*reactor.read(sum), 3); // can't recompute without `a`
+    }
+
+    #[test]
+    fn panic_during_traversal() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        let mut reactor = crate::ReactiveContext::<()>::default();
+        let a = reactor.new_signal(1);
+        let b = reactor.new_signal(1);
+        reactor.new_memo((a,), |(a,)| {
+            assert!(*a < 2, "calculation failed");
+            *a
+        });
+        let scaled = reactor.new_memo((b,), |(b,)| b * 10);
+
+        let failed = catch_unwind(AssertUnwindSafe(|| reactor.send_signal(a, 2)));
+        assert!(failed.is_err());
+
+        reactor.send_signal(b, 3);
+        assert_eq!(*reactor.read(scaled), 30);
+    }
+
+    #[test]
+    fn any_observable() {
+        use crate::observable::AnyObservable;
+
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let name = reactor.new_signal("Jane".to_string());
+        let age = reactor.new_signal(45);
+        let label = reactor.new_memo((name, age), |(name, age)| format!("{name} ({age})"));
+        let nodes: Vec<Box<dyn AnyObservable>> =
+            vec![Box::new(name), Box::new(age), Box::new(label)];
+
+        assert_eq!(nodes[1].data_type_id(), std::any::TypeId::of::<i32>());
+        assert_eq!(nodes[2].data_type_name(), std::any::type_name::<String>());
+        for node in nodes {
+            assert!(reactor.dispose(node));
+        }
+        assert_eq!(reactor.node_count(), 0);
+    }
+
+    #[test]
+    fn set_silent() {
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let a = reactor.new_signal(1);
+        let b = reactor.new_signal(2);
+        let sum = reactor.new_memo((a, b), |(a, b)| a + b);
+
+        assert!(a.set_silent(&mut reactor, 10));
+        assert!(b.set_silent(&mut reactor, 20));
+        assert!(!b.set_silent(&mut reactor, 20));
+        assert_eq!(*reactor.read(sum), 3); // stale until recomputed
+
+        reactor.recompute_subscribers(a);
+        assert_eq!(*reactor.read(sum), 30);
+    }
+
+    #[test]
+    fn replace_value() {
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let name = reactor.new_signal(String::from("Jane"));
+        let len = reactor.new_memo((name,), |(name,)| name.len());
+        let tick = reactor.change_tick();
+
+        let previous = reactor.replace_value(name, String::from("Jane"));
+        assert_eq!(previous, "Jane");
+        assert_eq!(
+            reactor.replace_value(name, String::from("Alexandra")),
+            "Jane"
+        );
+        assert_eq!(reactor.read(name), "Alexandra");
+        assert_eq!(reactor.change_tick(), tick);
+        assert_eq!(*reactor.read(len), 4); // stale until recomputed
+
+        reactor.recompute_subscribers(name);
+        assert_eq!(*reactor.read(len), 9);
+        assert_eq!(reactor.replace_value(len, 0), 9);
+        assert_eq!(*reactor.read(len), 0); // even memos are replaced as is
+    }
+
+    #[test]
+    fn lifecycle() {
+        use crate::observable::Observable;
+        use std::sync::{Arc, Mutex};
+
+        let mut reactor = crate::ReactiveContext::<()>::default();
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sensor = reactor.new_signal(0.0);
+        let (connects, disconnects) = (events.clone(), events.clone());
+        reactor.on_connect(sensor, move || connects.lock().unwrap().push("connect"));
+        reactor.on_disconnect(sensor, move || {
+            disconnects.lock().unwrap().push("disconnect")
+        });
+        assert!(events.lock().unwrap().is_empty());
+
+        let reading = reactor.new_memo((sensor,), |(sensor,)| sensor * 2.0);
+        assert_eq!(*events.lock().unwrap(), ["connect"]);
+        reactor.send_signal(sensor, 1.0); // resubscribing while propagating isn't a transition
+        assert_eq!(*events.lock().unwrap(), ["connect"]);
+
+        reactor.dispose(reading);
+        assert_eq!(*events.lock().unwrap(), ["connect", "disconnect"]);
+        let reading = reactor.new_memo((sensor,), |(sensor,)| sensor * 2.0);
+        reactor.unsubscribe(sensor, reading.reactive_entity());
+        assert_eq!(
+            *events.lock().unwrap(),
+            ["connect", "disconnect", "connect", "disconnect"]
+        );
+
+        reactor.send_signal(sensor, 2.0); // the memo isn't recomputed, as it was unsubscribed
+        reactor.subscribe(sensor, reading.reactive_entity());
+        reactor.dispose(sensor); // disposing a connected observable disconnects it
+        assert_eq!(events.lock().unwrap().len(), 6);
+    }
+
+    #[test]
+    fn scheduled_memo() {
+        use crate::memo::MemoScheduling;
+        use bevy_ecs::prelude::*;
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        };
+
+        let mut world = World::new();
+        let mut reactor = crate::ReactiveContext::<World>::default();
+        let a = reactor.new_signal(1);
+        let runs = Arc::new(AtomicUsize::new(0));
+        let counter = runs.clone();
+        let expensive = reactor.new_memo((a,), move |(a,)| {
+            counter.fetch_add(1, Ordering::Relaxed);
+            a * 10
+        });
+        let label = reactor.new_memo((expensive,), |(value,)| format!("{value}"));
+        expensive.set_scheduling(&mut reactor, MemoScheduling::Scheduled);
+
+        reactor.send_signal(a, 2);
+        reactor.send_signal(a, 3);
+        assert_eq!(*reactor.read(expensive), 10);
+        assert_eq!(reactor.read(label), "10");
+        reactor.flush_effects(&mut world);
+        assert_eq!(runs.load(Ordering::Relaxed), 2);
+        assert_eq!(reactor.read(label), "30");
+
+        reactor.send_signal(a, 4);
+        expensive.set_scheduling(&mut reactor, MemoScheduling::Eager);
+        assert_eq!(reactor.read(label), "40");
+        reactor.flush_effects(&mut world);
+        assert_eq!(runs.load(Ordering::Relaxed), 3);
+
+        expensive.set_scheduling(&mut reactor, MemoScheduling::Scheduled);
+        reactor.send_signal(a, 5);
+        let counter = runs.clone();
+        expensive.set_derive(&mut reactor, (a,), move |(a,)| {
+            counter.fetch_add(1, Ordering::Relaxed);
+            a * 100
+        });
+        assert_eq!(reactor.read(label), "40");
+        reactor.flush_effects(&mut world);
+        assert_eq!(runs.load(Ordering::Relaxed), 4);
+        assert_eq!(reactor.read(label), "500");
+        reactor.flush_effects(&mut world);
+        assert_eq!(runs.load(Ordering::Relaxed), 4);
+    }
+
+    #[test]
+    fn lazy_memo() {
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        };
+
+        let mut reactor = crate::ReactiveContext::<()>::default();
+        let (b_runs, d_runs) = (Arc::new(AtomicUsize::new(0)), Arc::new(AtomicUsize::new(0)));
+        let runs = |runs: &Arc<AtomicUsize>| runs.load(Ordering::Relaxed);
+
+        let a = reactor.new_signal(1);
+        let counter = b_runs.clone();
+        let b = reactor.new_lazy_memo((a,), move |(a,)| {
+            counter.fetch_add(1, Ordering::Relaxed);
+            a * 10
+        });
+        let c = reactor.new_memo((a,), |(a,)| a + 1);
+        let counter = d_runs.clone();
+        let d = reactor.new_lazy_memo((b, c), move |(b, c)| {
+            counter.fetch_add(1, Ordering::Relaxed);
+            b + c
+        });
+        assert_eq!((runs(&b_runs), runs(&d_runs)), (0, 0)); // not computed until read
+
+        assert_eq!(*reactor.read(d), 12); // computes the stale ancestor first
+        assert_eq!((runs(&b_runs), runs(&d_runs)), (1, 1));
+        reactor.send_signal(a, 2);
+        reactor.send_signal(a, 3);
+        assert_eq!((runs(&b_runs), runs(&d_runs)), (1, 1));
+        assert_eq!(*reactor.read(c), 4); // eager memos are still computed as usual
+        assert_eq!(*reactor.read(d), 34);
+        assert_eq!(*reactor.read(d), 34);
+        assert_eq!((runs(&b_runs), runs(&d_runs)), (2, 2));
+
+        // An eager subscriber computes the lazy memos it reads when it is recomputed.
+        let e = reactor.new_memo((d,), |(d,)| d * 2);
+        reactor.send_signal(a, 4);
+        let computed = (runs(&b_runs), runs(&d_runs));
+        assert_eq!(computed.0, 3);
+        assert_eq!(*reactor.read(e), 90);
+        assert_eq!(*reactor.read(b), 40);
+        assert_eq!((runs(&b_runs), runs(&d_runs)), computed); // already up to date
+    }
+
+    #[test]
+    fn dead_nodes() {
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let a = reactor.new_signal(1);
+        let double = reactor.new_memo((a,), |(a,)| a * 2);
+        let quad = reactor.new_memo((double,), |(d,)| d * 2);
+        let orphan = reactor.new_memo((a,), |(a,)| a + 1);
+
+        let mut dead = reactor.dead_nodes();
+        dead.sort();
+        let mut expected = vec![quad.reactor_entity, orphan.reactor_entity];
+        expected.sort();
+        assert_eq!(dead, expected);
+    }
+
+    #[test]
+    fn option_combinators() {
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let selection = reactor.new_signal(None::<u32>);
+        let selected = reactor.unwrap_or(selection, 0);
+        let valid = reactor.filter(selection, |id| *id < 100);
+        assert_eq!((*reactor.read(selected), *reactor.read(valid)), (0, None));
+
+        reactor.send_signal(selection, Some(7));
+        assert_eq!(
+            (*reactor.read(selected), *reactor.read(valid)),
+            (7, Some(7))
+        );
+
+        // Rejected values and `None`s are ignored, keeping the last valid selection.
+        reactor.send_signal(selection, Some(500));
+        assert_eq!(*reactor.read(valid), Some(7));
+        reactor.send_signal(selection, None);
+        assert_eq!(
+            (*reactor.read(selected), *reactor.read(valid)),
+            (0, Some(7))
+        );
+    }
+
+    #[test]
+    fn mapped_signal() {
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let query = reactor.new_signal_mapped("Hello".to_string(), |text| text.to_lowercase());
+        assert_eq!(reactor.read(query), "hello");
+        reactor.send_signal(query, "WORLD".to_string());
+        assert_eq!(reactor.read(query), "world");
+    }
+
+    #[test]
+    fn mapped_signal_restore() {
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let count = reactor.new_signal_mapped(1, |x| x + 1);
+        reactor.track_history(count, 4);
+        assert_eq!(*reactor.read(count), 2);
+        reactor.send_signal(count, 2);
+        assert_eq!(*reactor.read(count), 3);
+
+        assert!(reactor.undo(count));
+        assert_eq!(*reactor.read(count), 2);
+        assert!(reactor.redo(count));
+        assert_eq!(*reactor.read(count), 3);
+
+        count.reset(&mut reactor);
+        assert_eq!(*reactor.read(count), 2);
+    }
+
+    #[test]
+    fn pulse() {
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let tick = reactor.new_pulse();
+        let ticks = reactor.new_fold(tick, 0, |ticks, _| ticks + 1);
+        for _ in 0..3 {
+            tick.send(&mut reactor);
+        }
+        assert_eq!(*reactor.read(ticks), 4); // once on creation, and once per pulse
+    }
+
+    #[test]
+    fn reentrant_pulse() {
+        use crate::{
+            memo::{Memo, RxMemo},
+            observable::{Observable, RxObservableData, RxSubscribers},
+            signal::Pulse,
+        };
+
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let trigger = reactor.new_signal(0);
+        let tick = reactor.new_pulse();
+        let ticks = reactor.new_fold(tick, 0, |ticks, _| ticks + 1);
+        let (trigger_entity, tick_entity) = (trigger.reactive_entity(), tick.reactive_entity());
+
+        // Sends the pulse twice while the graph is being traversed.
+        let _twice: Memo<i32> = Memo::from_calculation(&mut reactor, |entity| {
+            RxMemo::from_fn(move |world, stack| {
+                RxSubscribers::add(world, trigger_entity, entity);
+                Pulse::send_in(world, tick_entity);
+                Pulse::send_in(world, tick_entity);
+                RxObservableData::update_value(world, stack, entity, 0);
+            })
+        });
+        assert_eq!(*reactor.read(ticks), 3);
+
+        reactor.send_signal(trigger, 1);
+        assert_eq!(*reactor.read(ticks), 5);
+    }
+
+    #[test]
+    fn find_duplicate_memos() {
+        use crate::observable::Observable;
+
+        let mut reactor = crate::ReactiveContext::<()>::default();
+        let a = reactor.new_signal(1);
+        let b = reactor.new_signal(2);
+        let sum = |(a, b): (&i32, &i32)| a + b;
+        let sum_1 = reactor.new_memo((a, b), sum);
+        let sum_2 = reactor.new_memo((a, b), sum);
+        reactor.new_memo((b, a), sum);
+        reactor.new_memo((a, b), |(a, b)| a * b);
+        assert_eq!(
+            reactor.find_duplicate_memos(),
+            vec![vec![sum_1.reactive_entity(), sum_2.reactive_entity()]]
+        );
+
+        let offset = 10;
+        let offset_1 = reactor.new_memo((a,), move |(a,)| a + offset);
+        let offset_2 = reactor.new_memo((a,), move |(a,)| a + offset);
+        assert_eq!(reactor.find_duplicate_memos().len(), 1);
+        offset_1.set_identity_key(&mut reactor, "offset");
+        offset_2.set_identity_key(&mut reactor, "offset");
+        assert_eq!(reactor.find_duplicate_memos().len(), 2);
+    }
+
+    #[test]
+    fn panic_hook() {
+        use crate::{error::RxError, observable::Observable};
+        use std::sync::{Arc, Mutex};
+
+        let mut reactor = crate::ReactiveContext::<()>::default();
+        let a = reactor.new_signal(1);
+        let errors = Arc::new(Mutex::new(Vec::new()));
+        let hook_errors = errors.clone();
+        reactor.set_panic_hook(move |error| hook_errors.lock().unwrap().push(error));
+
+        let entity = a.reactive_entity();
+        crate::RxObservableData::update_value(
+            &mut reactor.reactive_state,
+            &mut Vec::new(),
+            entity,
+            "a",
+        );
+        assert!(matches!(
+            errors.lock().unwrap().as_slice(),
+            [RxError::TypeMismatch { .. }]
+        ));
+        assert_eq!(*reactor.read(a), 1);
+        reactor.send_signal(a, 2);
+        assert_eq!(*reactor.read(a), 2);
+
+        reactor.dispose(a);
+        let subscription = reactor.on_change(a, |_| {});
+        assert!(!subscription.is_active());
+        assert!(matches!(
+            errors.lock().unwrap().as_slice(),
+            [RxError::TypeMismatch { .. }, RxError::NodeDisposed(_)]
+        ));
+    }
+
+    #[test]
+    fn reactive_world() {
+        use crate::observable::Observable;
+        use bevy_ecs::prelude::*;
+
+        #[derive(Component)]
+        struct Label(&'static str);
+
+        let mut reactor = crate::ReactiveContext::<()>::default();
+        let a = reactor.new_signal(1);
+        let b = reactor.new_memo((a,), |(a,)| a + 1);
+        reactor
+            .reactive_world_mut()
+            .entity_mut(b.reactive_entity())
+            .insert(Label("b"));
+
+        reactor.send_signal(a, 2);
+        assert_eq!(*reactor.read(b), 3);
+        let world = reactor.reactive_world();
+        assert_eq!(world.get::<Label>(b.reactive_entity()).unwrap().0, "b");
+    }
+
+    #[test]
+    fn hashed() {
+        use crate::observable::Hashed;
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        };
+
+        let mut reactor = crate::ReactiveContext::<()>::default();
+        let tiles = reactor.new_signal_hashed(vec![0u8; 1024]);
+        let runs = Arc::new(AtomicUsize::new(0));
+        let counter = runs.clone();
+        let filled = reactor.new_memo_hashed((tiles,), move |(tiles,)| {
+            counter.fetch_add(1, Ordering::Relaxed);
+            tiles.iter().filter(|tile| **tile != 0).count()
+        });
+        let total = reactor.new_memo((filled,), |(filled,)| **filled * 2);
+        assert_eq!(runs.load(Ordering::Relaxed), 1);
+
+        reactor.send_signal(tiles, Hashed::new(vec![0u8; 1024]));
+        assert_eq!(runs.load(Ordering::Relaxed), 1);
+        let mut changed = vec![0u8; 1024];
+        changed[3] = 1;
+        reactor.send_signal(tiles, Hashed::new(changed));
+        assert_eq!(runs.load(Ordering::Relaxed), 2);
+        assert_eq!(*reactor.read(total), 2);
+    }
+
+    #[test]
+    fn generation() {
+        use bevy_ecs::prelude::*;
+
+        let mut world = World::new();
+        let mut reactor = crate::ReactiveContext::<World>::default();
+        let a = reactor.new_signal(1);
+        let b = reactor.new_signal(2);
+        let generation = reactor.generation();
+
+        reactor.send_signal(a, 1);
+        assert_eq!(reactor.generation(), generation + 1);
+        reactor.write_coalesced(a, 3);
+        reactor.write_coalesced(b, 4);
+        reactor.flush_effects(&mut world);
+        assert_eq!(reactor.generation(), generation + 2);
+    }
+
+    #[test]
+    fn stats() {
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let a = reactor.new_signal(1);
+        let b = reactor.new_signal(2);
+        reactor.new_memo((a, b), |(a, b)| a + b);
+        reactor.new_memo((a,), |(a,)| a * 2);
+
+        let stats = reactor.stats();
+        assert_eq!(stats.nodes, 4);
+        assert_eq!(stats.subscriber_edges, 3);
+        assert_eq!(stats.max_fan_out, 2);
+    }
+
+    #[test]
+    fn traversal_order() {
+        use crate::traversal::Traversal;
+        use std::sync::{Arc, Mutex};
+
+        let order = |mode| {
+            let mut reactor = crate::ReactiveContext::<()>::default();
+            reactor.set_traversal(mode);
+            let log = Arc::new(Mutex::new(Vec::new()));
+            let logged = |name: &'static str| {
+                let log = log.clone();
+                move |(n,): (&i32,)| {
+                    log.lock().unwrap().push(name);
+                    *n
+                }
+            };
+
+            let s = reactor.new_signal(0);
+            let a = reactor.new_memo((s,), logged("a"));
+            let b = reactor.new_memo((s,), logged("b"));
+            reactor.new_memo((a,), logged("c"));
+            reactor.new_memo((b,), logged("d"));
+
+            log.lock().unwrap().clear();
+            reactor.send_signal(s, 1);
+            let order = log.lock().unwrap().clone();
+            order
+        };
+
+        assert_eq!(order(Traversal::DepthFirst), ["b", "d", "a", "c"]);
+        assert_eq!(order(Traversal::BreadthFirst), ["a", "b", "c", "d"]);
+    }
+
+    #[test]
+    fn parallel_traversal_disposed_inputs() {
+        use crate::{dispose::DisposedInputs, error::RxError, traversal::Traversal};
+
+        let violations = [Traversal::BreadthFirst, Traversal::Parallel].map(|mode| {
+            let mut reactor = crate::ReactiveContext::<()>::default();
+            reactor.set_traversal(mode);
+            let a = reactor.new_signal(1);
+            let b = reactor.new_signal(2);
+            let sum = reactor.new_memo((a, b), |(a, b)| a + b);
+            let kept = reactor.new_memo((b, a), |(b, a)| a * b);
+            reactor.set_disposed_inputs(sum, DisposedInputs::Cascade);
+
+            reactor.dispose(b);
+            assert!(matches!(
+                reactor.try_read(sum),
+                Err(RxError::NodeDisposed(_))
+            ));
+            assert_eq!(*reactor.read(kept), 2);
+            reactor.send_signal(a, 5);
+            assert_eq!(*reactor.read(kept), 2);
+            assert_eq!(reactor.node_count(), 2, "{mode:?}");
+            reactor.debug_validate().len()
+        });
+        // `kept` still lists the disposed input in both modes.
+        assert_eq!(violations[0], violations[1]);
+    }
+
+    #[test]
+    fn parallel_traversal() {
+        use crate::traversal::Traversal;
+
+        let mut reactor = crate::ReactiveContext::<()>::default();
+        reactor.set_traversal(Traversal::Parallel);
+        let s = reactor.new_signal(1);
+        let scaled: Vec<_> = (0..16)
+            .map(|i| reactor.new_memo((s,), move |(s,)| s * i))
+            .collect();
+        let sum = reactor.new_memo((scaled[1], scaled[2], s), |(a, b, s)| a + b + s);
+        let lazy = reactor.new_lazy_memo((sum,), |(sum,)| sum * 10);
+        let outer = reactor.new_signal(scaled[3]);
+        let flat = reactor.flatten(outer);
+        assert_eq!(*reactor.read(sum), 4);
+
+        let recomputes = reactor.propagation_counters().recomputes;
+        reactor.send_signal(s, 2);
+        for (i, memo) in scaled.iter().enumerate() {
+            assert_eq!(*reactor.read(*memo), 2 * i as i32);
+        }
+        assert_eq!(*reactor.read(sum), 8);
+        assert_eq!(*reactor.read(flat), 6);
+        assert_eq!(*reactor.read(lazy), 80);
+        // `sum` is queued by `s` in the first wave, and by two of the memos in the second, where it
+        // is only recomputed once. `flat` is recomputed in the second wave, and `lazy` when read.
+        let recomputed = reactor.propagation_counters().recomputes - recomputes;
+        assert_eq!(recomputed, 16 + 2 + 1 + 1);
+
+        // Inputs stay subscribed when recomputed in parallel.
+        reactor.send_signal(s, 3);
+        assert_eq!(*reactor.read(sum), 12);
+        assert_eq!(*reactor.read(flat), 9);
+        assert!(reactor.debug_validate().is_empty());
+    }
+
+    #[test]
+    fn zip() {
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let name = reactor.new_signal("Jane");
+        let age = reactor.new_signal(45);
+        let person = reactor.zip(name, age);
+        let label = reactor.new_memo((person,), |((name, age),)| format!("{name} ({age})"));
+
+        reactor.send_signal(age, 46);
+        assert_eq!(reactor.read(person), &("Jane", 46));
+        assert_eq!(reactor.read(label), "Jane (46)");
+    }
+
+    #[test]
+    fn split() {
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let position = reactor.new_signal((1, 2));
+        let (x, y) = reactor.split(position);
+        let y_changes = reactor.new_fold(y, 0, |changes, _| changes + 1);
+
+        reactor.send_signal(position, (5, 2));
+        assert_eq!((*reactor.read(x), *reactor.read(y)), (5, 2));
+        assert_eq!(*reactor.read(y_changes), 1); // only the initial value
+
+        let rejoined = reactor.zip(x, y);
+        reactor.send_signal(position, (5, 3));
+        assert_eq!(*reactor.read(rejoined), (5, 3));
+        assert_eq!(*reactor.read(y_changes), 2);
+    }
+
+    #[test]
+    fn send_reports_change() {
+        let mut reactor = crate::ReactiveContext::<()>::default();
+        let signal = reactor.new_signal(1);
+
+        assert!(!reactor.send_signal(signal, 1));
+        assert!(reactor.send_signal(signal, 2));
+        assert_eq!(signal.try_send(&mut reactor, 2), Ok(false));
+    }
+
+    #[test]
+    fn unsubscribed_signal() {
+        use bevy_ecs::prelude::*;
+
+        let mut world = World::new();
+        let mut reactor = crate::ReactiveContext::<World>::default();
+        let age = reactor.new_signal(30);
+        assert!(!reactor.is_observed(age));
+
+        assert!(reactor.send_signal(age, 31));
+        assert_eq!(*reactor.read(age), 31);
+        let tick = reactor.change_tick();
+
+        assert!(!reactor.send_signal(age, 31)); // no-op
+        assert_eq!(*reactor.read(age), 31);
+        assert_eq!(reactor.change_tick(), tick);
+        assert_eq!(reactor.propagation_counters().cutoffs, 1);
+
+        // No memo, effect or callback machinery is touched.
+        assert_eq!(reactor.propagation_counters().recomputes, 0);
+        assert_eq!(reactor.pending_effect_count(), 0);
+        reactor.flush_effects(&mut world);
+        assert!(reactor.fired_effects().is_empty());
+        assert_eq!(reactor.node_count(), 1);
+        assert!(!reactor.is_observed(age));
+    }
+
+    #[test]
+    fn read_with_status() {
+        use crate::observable::Freshness;
+
+        let mut reactor = crate::ReactiveContext::<()>::default();
+        let n = reactor.new_signal(1);
+        let parity = reactor.new_memo((n,), |(n,)| n % 2);
+
+        assert_eq!(reactor.read_with_status(parity), (&1, Freshness::Fresh));
+        assert_eq!(reactor.read_with_status(parity), (&1, Freshness::Unchanged));
+        reactor.send_signal(n, 3); // recomputed, but the value is the same
+        assert_eq!(reactor.read_with_status(parity), (&1, Freshness::Unchanged));
+        reactor.send_signal(n, 4);
+        reactor.read(parity); // plain reads don't affect the status
+        assert_eq!(reactor.read_with_status(parity), (&0, Freshness::Fresh));
+        assert_eq!(reactor.read_with_status(n), (&4, Freshness::Fresh));
+    }
+
+    #[test]
+    fn transitive_closure() {
+        use crate::observable::Observable;
+        use bevy_utils::HashSet;
+
+        let mut reactor = crate::ReactiveContext::<()>::default();
+        let a = reactor.new_signal(1);
+        let b = reactor.new_memo((a,), |(a,)| a + 1);
+        let c = reactor.new_memo((a,), |(a,)| a * 2);
+        let d = reactor.new_memo((b, c), |(b, c)| b + c);
+        let unrelated = reactor.new_signal(0);
+        let (a_id, b_id, c_id, d_id) = (
+            a.reactive_entity(),
+            b.reactive_entity(),
+            c.reactive_entity(),
+            d.reactive_entity(),
+        );
+
+        let downstream = reactor.transitive_subscribers(a);
+        assert_eq!(downstream.len(), 3);
+        assert_eq!(downstream.last(), Some(&d_id)); // nearest first
+        assert_eq!(
+            HashSet::from_iter(downstream),
+            HashSet::from([b_id, c_id, d_id])
+        );
+        let upstream = reactor.transitive_dependencies(d);
+        assert_eq!(upstream.last(), Some(&a_id));
+        assert_eq!(
+            HashSet::from_iter(upstream),
+            HashSet::from([a_id, b_id, c_id])
+        );
+        assert!(reactor.transitive_subscribers(unrelated).is_empty());
+        assert!(reactor.transitive_dependencies(a).is_empty());
+
+        // Terminates even if the graph has a cycle.
+        reactor.subscribe(d, b_id);
+        let downstream = reactor.transitive_subscribers(a);
+        assert_eq!(
+            HashSet::from_iter(downstream),
+            HashSet::from([b_id, c_id, d_id])
+        );
+        let upstream = reactor.transitive_dependencies(b);
+        assert_eq!(
+            HashSet::from_iter(upstream),
+            HashSet::from([a_id, c_id, d_id])
+        );
+    }
+
+    #[test]
+    fn node_ids() {
+        use crate::{error::RxError, node_id::NodeId};
+
+        let mut reactor = crate::ReactiveContext::<()>::default();
+        let build = |reactor: &mut crate::ReactiveContext<()>| {
+            let volume = reactor.new_signal(5);
+            let muted = reactor.new_memo((volume,), |(volume,)| *volume == 0);
+            reactor.assign_id(volume, NodeId(1)).unwrap();
+            reactor.assign_id(muted, NodeId(2)).unwrap();
+            volume
+        };
+
+        let volume = build(&mut reactor);
+        assert_eq!(reactor.node_id(volume), Some(NodeId(1)));
+        assert_eq!(reactor.resolve_signal::<i32>(NodeId(1)), Ok(volume));
+        reactor.dispose(volume);
+        assert_eq!(
+            reactor.resolve_signal::<i32>(NodeId(1)),
+            Err(RxError::NodeDisposed(volume.reactor_entity))
+        );
+
+        reactor.clear();
+        assert_eq!(
+            reactor.resolve_signal::<i32>(NodeId(1)),
+            Err(RxError::UnknownId(NodeId(1)))
+        );
+        let rebuilt = build(&mut reactor);
+        let volume = reactor.resolve_signal::<i32>(NodeId(1)).unwrap();
+        assert_eq!(volume, rebuilt);
+        reactor.send_signal(volume, 0);
+        let muted = reactor.resolve_memo::<bool>(NodeId(2)).unwrap();
+        assert!(reactor.read(muted));
+        assert!(reactor.resolve_signal::<bool>(NodeId(2)).is_err());
+        assert!(reactor.resolve_signal::<f32>(NodeId(1)).is_err());
+    }
+
+    #[test]
+    fn manual_subscription() {
+        use crate::observable::Observable;
+
+        let mut reactor = crate::ReactiveContext::<()>::default();
+        let input = reactor.new_signal(1);
+        let refresh = reactor.new_pulse();
+        let doubled = reactor.new_memo((input,), |(n,)| n * 2);
+
+        assert!(reactor.subscribe(refresh, doubled.reactive_entity()));
+        reactor.reset_propagation_counters();
+        refresh.send(&mut reactor);
+        assert_eq!(reactor.propagation_counters().recomputes, 1);
+        refresh.send(&mut reactor); // the edge was consumed by the first recompute
+        assert_eq!(reactor.propagation_counters().recomputes, 1);
+
+        reactor.unsubscribe(input, doubled.reactive_entity());
+        reactor.send_signal(input, 2);
+        assert_eq!(*reactor.read(doubled), 2);
+    }
+
+    #[test]
+    fn persistent_subscription() {
+        use crate::observable::Observable;
+
+        let mut reactor = crate::ReactiveContext::<()>::default();
+        let input = reactor.new_signal(1);
+        let refresh = reactor.new_pulse();
+        let doubled = reactor.new_memo((input,), |(n,)| n * 2);
+
+        assert!(reactor.subscribe_persistent(refresh, doubled.reactive_entity()));
+        reactor.reset_propagation_counters();
+        refresh.send(&mut reactor);
+        refresh.send(&mut reactor);
+        assert_eq!(reactor.propagation_counters().recomputes, 2);
+
+        reactor.unsubscribe(refresh, doubled.reactive_entity());
+        refresh.send(&mut reactor);
+        assert_eq!(reactor.propagation_counters().recomputes, 2);
+        reactor.send_signal(input, 2); // the memo's own input is unaffected
+        assert_eq!(*reactor.read(doubled), 4);
+
+        reactor.subscribe_persistent(refresh, doubled.reactive_entity());
+        reactor.dispose(doubled);
+        assert!(!reactor.is_observed(refresh));
+    }
+
+    #[test]
+    fn memo_multi() {
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        };
+
+        let mut reactor = crate::ReactiveContext::<()>::default();
+        let runs = Arc::new(AtomicUsize::new(0));
+
+        let numbers = reactor.new_signal(vec![3, 1, 2]);
+        let (min, max) = reactor.new_memo_multi((numbers,), {
+            let runs = runs.clone();
+            move |(numbers,)| {
+                runs.fetch_add(1, Ordering::Relaxed);
+                let min = *numbers.iter().min().unwrap();
+                let max = *numbers.iter().max().unwrap();
+                (min, max)
+            }
+        });
+        let max_changes = reactor.new_fold(max, 0, |changes, _| changes + 1);
+        assert_eq!((*reactor.read(min), *reactor.read(max)), (1, 3));
+
+        reactor.send_signal(numbers, vec![0, 3]);
+        assert_eq!((*reactor.read(min), *reactor.read(max)), (0, 3));
+        assert_eq!(runs.load(Ordering::Relaxed), 2); // computed once per change, not per output
+        assert_eq!(*reactor.read(max_changes), 1); // only the initial value
+    }
+
+    #[test]
+    fn sample() {
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let cursor = reactor.new_signal((0, 0));
+        let click = reactor.new_pulse();
+        let clicked_at = reactor.sample(cursor, click);
+
+        reactor.send_signal(cursor, (3, 4));
+        assert_eq!(*reactor.read(clicked_at), (0, 0)); // the cursor alone does nothing
+
+        click.send(&mut reactor);
+        assert_eq!(*reactor.read(clicked_at), (3, 4));
+    }
+
+    #[test]
+    fn merge() {
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let keyboard = reactor.new_signal(0.0);
+        let gamepad = reactor.new_signal(0.5);
+        let throttle = reactor.merge(&[keyboard, gamepad]);
+        assert_eq!(*reactor.read(throttle), 0.5); // the gamepad was created last
+
+        reactor.send_signal(keyboard, 1.0);
+        assert_eq!(*reactor.read(throttle), 1.0);
+        reactor.send_signal(gamepad, 0.25);
+        assert_eq!(*reactor.read(throttle), 0.25);
+        reactor.send_signal(keyboard, 1.0); // unchanged, so the gamepad is still the latest
+        assert_eq!(*reactor.read(throttle), 0.25);
+    }
+
+    #[test]
+    fn all_switches() {
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let switches: Vec<_> = (0..100).map(|_| reactor.new_signal(true)).collect();
+        let last = *switches.last().unwrap();
+        reactor.send_signal(last, false);
+
+        let all = reactor.all(&switches);
+        let any = reactor.any(&switches);
+        let flips = reactor.new_fold(all, 0, |flips, _| flips + 1);
+        assert!(!reactor.read(all));
+        assert!(reactor.read(any));
+
+        reactor.send_signal(last, true);
+        assert!(reactor.read(all));
+        assert_eq!(*reactor.read(flips), 2); // once on creation, once when flipped
+    }
+
+    #[test]
+    fn reactive_list_callbacks() {
+        use std::sync::{Arc, Mutex};
+
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let views = Arc::new(Mutex::new(Vec::new()));
+        let mut list = crate::list::ReactiveList::new(&mut reactor);
+        let log = views.clone();
+        let _inserts = list.on_insert(move |k, v| log.lock().unwrap().push(format!("+{k}={v}")));
+        let log = views.clone();
+        let _updates = list.on_update(move |k, v| log.lock().unwrap().push(format!("~{k}={v}")));
+        let log = views.clone();
+        let _removes = list.on_remove(move |k| log.lock().unwrap().push(format!("-{k}")));
+
+        list.insert(&mut reactor, "a", 1);
+        list.insert(&mut reactor, "a", 2);
+        list.update(&mut reactor, "a", 2);
+        list.remove(&mut reactor, &"a");
+        assert_eq!(*views.lock().unwrap(), ["+a=1", "~a=2", "-a"]);
+    }
+
+    #[test]
+    fn index_source() {
+        let mut reactor = crate::ReactiveContext::<()>::default();
+        let level = reactor.new_signal(1);
+        let ids = reactor.new_index_source(level);
+        assert_eq!(*reactor.read(ids.current()), 0);
+
+        let mut list = crate::list::ReactiveList::new(&mut reactor);
+        for name in ["slime", "bat"] {
+            let id = ids.next(&mut reactor);
+            list.insert(&mut reactor, id, name);
+        }
+        assert_eq!(list.keys().collect::<Vec<_>>(), vec![&0, &1]);
+        assert_eq!(*reactor.read(ids.current()), 2);
+
+        reactor.send_signal(level, 2);
+        assert_eq!(*reactor.read(ids.current()), 0);
+        assert_eq!(ids.next(&mut reactor), 0);
+        assert_eq!(ids.next(&mut reactor), 1);
+    }
+
+    #[test]
+    fn reactive_list() {
+        let mut reactor = crate::ReactiveContext::<()>::default();
+
+        let mut list = crate::list::ReactiveList::new(&mut reactor);
+        list.insert(&mut reactor, "a", 1);
+        list.insert(&mut reactor, "b", 2);
+        let a_view = list.derive(&mut reactor, &"a", |a| a * 10).unwrap();
+        let a_renders = reactor.new_fold(a_view, 0, |renders, _| renders + 1);
+
+        list.update(&mut reactor, "b", 3);
+        list.update(&mut reactor, "b", 3);
+        assert_eq!(*reactor.read(a_renders), 1); // "a" is not recomputed when "b" changes
+        assert_eq!(reactor.read(list.changes()).updated, vec!["b"]);
+
+        list.update(&mut reactor, "a", 2);
+        assert_eq!(*reactor.read(a_view), 20);
+
+        list.remove(&mut reactor, &"a");
+        assert_eq!(reactor.read(list.changes()).removed, vec!["a"]);
+        assert_eq!(list.keys().collect::<Vec<_>>(), vec![&"b"]);
+    }
+
+    #[test]
+    #[cfg(feature = "bevy_app")]
+    fn track_component() {
+        use crate::ReactiveAppExt;
+        use bevy_ecs::prelude::*;
+
+        #[derive(Component)]
+        struct Health(i32, f32);
+
+        let mut app = bevy_app::App::new();
+        app.add_plugins(crate::ReactiveExtensionsPlugin)
+            .track_components::<Health>();
+        let player = app.world.spawn(Health(100, 0.0)).id();
+        let mut reactor = app.world.resource_mut::<crate::ReactiveContext<World>>();
+        let health = reactor.track_component(player, |health: &Health| health.0);
+        let changes = reactor.new_fold(health, 0, |changes, _| changes + 1);
+        assert_eq!(*reactor.read(health), None);
+
+        app.update();
+        app.world.get_mut::<Health>(player).unwrap().1 = 1.0; // not part of the extracted value
+        app.update();
+        app.world.get_mut::<Health>(player).unwrap().0 = 90;
+        app.update();
+
+        let mut reactor = app.world.resource_mut::<crate::ReactiveContext<World>>();
+        assert_eq!(*reactor.read(health), Some(90));
+        assert_eq!(*reactor.read(changes), 3);
+    }
+
+    #[test]
+    #[cfg(feature = "bevy_app")]
+    fn reactor_scope() {
+        use crate::{observable::Freshness, Reactor};
+        use bevy_ecs::prelude::*;
+
+        #[derive(Component)]
+        struct Banner;
+
+        let mut app = bevy_app::App::new();
+        app.add_plugins(crate::ReactiveExtensionsPlugin);
+        let mut reactor = app.world.resource_mut::<crate::ReactiveContext<World>>();
+        let level = reactor.new_signal(1);
+        app.add_systems(bevy_app::Update, move |mut reactor: Reactor| {
+            reactor.scope(|rctx, commands| {
+                if rctx.read_with_status(level).1 == Freshness::Fresh {
+                    commands.spawn(Banner);
+                }
+            });
+        });
+
+        app.update();
+        app.update();
+        let mut reactor = app.world.resource_mut::<crate::ReactiveContext<World>>();
+        reactor.send_signal(level, 2);
+        app.update();
+        let banners = app.world.query::<&Banner>().iter(&app.world).count();
+        assert_eq!(banners, 2);
+    }
+
+    #[test]
+    #[cfg(feature = "bevy_app")]
+    fn time_signals() {
+        use bevy_ecs::prelude::*;
+
+        let mut app = bevy_app::App::new();
+        app.add_plugins(crate::ReactiveExtensionsPlugin)
+            .init_resource::<bevy_time::Time>();
+        let mut reactor = app.world.resource_mut::<crate::ReactiveContext<World>>();
+        let frame = reactor.frame_signal();
+        let even = reactor.new_memo((frame,), |(frame,)| frame % 2 == 0);
+        let elapsed = reactor.time_signal();
+
+        app.update();
+        app.update();
+        let mut reactor = app.world.resource_mut::<crate::ReactiveContext<World>>();
+        assert_eq!(*reactor.read(frame), 2);
+        assert!(reactor.read(even));
+        assert_eq!(*reactor.read(elapsed), std::time::Duration::ZERO); // the clock never advanced
+    }
+
+    #[test]
+    #[cfg(feature = "reflect")]
+    fn inspect() {
+        use crate::{inspect::Inspected, ReactiveAppExt};
+        use bevy_ecs::prelude::*;
+
+        let mut app = bevy_app::App::new();
+        app.add_plugins(crate::ReactiveExtensionsPlugin)
+            .inspect::<i32>();
+        let mut reactor = app.world.resource_mut::<crate::ReactiveContext<World>>();
+        let health = reactor.new_signal(100);
+        let mirror = app.world.spawn(Inspected::new(health)).id();
+
+        app.update();
+        let mut reactor = app.world.resource_mut::<crate::ReactiveContext<World>>();
+        reactor.send_signal(health, 50);
+        app.update();
+        let inspected = app.world.get::<Inspected<i32>>(mirror).unwrap();
+        assert_eq!(inspected.value(), Some(&50));
+
+        let registry = app.world.resource::<bevy_ecs::reflect::AppTypeRegistry>();
+        assert!(registry
+            .read()
+            .get(std::any::TypeId::of::<Inspected<i32>>())
+            .is_some());
+    }
+
+    #[test]
+    #[cfg(feature = "bevy_app")]
+    fn event_signal() {
+        use crate::ReactiveAppExt;
+        use bevy_ecs::prelude::*;
+
+        #[derive(Event, Debug, Clone, PartialEq)]
+        struct KeyPress(char);
+
+        let mut app = bevy_app::App::new();
+        app.add_plugins(crate::ReactiveExtensionsPlugin)
+            .add_event_signal::<KeyPress>();
+        let mut reactor = app.world.resource_mut::<crate::ReactiveContext<World>>();
+        let key = reactor.signal_from_events::<KeyPress>();
+        let shout = reactor.new_memo((key,), |(key,)| {
+            key.as_ref().map(|k| k.0.to_ascii_uppercase())
+        });
+
+        app.world.send_event(KeyPress('a'));
+        app.world.send_event(KeyPress('b'));
+        app.update();
+        let mut reactor = app.world.resource_mut::<crate::ReactiveContext<World>>();
+        assert_eq!(*reactor.read(shout), Some('B'));
     }
 
     #[test]
@@ -0,0 +1,154 @@
+//! Callbacks run when an observable gains its first subscriber, or loses its last, so sources can
+//! do work only while something observes them. See [`ReactiveContext::on_connect`].
+
+use bevy_ecs::prelude::*;
+use bevy_utils::HashMap;
+
+use crate::{observable::RxSubscribers, traversal::RxTraversal, ReactiveContext};
+
+/// A callback run when an observable is connected or disconnected.
+type LifecycleFn = dyn FnMut() + Send + Sync;
+
+/// The lifecycle callbacks of a single observable.
+#[derive(Default)]
+struct Lifecycle {
+    /// Set while the observable has subscribers, as of the last time it was checked.
+    connected: bool,
+    on_connect: Option<Box<LifecycleFn>>,
+    on_disconnect: Option<Box<LifecycleFn>>,
+}
+
+/// The lifecycle callbacks of every observable that has any.
+///
+/// Subscribers are removed every time an observable changes, and subscribe again as they are
+/// recomputed, so the subscriber list is only checked for disconnects once the graph has settled.
+#[derive(Resource, Default)]
+pub(crate) struct RxLifecycle {
+    nodes: HashMap<Entity, Lifecycle>,
+}
+
+impl RxLifecycle {
+    /// Run `callback` every time the `observable` gains its first subscriber, and immediately if
+    /// it already has subscribers.
+    pub(crate) fn on_connect(
+        rx_world: &mut World,
+        observable: Entity,
+        callback: impl FnMut() + Send + Sync + 'static,
+    ) {
+        let mut callback = Box::new(callback);
+        let lifecycle = Self::get_or_insert(rx_world, observable);
+        if lifecycle.connected {
+            callback();
+        }
+        lifecycle.on_connect = Some(callback);
+    }
+
+    /// Run `callback` every time the `observable` loses its last subscriber, or is disposed while
+    /// it has subscribers.
+    pub(crate) fn on_disconnect(
+        rx_world: &mut World,
+        observable: Entity,
+        callback: impl FnMut() + Send + Sync + 'static,
+    ) {
+        Self::get_or_insert(rx_world, observable).on_disconnect = Some(Box::new(callback));
+    }
+
+    fn get_or_insert(rx_world: &mut World, observable: Entity) -> &mut Lifecycle {
+        let connected = is_connected(rx_world, observable);
+        if let Some(mut subscribers) = rx_world.get_mut::<RxSubscribers>(observable) {
+            subscribers.lifecycle = true;
+        }
+        rx_world.resource_mut::<RxTraversal>().lifecycles = true;
+        rx_world
+            .resource_mut::<Self>()
+            .into_inner()
+            .nodes
+            .entry(observable)
+            .or_insert_with(|| Lifecycle {
+                connected,
+                ..Default::default()
+            })
+    }
+
+    /// Called after a subscriber was added to an `observable` with lifecycle callbacks, to
+    /// connect it if it had none.
+    pub(crate) fn subscribed(rx_world: &mut World, observable: Entity) {
+        let mut lifecycles = rx_world.resource_mut::<Self>();
+        let Some(lifecycle) = lifecycles.nodes.get_mut(&observable) else {
+            return;
+        };
+        if !lifecycle.connected {
+            lifecycle.connected = true;
+            if let Some(on_connect) = lifecycle.on_connect.as_mut() {
+                on_connect();
+            }
+        }
+    }
+
+    /// Disconnect the observables that have no subscribers left, once the graph has settled.
+    /// Observables that were disposed are disconnected, and their callbacks dropped.
+    pub(crate) fn settle(rx_world: &mut World) {
+        if rx_world.resource::<Self>().nodes.is_empty() {
+            return;
+        }
+        rx_world.resource_scope(|rx_world, mut lifecycles: Mut<Self>| {
+            lifecycles.nodes.retain(|&observable, lifecycle| {
+                if lifecycle.connected && !is_connected(rx_world, observable) {
+                    lifecycle.connected = false;
+                    if let Some(on_disconnect) = lifecycle.on_disconnect.as_mut() {
+                        on_disconnect();
+                    }
+                }
+                rx_world.get_entity(observable).is_some()
+            });
+        });
+    }
+}
+
+/// Returns `true` if the `observable` has a subscriber. Disposed subscribers are not removed from
+/// the subscriber lists until the next time the observable changes, so they are skipped.
+fn is_connected(rx_world: &World, observable: Entity) -> bool {
+    rx_world
+        .get::<RxSubscribers>(observable)
+        .is_some_and(|subscribers| {
+            subscribers
+                .subscribers
+                .iter()
+                .any(|subscriber| rx_world.get_entity(*subscriber).is_some())
+        })
+}
+
+impl<S> ReactiveContext<S> {
+    /// Run `callback` every time the `observable` gains its first subscriber, e.g. to start polling
+    /// a sensor only while something depends on it. If the observable already has subscribers,
+    /// `callback` is also run immediately. Replaces any previous callback.
+    ///
+    /// Subscribers are memos and manual subscriptions, not callbacks or effects.
+    pub fn on_connect(
+        &mut self,
+        observable: impl crate::observable::AnyObservable,
+        callback: impl FnMut() + Send + Sync + 'static,
+    ) {
+        RxLifecycle::on_connect(
+            &mut self.reactive_state,
+            observable.reactive_entity(),
+            callback,
+        );
+    }
+
+    /// Run `callback` every time the `observable` loses its last subscriber, or is disposed while
+    /// it has subscribers, e.g. to stop the work started by [`ReactiveContext::on_connect`]. This
+    /// is checked once the change that removed the subscriber has finished propagating. Replaces
+    /// any previous callback.
+    pub fn on_disconnect(
+        &mut self,
+        observable: impl crate::observable::AnyObservable,
+        callback: impl FnMut() + Send + Sync + 'static,
+    ) {
+        RxLifecycle::on_disconnect(
+            &mut self.reactive_state,
+            observable.reactive_entity(),
+            callback,
+        );
+    }
+}
@@ -0,0 +1,232 @@
+use std::hash::Hash;
+
+use bevy_utils::HashMap;
+
+use crate::{
+    callback::{Listeners, Subscription},
+    memo::Memo,
+    observable::Observable,
+    signal::Signal,
+    ReactiveContext,
+};
+
+/// A keyed collection of reactive values, where every item is stored in its own [`Signal`].
+///
+/// Memos derived from a single item with [`ReactiveList::derive`] only recompute when that item
+/// changes, not when other items are inserted, updated, or removed. Changes to the list as a whole
+/// are described by the [`ReactiveList::changes`] memo, which can be used to create, destroy, or
+/// patch the views of individual items. To handle each change as it happens, register callbacks
+/// with [`ReactiveList::on_insert`], [`ReactiveList::on_update`], and [`ReactiveList::on_remove`].
+pub struct ReactiveList<K: Send + Sync + 'static, T: Send + Sync + 'static> {
+    items: HashMap<K, Signal<T>>,
+    order: Vec<K>,
+    changes_signal: Signal<ListChanges<K>>,
+    changes: Memo<ListChanges<K>>,
+    on_insert: Listeners<ItemFn<K, T>>,
+    on_update: Listeners<ItemFn<K, T>>,
+    on_remove: Listeners<KeyFn<K>>,
+}
+
+/// A callback invoked with the key and value of an item in a [`ReactiveList`].
+type ItemFn<K, T> = dyn FnMut(&K, &T) + Send + Sync;
+
+/// A callback invoked with the key of an item in a [`ReactiveList`].
+type KeyFn<K> = dyn FnMut(&K) + Send + Sync;
+
+/// The keys affected by the most recent change to a [`ReactiveList`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListChanges<K> {
+    /// Incremented with every change, so that consecutive identical changes are still propagated.
+    pub generation: u64,
+    pub inserted: Vec<K>,
+    pub updated: Vec<K>,
+    pub removed: Vec<K>,
+}
+
+impl<K> Default for ListChanges<K> {
+    fn default() -> Self {
+        Self {
+            generation: 0,
+            inserted: Vec::new(),
+            updated: Vec::new(),
+            removed: Vec::new(),
+        }
+    }
+}
+
+impl<K, T> ReactiveList<K, T>
+where
+    K: Clone + Eq + Hash + Send + Sync + 'static,
+    T: Clone + PartialEq + Send + Sync + 'static,
+{
+    pub fn new<S>(rctx: &mut ReactiveContext<S>) -> Self {
+        let changes_signal = rctx.new_signal(ListChanges::default());
+        let changes = rctx.new_memo((changes_signal,), |(changes,)| changes.clone());
+        Self {
+            items: HashMap::default(),
+            order: Vec::new(),
+            changes_signal,
+            changes,
+            on_insert: Listeners::default(),
+            on_update: Listeners::default(),
+            on_remove: Listeners::default(),
+        }
+    }
+
+    /// Insert a new item, or update the value of an existing item.
+    pub fn insert<S>(&mut self, rctx: &mut ReactiveContext<S>, key: K, value: T) {
+        if self.items.contains_key(&key) {
+            self.update(rctx, key, value);
+            return;
+        }
+        let signal = rctx.new_signal(value);
+        self.items.insert(key.clone(), signal);
+        self.order.push(key.clone());
+        self.emit(rctx, |changes| changes.inserted.push(key.clone()));
+        let value = rctx.read(signal);
+        self.on_insert.notify(|callback| callback(&key, value));
+    }
+
+    /// Update the value of an existing item, returning `false` if the key is not in the list. Only
+    /// subscribers of this item are recomputed, and only if the value changed.
+    pub fn update<S>(&mut self, rctx: &mut ReactiveContext<S>, key: K, value: T) -> bool {
+        let Some(signal) = self.items.get(&key).copied() else {
+            return false;
+        };
+        if *rctx.read(signal) != value {
+            rctx.send_signal(signal, value);
+            self.emit(rctx, |changes| changes.updated.push(key.clone()));
+            let value = rctx.read(signal);
+            self.on_update.notify(|callback| callback(&key, value));
+        }
+        true
+    }
+
+    /// Remove an item, returning `false` if the key is not in the list. The item's signal is
+    /// disposed, and memos derived from it keep their last value.
+    pub fn remove<S>(&mut self, rctx: &mut ReactiveContext<S>, key: &K) -> bool {
+        let Some(signal) = self.items.remove(key) else {
+            return false;
+        };
+        self.order.retain(|k| k != key);
+        rctx.reactive_state.despawn(signal.reactive_entity());
+        self.emit(rctx, |changes| changes.removed.push(key.clone()));
+        self.on_remove.notify(|callback| callback(key));
+        true
+    }
+
+    /// Invoke `callback` with the key and value of every item inserted from now on, e.g. to create
+    /// a view for it. The callback runs synchronously, after the item's signal has been created,
+    /// and is unsubscribed when the returned [`Subscription`] is dropped.
+    pub fn on_insert(
+        &mut self,
+        callback: impl FnMut(&K, &T) + Send + Sync + 'static,
+    ) -> Subscription {
+        self.on_insert.add(Box::new(callback))
+    }
+
+    /// Invoke `callback` with the key and new value of every item whose value changes, e.g. to
+    /// patch its view. See [`ReactiveList::on_insert`].
+    pub fn on_update(
+        &mut self,
+        callback: impl FnMut(&K, &T) + Send + Sync + 'static,
+    ) -> Subscription {
+        self.on_update.add(Box::new(callback))
+    }
+
+    /// Invoke `callback` with the key of every item removed from now on, e.g. to destroy its view.
+    /// The callback runs after the item's signal has been disposed. See
+    /// [`ReactiveList::on_insert`].
+    pub fn on_remove(&mut self, callback: impl FnMut(&K) + Send + Sync + 'static) -> Subscription {
+        self.on_remove.add(Box::new(callback))
+    }
+
+    /// The signal holding the value of an item.
+    pub fn get(&self, key: &K) -> Option<Signal<T>> {
+        self.items.get(key).copied()
+    }
+
+    /// Create a memo from the value of a single item, which only recomputes when that item
+    /// changes. Returns `None` if the key is not in the list.
+    pub fn derive<S, U: Clone + PartialEq + Send + Sync + 'static>(
+        &self,
+        rctx: &mut ReactiveContext<S>,
+        key: &K,
+        derive_fn: impl Fn(&T) -> U + Send + Sync + Clone + 'static,
+    ) -> Option<Memo<U>> {
+        let signal = self.get(key)?;
+        Some(rctx.new_memo((signal,), move |(value,)| derive_fn(value)))
+    }
+
+    /// A memo describing the keys affected by the most recent change to the list.
+    pub fn changes(&self) -> Memo<ListChanges<K>> {
+        self.changes
+    }
+
+    /// The keys in the list, in insertion order.
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.order.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+
+    fn emit<S>(&self, rctx: &mut ReactiveContext<S>, change: impl FnOnce(&mut ListChanges<K>)) {
+        let mut changes = ListChanges {
+            generation: rctx.read(self.changes_signal).generation + 1,
+            ..Default::default()
+        };
+        change(&mut changes);
+        rctx.send_signal(self.changes_signal, changes);
+    }
+}
+
+/// Generates increasing ids, e.g. to use as the keys of a [`ReactiveList`]. Ids start at `0`, and
+/// start over every time the reset observable changes. See [`ReactiveContext::new_index_source`].
+#[derive(Debug, Clone, Copy)]
+pub struct IndexSource {
+    /// The number of times the reset observable changed.
+    resets: Memo<u64>,
+    /// The next id, and the number of resets it was issued after.
+    next: Signal<(u64, u64)>,
+    current: Memo<u64>,
+}
+
+impl IndexSource {
+    pub fn new<S>(rctx: &mut ReactiveContext<S>, reset: impl Observable) -> Self {
+        let resets = rctx.new_fold(reset, 0, |resets, _| resets + 1);
+        let initial_resets = *rctx.read(resets);
+        let next = rctx.new_signal((initial_resets, 0));
+        let current = rctx.new_memo((resets, next), |(resets, (issued_after, next))| {
+            if resets == issued_after {
+                *next
+            } else {
+                0
+            }
+        });
+        Self {
+            resets,
+            next,
+            current,
+        }
+    }
+
+    /// Issue the next id.
+    pub fn next<S>(&self, rctx: &mut ReactiveContext<S>) -> u64 {
+        let resets = *rctx.read(self.resets);
+        let id = *rctx.read(self.current);
+        rctx.send_signal(self.next, (resets, id + 1));
+        id
+    }
+
+    /// A memo holding the id that will be issued next, which is also the number of ids issued
+    /// since the last reset.
+    pub fn current(&self) -> Memo<u64> {
+        self.current
+    }
+}
@@ -0,0 +1,257 @@
+//! Reactive values that are not `Send` or `Sync`, like `Rc` or `RefCell` based UI state, for
+//! contexts that are only used from a single thread. Only available with the `local` feature.
+//!
+//! The graph lives in a bevy [`World`], whose components must be `Send` and `Sync`, so local
+//! values are stored in a non-send resource of the reactive world instead. Each local value is
+//! still a node of the graph, holding a version that is incremented every time the value is
+//! replaced, so local values are subscribed to, propagated, and disposed like any other node. Local
+//! values are not diffed: every send and recompute propagates.
+//!
+//! Bevy only allows non-send resources to be accessed from the thread that inserted them, so once
+//! a local value has been created, reading, recomputing, or disposing nodes from another thread
+//! panics. A context used as a resource in a multithreaded bevy app should not hold local values.
+
+use std::{
+    any::{type_name, Any, TypeId},
+    marker::PhantomData,
+};
+
+use bevy_ecs::prelude::*;
+use bevy_utils::HashMap;
+
+use crate::{
+    error::RxError,
+    memo::{Memo, RxMemo},
+    observable::{self, Observable, RxObservableData, RxSubscribers},
+    signal::Signal,
+    ReactiveContext,
+};
+
+/// A handle to a local value, which can be read with [`ReactiveContext::read_local`], and used as
+/// the input of [`ReactiveContext::new_local_memo`]. Local handles also implement
+/// [`observable::AnyObservable`], so they can be disposed with [`ReactiveContext::dispose`].
+pub trait LocalObservable: Copy + 'static {
+    type DataType: 'static;
+    fn reactive_entity(&self) -> Entity;
+}
+
+/// A signal holding a value that is not `Send` or `Sync`, see
+/// [`ReactiveContext::new_local_signal`]. The handle itself is `Send` and `Sync`, so it can be
+/// stored anywhere.
+#[derive(Debug)]
+pub struct LocalSignal<T: 'static> {
+    version: Signal<u64>,
+    p: PhantomData<fn() -> T>,
+}
+
+/// A memo holding a value that is not `Send` or `Sync`, see [`ReactiveContext::new_local_memo`].
+#[derive(Debug)]
+pub struct LocalMemo<T: 'static> {
+    version: Memo<u64>,
+    p: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for LocalSignal<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for LocalSignal<T> {}
+
+impl<T> Clone for LocalMemo<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for LocalMemo<T> {}
+
+impl<T: 'static> LocalObservable for LocalSignal<T> {
+    type DataType = T;
+    fn reactive_entity(&self) -> Entity {
+        self.version.reactive_entity()
+    }
+}
+
+impl<T: 'static> LocalObservable for LocalMemo<T> {
+    type DataType = T;
+    fn reactive_entity(&self) -> Entity {
+        self.version.reactive_entity()
+    }
+}
+
+impl<T: 'static> observable::AnyObservable for LocalSignal<T> {
+    fn reactive_entity(&self) -> Entity {
+        LocalObservable::reactive_entity(self)
+    }
+
+    fn data_type_id(&self) -> TypeId {
+        TypeId::of::<T>()
+    }
+
+    fn data_type_name(&self) -> &'static str {
+        type_name::<T>()
+    }
+}
+
+impl<T: 'static> observable::AnyObservable for LocalMemo<T> {
+    fn reactive_entity(&self) -> Entity {
+        LocalObservable::reactive_entity(self)
+    }
+
+    fn data_type_id(&self) -> TypeId {
+        TypeId::of::<T>()
+    }
+
+    fn data_type_name(&self) -> &'static str {
+        type_name::<T>()
+    }
+}
+
+/// The values of every local node, keyed by the node's entity.
+#[derive(Default)]
+pub(crate) struct RxLocalValues(HashMap<Entity, Box<dyn Any>>);
+
+impl RxLocalValues {
+    /// Drop the values of the disposed `nodes`.
+    pub(crate) fn remove(rx_world: &mut World, nodes: &[Entity]) {
+        if !rx_world.contains_non_send::<Self>() {
+            return;
+        }
+        let mut values = rx_world.non_send_resource_mut::<Self>();
+        for node in nodes {
+            values.0.remove(node);
+        }
+    }
+
+    /// Drop the values of every local node, see [`ReactiveContext::clear`].
+    pub(crate) fn clear(rx_world: &mut World) {
+        rx_world.remove_non_send_resource::<Self>();
+    }
+
+    fn read<T: 'static>(rx_world: &World, node: Entity) -> Result<&T, RxError> {
+        if rx_world.get_entity(node).is_none() {
+            return Err(RxError::NodeDisposed(node));
+        }
+        let value = rx_world
+            .get_non_send_resource::<Self>()
+            .and_then(|values| values.0.get(&node))
+            .ok_or(RxError::NeverComputed(node))?;
+        value
+            .downcast_ref::<T>()
+            .ok_or_else(|| RxError::TypeMismatch {
+                entity: node,
+                expected: std::any::type_name::<T>(),
+                found: "a local value of another type",
+            })
+    }
+
+    fn store<T: 'static>(rx_world: &mut World, node: Entity, value: T) {
+        rx_world.init_non_send_resource::<Self>();
+        rx_world
+            .non_send_resource_mut::<Self>()
+            .0
+            .insert(node, Box::new(value));
+    }
+}
+
+/// The version of a local node after its value is replaced.
+fn next_version(rx_world: &World, node: Entity) -> u64 {
+    RxObservableData::<u64>::read(rx_world, node).map_or(0, |version| version + 1)
+}
+
+impl<S> ReactiveContext<S> {
+    /// Create a signal holding a value that is not `Send` or `Sync`. See the [`crate::local`]
+    /// module for the restrictions on local values.
+    pub fn new_local_signal<T: 'static>(&mut self, initial_value: T) -> LocalSignal<T> {
+        let version = self.new_signal(0);
+        RxLocalValues::store(
+            &mut self.reactive_state,
+            version.reactive_entity(),
+            initial_value,
+        );
+        LocalSignal {
+            version,
+            p: PhantomData,
+        }
+    }
+
+    /// Replace the value of a local signal, and recompute its subscribers. Local values are not
+    /// diffed, so this always propagates.
+    pub fn send_local<T: 'static>(&mut self, signal: LocalSignal<T>, value: T) {
+        let node = signal.reactive_entity();
+        if self.reactive_state.get_entity(node).is_none() {
+            return;
+        }
+        RxLocalValues::store(&mut self.reactive_state, node, value);
+        let version = next_version(&self.reactive_state, node);
+        self.send_signal(signal.version, version);
+    }
+
+    /// Read the value of a local signal or memo.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node has been disposed or has no value, see
+    /// [`ReactiveContext::try_read_local`].
+    pub fn read_local<O: LocalObservable>(&self, local: O) -> &O::DataType {
+        self.try_read_local(local)
+            .unwrap_or_else(|error| panic!("Failed to read local value: {error}"))
+    }
+
+    /// Read the value of a local signal or memo, or return an error if it has been disposed, or
+    /// has no value.
+    pub fn try_read_local<O: LocalObservable>(&self, local: O) -> Result<&O::DataType, RxError> {
+        RxLocalValues::read(&self.reactive_state, local.reactive_entity())
+    }
+
+    /// Create a memo computing a local value from the value of a local `input`. The memo is
+    /// recomputed every time the input changes.
+    pub fn new_local_memo<O: LocalObservable, U: 'static>(
+        &mut self,
+        input: O,
+        derive_fn: impl Fn(&O::DataType) -> U + Send + Sync + 'static,
+    ) -> LocalMemo<U> {
+        let input = input.reactive_entity();
+        let version = Memo::from_calculation(self, |entity| {
+            RxMemo::from_fn(move |world: &mut World, stack: &mut Vec<Entity>| {
+                RxSubscribers::add(world, input, entity);
+                let Ok(value) = RxLocalValues::read::<O::DataType>(world, input) else {
+                    return;
+                };
+                let output = derive_fn(value);
+                RxLocalValues::store(world, entity, output);
+                let version = next_version(world, entity);
+                RxObservableData::update_value(world, stack, entity, version);
+            })
+        });
+        LocalMemo {
+            version,
+            p: PhantomData,
+        }
+    }
+
+    /// Create a regular memo from the value of a local `input`, e.g. to extract the parts of a
+    /// local value that the rest of the graph depends on. The memo is diffed like any other.
+    pub fn new_memo_from_local<O: LocalObservable, U>(
+        &mut self,
+        input: O,
+        derive_fn: impl Fn(&O::DataType) -> U + Send + Sync + 'static,
+    ) -> Memo<U>
+    where
+        U: Clone + PartialEq + Send + Sync + 'static,
+    {
+        let input = input.reactive_entity();
+        Memo::from_calculation(self, |entity| {
+            RxMemo::from_fn(move |world: &mut World, stack: &mut Vec<Entity>| {
+                RxSubscribers::add(world, input, entity);
+                let Ok(value) = RxLocalValues::read::<O::DataType>(world, input) else {
+                    return;
+                };
+                let output = derive_fn(value);
+                RxObservableData::update_value(world, stack, entity, output);
+            })
+        })
+    }
+}
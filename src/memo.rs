@@ -1,9 +1,26 @@
-use std::marker::PhantomData;
+use std::{
+    any::TypeId,
+    borrow::Cow,
+    collections::VecDeque,
+    marker::PhantomData,
+    sync::{mpsc::Sender, Arc},
+};
 
 use bevy_ecs::prelude::*;
-use bevy_utils::all_tuples_with_size;
+use bevy_utils::{all_tuples_with_size, tracing::warn, HashSet};
 
-use crate::{Observable, ReactiveContext, RxObservableData};
+use crate::{
+    callback::RxCallbacks,
+    dispose,
+    effect::Effect,
+    error::RxError,
+    history::RxHistory,
+    observable::{ObservableTuple, RxObservableData, RxSubscribers},
+    signal::{RxSignalReset, RxWriteMap, Signal},
+    stats,
+    traversal::{self, PendingWrite, RxTraversal},
+    Observable, ReactiveContext,
+};
 
 /// A reactive value that is automatically recalculated and memoized (cached).
 ///
@@ -29,14 +46,95 @@ impl<T: Send + Sync> Clone for Memo<T> {
 
 impl<T: Send + Sync> Copy for Memo<T> {}
 
+impl<T: Send + Sync> PartialEq for Memo<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.reactor_entity == other.reactor_entity
+    }
+}
+
+impl<T: Send + Sync> Eq for Memo<T> {}
+
 impl<T: Clone + PartialEq + Send + Sync> Memo<T> {
+    /// Create a memo, computing its initial value immediately.
+    ///
+    /// If the calculation can't produce a value, because one of its inputs has no value, a warning
+    /// is logged, and reading the memo will fail until it is recomputed. Use [`Memo::try_new`] to
+    /// handle this case.
     pub fn new<S, D: MemoQuery<T>>(
         rctx: &mut ReactiveContext<S>,
         input_deps: D,
-        derive_fn: (impl Fn(D::Query<'_>) -> T + Send + Sync + Clone + 'static),
+        derive_fn: impl Fn(D::Query<'_>) -> T + Send + Sync + Clone + 'static,
+    ) -> Self {
+        let memo = Self::spawn(rctx, input_deps, derive_fn);
+        memo.check_initial_value(rctx);
+        memo
+    }
+
+    /// Create a memo, computing its initial value immediately. Returns
+    /// [`RxError::NeverComputed`] and disposes of the memo if no value could be computed.
+    pub fn try_new<S, D: MemoQuery<T>>(
+        rctx: &mut ReactiveContext<S>,
+        input_deps: D,
+        derive_fn: impl Fn(D::Query<'_>) -> T + Send + Sync + Clone + 'static,
+    ) -> Result<Self, RxError> {
+        let memo = Self::spawn(rctx, input_deps, derive_fn);
+        if let Err(error) = rctx.try_read(memo) {
+            // The memo already subscribed to the inputs it read before failing.
+            for input in input_deps.reactive_entities() {
+                if let Some(mut subscribers) = rctx.reactive_state.get_mut::<RxSubscribers>(input) {
+                    subscribers.unsubscribe(memo.reactor_entity);
+                }
+            }
+            dispose::dispose(&mut rctx.reactive_state, memo.reactor_entity);
+            return Err(error);
+        }
+        Ok(memo)
+    }
+
+    /// Warn if the newly created memo could not compute a value. In strict mode, panic instead,
+    /// and check the graph, see [`ReactiveContext::set_strict`].
+    fn check_initial_value<S>(self, rctx: &mut ReactiveContext<S>) {
+        let strict = rctx.reactive_state.resource::<RxTraversal>().strict;
+        match rctx.try_read(self) {
+            Err(error) if strict => panic!("Strict mode: memo created without a value: {error}"),
+            Err(error) => warn!("Memo created without an initial value: {error}"),
+            Ok(_) => {}
+        }
+        if strict {
+            stats::check_strict(&rctx.reactive_state);
+        }
+    }
+
+    /// Spawn a memo backed by a custom calculation, and compute its initial value. The calculation
+    /// receives the memo's entity, which it must update and subscribe with.
+    pub(crate) fn from_calculation<S>(
+        rctx: &mut ReactiveContext<S>,
+        calculation: impl FnOnce(Entity) -> RxMemo,
+    ) -> Self {
+        let entity = rctx
+            .reactive_state
+            .spawn((RxSubscribers::default(), RxMemoNode))
+            .id();
+        let mut memo = calculation(entity);
+        memo.execute(&mut rctx.reactive_state, &mut Vec::new());
+        rctx.reactive_state.entity_mut(entity).insert(memo);
+        Self {
+            reactor_entity: entity,
+            p: PhantomData,
+        }
+    }
+
+    fn spawn<S, D: MemoQuery<T>>(
+        rctx: &mut ReactiveContext<S>,
+        input_deps: D,
+        derive_fn: impl Fn(D::Query<'_>) -> T + Send + Sync + Clone + 'static,
     ) -> Self {
-        let entity = rctx.reactive_state.spawn_empty().id();
-        let mut derived = RxMemo::new(entity, input_deps, derive_fn);
+        let entity = rctx
+            .reactive_state
+            .spawn((RxSubscribers::default(), RxMemoNode))
+            .id();
+        let parallel = RxTraversal::is_parallel(&rctx.reactive_state);
+        let mut derived = RxMemo::new(entity, input_deps, derive_fn, parallel);
         derived.execute(&mut rctx.reactive_state, &mut Vec::new());
         rctx.reactive_state.entity_mut(entity).insert(derived);
         Self {
@@ -45,11 +143,420 @@ impl<T: Clone + PartialEq + Send + Sync> Memo<T> {
         }
     }
 
+    /// Create a memo that caches up to `capacity` of its most recently used results, keyed on the
+    /// values of its inputs. When the inputs return to a combination that is still cached, the
+    /// result is reused instead of calling `derive_fn` again. The value is still diffed and
+    /// propagated like any other memo.
+    ///
+    /// This is meant for expensive calculations whose inputs move between a small set of values.
+    pub fn new_cached<S, D>(
+        rctx: &mut ReactiveContext<S>,
+        input_deps: D,
+        capacity: usize,
+        derive_fn: impl Fn(D::Query<'_>) -> T + Send + Sync + Clone + 'static,
+    ) -> Self
+    where
+        D: MemoQuery<T> + ObservableTuple,
+        D::Values: Eq + Send + Sync + 'static,
+    {
+        let memo = Self::from_calculation(rctx, |entity| {
+            RxMemo::new_cached(entity, input_deps, capacity, derive_fn)
+        });
+        memo.check_initial_value(rctx);
+        memo
+    }
+
+    /// Creates a memo that accumulates across changes of the `input` observable, like
+    /// [`Iterator::fold`]. Every time the input changes, `fold_fn` receives the memo's previous
+    /// value and the new input value, and returns the memo's next value.
+    ///
+    /// The memo is seeded with `initial_state`, and the input is folded into it immediately.
+    pub fn new_fold<S, O: Observable>(
+        rctx: &mut ReactiveContext<S>,
+        input: O,
+        initial_state: T,
+        fold_fn: impl Fn(&T, &O::DataType) -> T + Send + Sync + 'static,
+    ) -> Self {
+        let entity = rctx
+            .reactive_state
+            .spawn((RxSubscribers::default(), RxMemoNode))
+            .id();
+        // Seed the accumulator, the node has no subscribers yet.
+        RxObservableData::update_value(
+            &mut rctx.reactive_state,
+            &mut Vec::new(),
+            entity,
+            initial_state,
+        );
+        let mut fold = RxMemo::new_fold(entity, input, fold_fn);
+        fold.execute(&mut rctx.reactive_state, &mut Vec::new());
+        rctx.reactive_state.entity_mut(entity).insert(fold);
+        let memo = Self {
+            reactor_entity: entity,
+            p: PhantomData,
+        };
+        memo.check_initial_value(rctx);
+        memo
+    }
+
+    /// Creates a memo that tracks the value of the observable held *inside* the `outer`
+    /// observable, e.g. a `Memo<Signal<T>>` pointing at the currently selected signal.
+    ///
+    /// Whenever `outer` switches to a different inner observable, the memo unsubscribes from the
+    /// old inner observable and subscribes to the new one.
+    pub fn flatten<S, O>(rctx: &mut ReactiveContext<S>, outer: O) -> Self
+    where
+        O: Observable,
+        O::DataType: Observable<DataType = T> + Copy,
+    {
+        let entity = rctx
+            .reactive_state
+            .spawn((RxSubscribers::default(), RxMemoNode))
+            .id();
+        let mut flatten = RxMemo::flatten(entity, outer);
+        flatten.execute(&mut rctx.reactive_state, &mut Vec::new());
+        rctx.reactive_state.entity_mut(entity).insert(flatten);
+        Self {
+            reactor_entity: entity,
+            p: PhantomData,
+        }
+    }
+
+    /// Create a memo from reactive inputs and a `constant`, which `derive_fn` reads alongside the
+    /// inputs. The constant is configuration rather than an input: nothing subscribes to it, and it
+    /// can be replaced with [`Memo::set_const`] without rebuilding the memo's subscriptions.
+    pub fn new_with_const<S, K, D>(
+        rctx: &mut ReactiveContext<S>,
+        input_deps: D,
+        constant: K,
+        derive_fn: impl Fn(&K, D::Query<'_>) -> T + Send + Sync + Clone + 'static,
+    ) -> Self
+    where
+        K: Clone + Send + Sync + 'static,
+        D: MemoQuery<T>,
+    {
+        let entity = rctx
+            .reactive_state
+            .spawn((RxSubscribers::default(), RxMemoNode, RxMemoConst(constant)))
+            .id();
+        let mut derived = RxMemo::with_const(entity, input_deps, derive_fn);
+        derived.execute(&mut rctx.reactive_state, &mut Vec::new());
+        rctx.reactive_state.entity_mut(entity).insert(derived);
+        let memo = Self {
+            reactor_entity: entity,
+            p: PhantomData,
+        };
+        memo.check_initial_value(rctx);
+        memo
+    }
+
+    /// Replace the constant of a memo created with [`Memo::new_with_const`], and recompute the
+    /// memo with it. The memo stays subscribed to its inputs. Returns [`RxError::TypeMismatch`] if
+    /// the memo has no constant of type `K`.
+    pub fn set_const<S, K: Send + Sync + 'static>(
+        &self,
+        rctx: &mut ReactiveContext<S>,
+        constant: K,
+    ) -> Result<(), RxError> {
+        let entity = self.reactor_entity;
+        if rctx.reactive_state.get_entity(entity).is_none() {
+            return Err(RxError::NodeDisposed(entity));
+        }
+        if rctx.reactive_state.get::<RxMemoConst<K>>(entity).is_none() {
+            return Err(RxError::TypeMismatch {
+                entity,
+                expected: std::any::type_name::<K>(),
+                found: "a memo without this constant",
+            });
+        }
+        traversal::send(&mut rctx.reactive_state, move |world, stack| {
+            if let Some(mut current) = world.get_mut::<RxMemoConst<K>>(entity) {
+                current.0 = constant;
+                stack.push(entity);
+            }
+        });
+        Ok(())
+    }
+
+    /// Turn the `input` signal into a memo following `source`, see [`ReactiveContext::connect`].
+    pub fn connect<S, O: Observable<DataType = T>>(
+        rctx: &mut ReactiveContext<S>,
+        source: O,
+        input: Signal<T>,
+    ) -> Result<Self, RxError> {
+        let entity = input.reactor_entity;
+        let rx_world = &mut rctx.reactive_state;
+        if rx_world.get::<RxObservableData<T>>(entity).is_none() {
+            return Err(RxError::NodeDisposed(entity));
+        }
+        if rx_world.get::<RxMemoNode>(entity).is_some() {
+            return Err(RxError::MemoWrite(entity));
+        }
+        if depends_on(rx_world, source.reactive_entity(), entity) {
+            return Err(RxError::Cycle(entity));
+        }
+        let parallel = RxTraversal::is_parallel(rx_world);
+        let memo = RxMemo::new(entity, (source,), |(value,)| value.clone(), parallel);
+        // A memo follows its source exactly, so the parts of the signal that only apply to writes
+        // are removed with it.
+        rx_world
+            .entity_mut(entity)
+            .remove::<(RxSignalReset, RxHistory<T>, RxWriteMap<T>)>()
+            .insert((memo, RxMemoNode));
+        traversal::send(rx_world, move |world, stack| {
+            traversal::execute(world, entity, stack);
+        });
+        Ok(Self {
+            reactor_entity: entity,
+            p: PhantomData,
+        })
+    }
+
+    /// Create a lazy memo, which isn't computed when its inputs change. Instead, it is marked as
+    /// stale, and computed the next time it is read, along with any stale lazy memos it depends
+    /// on. Use this for expensive values that are read less often than their inputs change.
+    ///
+    /// Reading a lazy memo through the context, or from another memo, always returns a value
+    /// consistent with the latest signal values. Subscribers of a lazy memo are recomputed when it
+    /// becomes stale, since it may have changed, and its callbacks and effects run once it is
+    /// computed. It is not computed when created, so reads that don't compute stale memos, like
+    /// [`ReactiveContext::projected_read`], fail until it has been read once.
+    pub fn new_lazy<S, D: MemoQuery<T>>(
+        rctx: &mut ReactiveContext<S>,
+        input_deps: D,
+        derive_fn: impl Fn(D::Query<'_>) -> T + Send + Sync + Clone + 'static,
+    ) -> Self {
+        let entity = rctx
+            .reactive_state
+            .spawn((RxSubscribers::stale(), RxMemoNode))
+            .id();
+        // Lazy memos are never computed in parallel.
+        let mut memo = RxMemo::new(entity, input_deps, derive_fn, false);
+        memo.lazy = true;
+        rctx.reactive_state.entity_mut(entity).insert(memo);
+        Self {
+            reactor_entity: entity,
+            p: PhantomData,
+        }
+    }
+
+    /// Run the memo's calculation again, and recompute its subscribers even if its value did not
+    /// change.
+    ///
+    /// This is an escape hatch, for calculations that read state outside of their tracked inputs,
+    /// like non-reactive state that changed out-of-band. Prefer making that state a signal.
+    pub fn force_recompute<S>(&self, rctx: &mut ReactiveContext<S>) {
+        let entity = self.reactor_entity;
+        traversal::send(&mut rctx.reactive_state, move |world, stack| {
+            traversal::execute(world, entity, stack);
+            #[cfg(feature = "tracking")]
+            let len = stack.len();
+            if let Some(mut subscribers) = world.get_mut::<RxSubscribers>(entity) {
+                subscribers.drain_into(stack);
+            }
+            #[cfg(feature = "tracking")]
+            crate::tracker::RecomputeTracker::scheduled(world, entity, &stack[len..]);
+        });
+    }
+
+    /// Identify the memo's calculation with `key`, so
+    /// [`ReactiveContext::find_duplicate_memos`] reports it alongside other memos with the same
+    /// key and inputs. Use this for calculations that capture state, which are otherwise never
+    /// considered duplicates. The key is kept when the calculation is replaced with
+    /// [`Memo::set_derive`].
+    pub fn set_identity_key<S>(
+        &self,
+        rctx: &mut ReactiveContext<S>,
+        key: impl Into<Cow<'static, str>>,
+    ) {
+        if let Some(mut memo) = rctx.reactive_state.get_mut::<RxMemo>(self.reactor_entity) {
+            memo.identity = Some(MemoIdentity::Key(key.into()));
+        }
+    }
+
+    /// Choose whether the memo is recomputed while changes propagate, or once when the effects are
+    /// flushed. See [`MemoScheduling`]. If a dirty memo is made eager, it is recomputed
+    /// immediately. The scheduling is kept when the calculation is replaced with
+    /// [`Memo::set_derive`].
+    pub fn set_scheduling<S>(&self, rctx: &mut ReactiveContext<S>, scheduling: MemoScheduling) {
+        let entity = self.reactor_entity;
+        let Some(mut memo) = rctx.reactive_state.get_mut::<RxMemo>(entity) else {
+            return;
+        };
+        memo.scheduling = scheduling;
+        if scheduling == MemoScheduling::Eager && memo.dirty {
+            memo.dirty = false;
+            let mut scheduled = rctx.reactive_state.resource_mut::<RxScheduledMemos>();
+            scheduled.dirty.retain(|dirty| *dirty != entity);
+            traversal::send(&mut rctx.reactive_state, move |_, stack| stack.push(entity));
+        }
+    }
+
+    /// Replace the memo's calculation and inputs, keeping the same handle and subscribers, e.g. to
+    /// hot-reload logic without rebuilding the graph downstream of the memo. The memo is
+    /// recomputed with the new calculation immediately, and its subscribers are notified if its
+    /// value changed. A lazy memo stays lazy, and is only recomputed when it is next read, and a
+    /// scheduled memo stays scheduled, and is recomputed once when the effects are next flushed.
+    ///
+    /// This replaces any caching or folding the memo was created with. A key set with
+    /// [`Memo::set_identity_key`] is kept.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the memo has been disposed, or would depend on itself, see
+    /// [`Memo::try_set_derive`].
+    pub fn set_derive<S, D: MemoQuery<T> + 'static>(
+        &self,
+        rctx: &mut ReactiveContext<S>,
+        input_deps: D,
+        derive_fn: impl Fn(D::Query<'_>) -> T + Send + Sync + Clone + 'static,
+    ) where
+        T: 'static,
+    {
+        self.try_set_derive(rctx, input_deps, derive_fn)
+            .unwrap_or_else(|error| panic!("Failed to replace memo calculation: {error}"))
+    }
+
+    /// Replace the memo's calculation and inputs like [`Memo::set_derive`], or return an error if
+    /// the memo has been disposed, or if one of the new inputs depends on the memo, which would
+    /// create a cycle. The memo is left unchanged on error.
+    pub fn try_set_derive<S, D: MemoQuery<T> + 'static>(
+        &self,
+        rctx: &mut ReactiveContext<S>,
+        input_deps: D,
+        derive_fn: impl Fn(D::Query<'_>) -> T + Send + Sync + Clone + 'static,
+    ) -> Result<(), RxError>
+    where
+        T: 'static,
+    {
+        let entity = self.reactor_entity;
+        let rx_world = &mut rctx.reactive_state;
+        let Some(previous) = rx_world.get::<RxMemo>(entity) else {
+            return Err(RxError::NodeDisposed(entity));
+        };
+        if input_deps
+            .reactive_entities()
+            .iter()
+            .any(|input| depends_on(rx_world, *input, entity))
+        {
+            return Err(RxError::Cycle(entity));
+        }
+        let (lazy, scheduling, dirty) = (previous.lazy, previous.scheduling, previous.dirty);
+        let key = match &previous.identity {
+            Some(key @ MemoIdentity::Key(_)) => Some(key.clone()),
+            _ => None,
+        };
+        for input in previous.inputs.clone() {
+            if let Some(mut subscribers) = rx_world.get_mut::<RxSubscribers>(input) {
+                subscribers.unsubscribe(entity);
+            }
+        }
+        let parallel = RxTraversal::is_parallel(rx_world);
+        let mut memo = RxMemo::new(entity, input_deps, derive_fn, parallel);
+        memo.lazy = lazy;
+        memo.scheduling = scheduling;
+        memo.dirty = dirty;
+        if key.is_some() {
+            memo.identity = key;
+        }
+        rx_world.entity_mut(entity).insert(memo);
+        traversal::send(rx_world, move |world, stack| {
+            traversal::execute(world, entity, stack);
+        });
+        Ok(())
+    }
+
+    /// See [`ReactiveContext::read`].
     pub fn read<'r, S>(&self, rctx: &'r mut ReactiveContext<S>) -> &'r T {
-        rctx.reactive_state
-            .get::<RxObservableData<T>>(self.reactor_entity)
-            .unwrap()
-            .data()
+        rctx.read(*self)
+    }
+
+    /// See [`ReactiveContext::try_read`].
+    pub fn try_read<'r, S>(&self, rctx: &'r mut ReactiveContext<S>) -> Result<&'r T, RxError> {
+        rctx.try_read(*self)
+    }
+
+    /// Compute a result from the current value, borrowing the context only for the duration of
+    /// `f`. See [`ReactiveContext::projected_read`].
+    pub fn with<S, R>(&self, rctx: &ReactiveContext<S>, f: impl FnOnce(&T) -> R) -> R {
+        rctx.projected_read(*self, f)
+    }
+
+    /// Send every new value of the memo to `sender`, e.g. to record it for telemetry. Values are
+    /// sent synchronously as the change propagates, like [`ReactiveContext::on_change`]. The
+    /// sender is unsubscribed once its receiver has been dropped, which is detected when the next
+    /// value fails to send.
+    pub fn subscribe_to_channel<S>(&self, rctx: &mut ReactiveContext<S>, sender: Sender<T>)
+    where
+        T: 'static,
+    {
+        RxCallbacks::add_while(
+            &mut rctx.reactive_state,
+            self.reactor_entity,
+            move |value: &T| sender.send(value.clone()).is_ok(),
+        )
+        .detach();
+    }
+
+    /// Run `effect_system` when the memo changes, see [`ReactiveContext::new_deferred_effect`].
+    /// This keeps the memo and its effect together, e.g. `welcome.effect(rctx, print_welcome)`.
+    pub fn effect<S, M>(
+        &self,
+        rctx: &mut ReactiveContext<S>,
+        effect_system: impl IntoSystem<(), (), M>,
+    ) -> Effect
+    where
+        T: 'static,
+    {
+        Effect::new_deferred(rctx, *self, effect_system)
+    }
+
+    /// Derive a new memo from this one, so pipelines can be written as
+    /// `a.then(rctx, f).then(rctx, g)`. Each step is its own memo, cached and diffed like any
+    /// other, so later steps are only recomputed when an earlier step's value actually changes.
+    pub fn then<S, U: Clone + PartialEq + Send + Sync + 'static>(
+        self,
+        rctx: &mut ReactiveContext<S>,
+        f: impl Fn(&T) -> U + Send + Sync + Clone + 'static,
+    ) -> Memo<U>
+    where
+        T: 'static,
+    {
+        rctx.new_memo((self,), move |(value,)| f(value))
+    }
+}
+
+impl<T: Clone + PartialEq + Send + Sync + 'static> Memo<Option<T>> {
+    /// Like [`Memo::then`], for optional values. The new memo is `None` while this one is `None`,
+    /// otherwise it holds the output of `f`.
+    pub fn and_then<S, U: Clone + PartialEq + Send + Sync + 'static>(
+        self,
+        rctx: &mut ReactiveContext<S>,
+        f: impl Fn(&T) -> Option<U> + Send + Sync + Clone + 'static,
+    ) -> Memo<Option<U>> {
+        self.then(rctx, move |value| value.as_ref().and_then(&f))
+    }
+}
+
+/// Marks the node of a memo, whose value may only be written by its own calculation. Unlike
+/// [`RxMemo`], this stays on the node while the calculation runs.
+#[derive(Component)]
+pub(crate) struct RxMemoNode;
+
+impl RxMemoNode {
+    /// Returns an error if `observable` is a memo, which can't be written to from outside its
+    /// calculation without desyncing it from its inputs.
+    pub(crate) fn check_write(rx_world: &World, observable: Entity) -> Result<(), RxError> {
+        match rx_world.get::<Self>(observable) {
+            Some(_) => Err(RxError::MemoWrite(observable)),
+            None => Ok(()),
+        }
+    }
+
+    /// Panics if `observable` is a memo, see [`RxMemoNode::check_write`].
+    pub(crate) fn assert_write(rx_world: &World, observable: Entity) {
+        Self::check_write(rx_world, observable)
+            .unwrap_or_else(|error| panic!("Failed to write signal: {error}"));
     }
 }
 
@@ -64,18 +571,124 @@ impl<T: Clone + PartialEq + Send + Sync> Memo<T> {
 /// update the associated [`RxObservableData`] component.
 #[derive(Component)]
 pub(crate) struct RxMemo {
-    function: Box<dyn DeriveFn>,
+    /// Taken out while the calculation runs, so it can borrow the world mutably without moving the
+    /// component off the node, which would move the entity to another archetype on every recompute.
+    function: Option<Box<dyn DeriveFn>>,
+    /// The calculation without subscribing, for memos that can be computed from a shared world with
+    /// [`Traversal::Parallel`](crate::traversal::Traversal::Parallel).
+    shared: Option<Arc<SharedDeriveFn>>,
+    /// The inputs the memo always subscribes to, for checking the graph with
+    /// [`ReactiveContext::debug_validate`]. Memos whose inputs change while they run, like
+    /// flattened memos, only list the inputs that never change.
+    pub(crate) inputs: Vec<Entity>,
+    /// Lazy memos are only marked stale when their inputs change, and are computed once they are
+    /// read, see [`Memo::new_lazy`].
+    pub(crate) lazy: bool,
+    pub(crate) scheduling: MemoScheduling,
+    /// Set while a scheduled memo is waiting to be recomputed, see [`RxScheduledMemos`].
+    dirty: bool,
+    /// What the memo computes, used to find memos computing the same thing, see
+    /// [`ReactiveContext::find_duplicate_memos`].
+    pub(crate) identity: Option<MemoIdentity>,
+}
+
+/// When a memo is recomputed after its inputs change, see [`Memo::set_scheduling`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum MemoScheduling {
+    /// Recompute the memo while the change propagates, before the signal write returns.
+    #[default]
+    Eager,
+    /// Mark the memo as dirty while the change propagates, and recompute it once, no matter how
+    /// many of its inputs changed, the next time the effects are flushed, before any effect runs.
+    /// Until then, reading the memo returns its previous value, and its subscribers are not
+    /// recomputed. Use this for expensive memos whose inputs are written many times per frame.
+    Scheduled,
 }
 
+/// The scheduled memos waiting to be recomputed, see [`MemoScheduling::Scheduled`].
+#[derive(Resource, Default)]
+pub(crate) struct RxScheduledMemos {
+    dirty: Vec<Entity>,
+}
+
+impl RxScheduledMemos {
+    pub(crate) fn clear(&mut self) {
+        self.dirty.clear();
+    }
+
+    /// Mark the scheduled memo on `entity` as dirty, if it isn't already.
+    pub(crate) fn mark(rx_world: &mut World, entity: Entity) {
+        let Some(mut memo) = rx_world.get_mut::<RxMemo>(entity) else {
+            return;
+        };
+        if !memo.dirty {
+            memo.dirty = true;
+            rx_world.resource_mut::<Self>().dirty.push(entity);
+        }
+    }
+
+    /// Recompute every dirty scheduled memo, and propagate their changes. Scheduled memos that
+    /// become dirty again, because they depend on other scheduled memos, are recomputed in further
+    /// passes until none are left.
+    pub(crate) fn run(rx_world: &mut World) {
+        loop {
+            let dirty = std::mem::take(&mut rx_world.resource_mut::<Self>().dirty);
+            if dirty.is_empty() {
+                return;
+            }
+            traversal::send(rx_world, move |world, stack| {
+                for entity in dirty {
+                    let Some(mut memo) = world.get_mut::<RxMemo>(entity) else {
+                        continue;
+                    };
+                    memo.dirty = false;
+                    traversal::recompute(world, entity, stack);
+                }
+            });
+        }
+    }
+}
+
+/// Identifies the calculation of a memo, see [`ReactiveContext::find_duplicate_memos`].
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub(crate) enum MemoIdentity {
+    /// A calculation function that captures nothing, so its type uniquely identifies its behavior.
+    Function(TypeId),
+    /// A key given with [`Memo::set_identity_key`].
+    Key(Cow<'static, str>),
+}
+
+impl MemoIdentity {
+    /// The identity of `derive_fn`, if it captures nothing.
+    fn of<F: 'static>(_derive_fn: &F) -> Option<Self> {
+        (std::mem::size_of::<F>() == 0).then(|| Self::Function(TypeId::of::<F>()))
+    }
+}
+
+/// The constant read by a memo created with [`Memo::new_with_const`], which is not a reactive
+/// input.
+#[derive(Component, Clone)]
+pub(crate) struct RxMemoConst<K>(K);
+
+/// Computes a memo from a shared world, returning the write that updates its value, or `None` if
+/// an input has no value.
+pub(crate) type SharedDeriveFn = dyn Fn(&World) -> Option<Box<PendingWrite>> + Send + Sync;
+
 trait DeriveFn: Send + Sync + FnMut(&mut World, &mut Vec<Entity>) {}
 impl<T: Send + Sync + FnMut(&mut World, &mut Vec<Entity>)> DeriveFn for T {}
 
 impl RxMemo {
+    /// Create the memo's calculation. The shared form of the calculation is only built when
+    /// `parallel` is set, see [`RxTraversal::is_parallel`].
     pub(crate) fn new<C: Clone + Send + Sync + PartialEq + 'static, D: MemoQuery<C> + 'static>(
         entity: Entity,
         input_deps: D,
-        derive_fn: (impl Fn(D::Query<'_>) -> C + Clone + Send + Sync + 'static),
+        derive_fn: impl Fn(D::Query<'_>) -> C + Clone + Send + Sync + 'static,
+        parallel: bool,
     ) -> Self {
+        let inputs = input_deps.reactive_entities();
+        let identity = MemoIdentity::of(&derive_fn);
+        let shared = parallel.then(|| Self::shared_fn(entity, input_deps, derive_fn.clone()));
         let function = move |world: &mut World, stack: &mut Vec<Entity>| {
             let computed_value = D::read_and_derive(world, entity, derive_fn.clone(), input_deps);
             if let Some(computed_value) = computed_value {
@@ -83,15 +696,385 @@ impl RxMemo {
             }
         };
         let function = Box::new(function);
-        Self { function }
+        Self {
+            function: Some(function),
+            inputs,
+            lazy: false,
+            scheduling: MemoScheduling::Eager,
+            dirty: false,
+            identity,
+            shared,
+        }
+    }
+
+    /// The calculation of [`RxMemo::new`], computed from a shared world without subscribing.
+    fn shared_fn<C: Clone + Send + Sync + PartialEq + 'static, D: MemoQuery<C> + 'static>(
+        entity: Entity,
+        input_deps: D,
+        derive_fn: impl Fn(D::Query<'_>) -> C + Send + Sync + 'static,
+    ) -> Arc<SharedDeriveFn> {
+        Arc::new(move |world: &World| -> Option<Box<PendingWrite>> {
+            let computed_value = D::derive(world, &derive_fn, input_deps)?;
+            Some(Box::new(
+                move |world: &mut World, stack: &mut Vec<Entity>| {
+                    RxObservableData::update_value(world, stack, entity, computed_value);
+                },
+            ))
+        })
+    }
+
+    /// Like [`RxMemo::new`], but `derive_fn` also reads the memo's [`RxMemoConst`], without
+    /// subscribing to it.
+    fn with_const<C, K, D>(
+        entity: Entity,
+        input_deps: D,
+        derive_fn: impl Fn(&K, D::Query<'_>) -> C + Clone + Send + Sync + 'static,
+    ) -> Self
+    where
+        C: Clone + Send + Sync + PartialEq + 'static,
+        K: Clone + Send + Sync + 'static,
+        D: MemoQuery<C>,
+    {
+        let inputs = input_deps.reactive_entities();
+        let function = move |world: &mut World, stack: &mut Vec<Entity>| {
+            let Some(RxMemoConst(constant)) = world.get::<RxMemoConst<K>>(entity).cloned() else {
+                return;
+            };
+            let derive_fn = derive_fn.clone();
+            let derive = move |query: D::Query<'_>| derive_fn(&constant, query);
+            if let Some(computed_value) = D::read_and_derive(world, entity, derive, input_deps) {
+                RxObservableData::update_value(world, stack, entity, computed_value);
+            }
+        };
+        let function = Box::new(function);
+        Self {
+            function: Some(function),
+            inputs,
+            lazy: false,
+            scheduling: MemoScheduling::Eager,
+            dirty: false,
+            identity: None,
+            shared: None,
+        }
+    }
+
+    /// Create a memo from a function that updates the memo's value, typically with
+    /// [`RxObservableData::update_value`], after subscribing to the inputs it reads.
+    pub(crate) fn from_fn(
+        function: impl FnMut(&mut World, &mut Vec<Entity>) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            function: Some(Box::new(function)),
+            inputs: Vec::new(),
+            lazy: false,
+            scheduling: MemoScheduling::Eager,
+            dirty: false,
+            identity: None,
+            shared: None,
+        }
+    }
+
+    pub(crate) fn new_cached<T, D>(
+        entity: Entity,
+        input_deps: D,
+        capacity: usize,
+        derive_fn: impl Fn(D::Query<'_>) -> T + Clone + Send + Sync + 'static,
+    ) -> Self
+    where
+        T: Clone + Send + Sync + PartialEq + 'static,
+        D: MemoQuery<T> + ObservableTuple,
+        D::Values: Eq + Send + Sync + 'static,
+    {
+        let inputs = input_deps.reactive_entities();
+        let mut cache = LruCache::new(capacity);
+        let function = move |world: &mut World, stack: &mut Vec<Entity>| {
+            let Ok(key) = input_deps.read_cloned(world) else {
+                // Still subscribe to the inputs that exist, so the memo is computed once they do.
+                D::read_and_derive(world, entity, derive_fn.clone(), input_deps);
+                return;
+            };
+            let cached = cache.get(&key);
+            let computed_value = D::read_and_derive(
+                world,
+                entity,
+                |query| cached.clone().unwrap_or_else(|| derive_fn(query)),
+                input_deps,
+            );
+            if let Some(computed_value) = computed_value {
+                cache.insert(key, computed_value.clone());
+                RxObservableData::update_value(world, stack, entity, computed_value);
+            }
+        };
+        let function = Box::new(function);
+        Self {
+            function: Some(function),
+            inputs,
+            lazy: false,
+            scheduling: MemoScheduling::Eager,
+            dirty: false,
+            identity: None,
+            shared: None,
+        }
+    }
+
+    pub(crate) fn new_fold<T: Clone + PartialEq + Send + Sync + 'static, O: Observable>(
+        entity: Entity,
+        input: O,
+        fold_fn: impl Fn(&T, &O::DataType) -> T + Send + Sync + 'static,
+    ) -> Self {
+        let inputs = vec![input.reactive_entity()];
+        let function = move |world: &mut World, stack: &mut Vec<Entity>| {
+            let input_entity = input.reactive_entity();
+            if !RxSubscribers::add(world, input_entity, entity) {
+                return;
+            }
+            // The previous value of this memo is the accumulator of the fold.
+            let (Some(input_data), Some(state)) = (
+                world.get::<RxObservableData<O::DataType>>(input_entity),
+                world.get::<RxObservableData<T>>(entity),
+            ) else {
+                return;
+            };
+            let computed_value = fold_fn(state.data(), input_data.data());
+            RxObservableData::update_value(world, stack, entity, computed_value);
+        };
+        let function = Box::new(function);
+        Self {
+            function: Some(function),
+            inputs,
+            lazy: false,
+            scheduling: MemoScheduling::Eager,
+            dirty: false,
+            identity: None,
+            shared: None,
+        }
+    }
+
+    pub(crate) fn flatten<T, O>(entity: Entity, outer: O) -> Self
+    where
+        T: Clone + PartialEq + Send + Sync + 'static,
+        O: Observable,
+        O::DataType: Observable<DataType = T> + Copy,
+    {
+        let inputs = vec![outer.reactive_entity()];
+        let mut current_inner: Option<Entity> = None;
+        let function = move |world: &mut World, stack: &mut Vec<Entity>| {
+            let outer_entity = outer.reactive_entity();
+            if !RxSubscribers::add(world, outer_entity, entity) {
+                return;
+            }
+            let Some(outer_data) = world.get::<RxObservableData<O::DataType>>(outer_entity) else {
+                return;
+            };
+            let inner = outer_data.data().reactive_entity();
+
+            // The outer observable switched to a new inner observable, so stop listening to the
+            // old one. Otherwise, changes to the old inner value would still recompute this memo.
+            if let Some(previous) = current_inner.replace(inner).filter(|prev| *prev != inner) {
+                if let Some(mut previous) = world.get_mut::<RxSubscribers>(previous) {
+                    previous.unsubscribe(entity);
+                }
+            }
+
+            if !RxSubscribers::add(world, inner, entity) {
+                return;
+            }
+            let Some(inner_data) = world.get::<RxObservableData<T>>(inner) else {
+                return;
+            };
+            let computed_value = inner_data.data().clone();
+            RxObservableData::update_value(world, stack, entity, computed_value);
+        };
+        let function = Box::new(function);
+        Self {
+            function: Some(function),
+            inputs,
+            lazy: false,
+            scheduling: MemoScheduling::Eager,
+            dirty: false,
+            identity: None,
+            shared: None,
+        }
+    }
+
+    /// Mark the lazy memo on `entity` as stale instead of computing it, and invalidate its
+    /// subscribers, which compute it if they read it.
+    pub(crate) fn invalidate(world: &mut World, entity: Entity, stack: &mut Vec<Entity>) {
+        #[cfg(feature = "tracking")]
+        let len = stack.len();
+        if let Some(mut subscribers) = world.get_mut::<RxSubscribers>(entity) {
+            subscribers.stale = true;
+            subscribers.drain_into(stack);
+        }
+        #[cfg(feature = "tracking")]
+        crate::tracker::RecomputeTracker::scheduled(world, entity, &stack[len..]);
+    }
+
+    /// Compute the memo on `entity` if it is a stale lazy memo. Its stale ancestors are computed
+    /// first, in dependency order, so the value is consistent with the latest signal values.
+    pub(crate) fn pull(world: &mut World, entity: Entity) {
+        let is_stale = |world: &World, node: Entity| {
+            world
+                .get::<RxSubscribers>(node)
+                .is_some_and(|subscribers| subscribers.stale)
+        };
+        if !is_stale(world, entity) {
+            return;
+        }
+        // Walk up the stale inputs depth first, recording each node after its inputs.
+        let mut order = Vec::new();
+        let mut visited = HashSet::default();
+        let mut walk = vec![(entity, false)];
+        while let Some((node, inputs_walked)) = walk.pop() {
+            if inputs_walked {
+                order.push(node);
+                continue;
+            }
+            if !visited.insert(node) {
+                continue;
+            }
+            walk.push((node, true));
+            if let Some(memo) = world.get::<Self>(node) {
+                let stale_inputs = memo.inputs.iter().filter(|input| is_stale(world, **input));
+                walk.extend(stale_inputs.map(|input| (*input, false)));
+            }
+        }
+        for node in order {
+            if let Some(mut subscribers) = world.get_mut::<RxSubscribers>(node) {
+                subscribers.stale = false;
+            }
+            world.resource_mut::<RxTraversal>().counters.recomputes += 1;
+            // Subscribers were invalidated when the memo became stale, so there are none to notify.
+            Self::execute_in_place(world, node, &mut Vec::new());
+        }
     }
 
     pub(crate) fn execute(&mut self, world: &mut World, stack: &mut Vec<Entity>) {
-        (self.function)(world, stack);
+        if let Some(function) = &mut self.function {
+            function(world, stack);
+        }
+    }
+
+    /// The calculation of the eager memo on `entity`, if it can be computed from a shared world.
+    /// The memo's inputs must be subscribed to before it runs.
+    pub(crate) fn shared(world: &World, entity: Entity) -> Option<Arc<SharedDeriveFn>> {
+        let memo = world.get::<Self>(entity)?;
+        let eager = memo.is_idle() && !memo.lazy && memo.scheduling == MemoScheduling::Eager;
+        memo.shared.clone().filter(|_| eager)
+    }
+
+    /// Returns `false` while the calculation is running.
+    pub(crate) fn is_idle(&self) -> bool {
+        self.function.is_some()
+    }
+
+    /// Run the calculation of the memo on the `entity` node in place. Does nothing if the node is
+    /// not a memo, or if its calculation is already running.
+    pub(crate) fn execute_in_place(world: &mut World, entity: Entity, stack: &mut Vec<Entity>) {
+        let Some(mut function) = world
+            .get_mut::<Self>(entity)
+            .and_then(|mut memo| memo.function.take())
+        else {
+            return;
+        };
+        function(world, stack);
+        // The calculation may have been replaced, or the node disposed, while it was running.
+        if let Some(mut memo) = world.get_mut::<Self>(entity) {
+            memo.function.get_or_insert(function);
+        }
     }
 }
 
-/// Implemented on tuples to be used for querying
+/// Returns `true` if `node` is `target`, or is recomputed when `target` changes, found by following
+/// the subscribers of `target`.
+fn depends_on(rx_world: &World, node: Entity, target: Entity) -> bool {
+    let mut visited = HashSet::default();
+    let mut stack = vec![target];
+    while let Some(next) = stack.pop() {
+        if next == node {
+            return true;
+        }
+        if let Some(subscribers) = rx_world.get::<RxSubscribers>(next) {
+            let unvisited = subscribers
+                .subscribers
+                .iter()
+                .filter(|s| visited.insert(**s));
+            stack.extend(unvisited);
+        }
+    }
+    false
+}
+
+/// A small least recently used cache. Entries are searched linearly, which is faster than hashing
+/// for the handful of entries it is meant to hold.
+struct LruCache<K, V> {
+    /// Entries ordered from least to most recently used.
+    entries: VecDeque<(K, V)>,
+    capacity: usize,
+}
+
+impl<K: Eq, V: Clone> LruCache<K, V> {
+    fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    fn get(&self, key: &K) -> Option<V> {
+        self.entries
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.clone())
+    }
+
+    /// Insert or refresh an entry, marking it as the most recently used.
+    fn insert(&mut self, key: K, value: V) {
+        if let Some(index) = self.entries.iter().position(|(k, _)| *k == key) {
+            self.entries.remove(index);
+        }
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back((key, value));
+    }
+}
+
+/// Subscribe the `reader` to the `input` observable, for [`MemoQuery`] implementations generated by
+/// `#[derive(MemoQuery)]`.
+#[doc(hidden)]
+pub fn subscribe_input(world: &mut World, input: &impl Observable, reader: Entity) -> bool {
+    RxSubscribers::add(world, input.reactive_entity(), reader)
+}
+
+/// Read the value of the `input` observable, for [`MemoQuery`] implementations generated by
+/// `#[derive(MemoQuery)]`.
+#[doc(hidden)]
+pub fn read_input<'w, O: Observable>(world: &'w World, input: &O) -> Option<&'w O::DataType> {
+    world
+        .get::<RxObservableData<O::DataType>>(input.reactive_entity())
+        .map(RxObservableData::data)
+}
+
+/// Implemented on tuples to be used for querying, and on structs of observables with
+/// `#[derive(MemoQuery)]`, see [`crate::MemoQuery`].
+///
+/// This is not implemented for `()`, because a memo without inputs would never be recomputed. Use a
+/// signal for a value that is set rather than computed.
+///
+/// ```compile_fail
+/// # use bevy_rx::prelude::*;
+/// let mut reactor = ReactiveContext::<()>::default();
+/// reactor.new_memo((), |()| 5);
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can't be used as the inputs of a memo",
+    label = "expected a tuple of observables, like `(signal,)`, or a `#[derive(MemoQuery)]` struct",
+    note = "a memo needs at least one input, use a signal for values that are set directly"
+)]
 pub trait MemoQuery<T>: Copy + Send + Sync + 'static {
     type Query<'a>;
     fn read_and_derive(
@@ -100,6 +1083,16 @@ pub trait MemoQuery<T>: Copy + Send + Sync + 'static {
         derive_fn: impl Fn(Self::Query<'_>) -> T,
         input_deps: Self,
     ) -> Option<T>;
+    /// Read the inputs and derive the value without subscribing to them, so the memo can be
+    /// computed from a shared world, see
+    /// [`Traversal::Parallel`](crate::traversal::Traversal::Parallel).
+    fn derive(
+        world: &World,
+        derive_fn: impl Fn(Self::Query<'_>) -> T,
+        input_deps: Self,
+    ) -> Option<T>;
+    /// The reactive entities of the inputs, in order.
+    fn reactive_entities(&self) -> Vec<Entity>;
 }
 
 macro_rules! impl_CalcQuery {
@@ -114,21 +1107,73 @@ macro_rules! impl_CalcQuery {
                 entities: Self,
             ) -> Option<D> {
                 let ($($I,)*) = entities;
-                let entities = [$($I.reactive_entity(),)*];
 
-                // Note this is left to unwrap intentionally. If aliased mutability happens, this is
-                // an error and should panic. If we were to early exit here, it would lead to
-                // harder-to-debug errors down the line.
-                let [$(mut $I,)*] = world.get_many_entities_mut(entities).unwrap();
+                // Subscribing and reading are done one input at a time, rather than borrowing all
+                // inputs at once, so the same observable can be used as an input more than once.
+                $(
+                    if !RxSubscribers::add(world, $I.reactive_entity(), reader) {
+                        return None;
+                    }
+                )*
 
-                $($I.get_mut::<RxObservableData<$T::DataType>>()?.subscribe(reader);)*
+                Self::derive(world, derive_fn, entities)
+            }
 
+            fn derive(
+                world: &World,
+                derive_fn: impl Fn(Self::Query<'_>) -> D,
+                entities: Self,
+            ) -> Option<D> {
+                let ($($I,)*) = entities;
                 Some(derive_fn((
-                    $($I.get::<RxObservableData<$T::DataType>>()?.data(),)*
+                    $(world.get::<RxObservableData<$T::DataType>>($I.reactive_entity())?.data(),)*
                 )))
             }
+
+            fn reactive_entities(&self) -> Vec<Entity> {
+                let ($($I,)*) = self;
+                vec![$($I.reactive_entity(),)*]
+            }
         }
     }
 }
 
 all_tuples_with_size!(impl_CalcQuery, 1, 32, T, s);
+
+/// Implemented on tuples of values computed together by [`ReactiveContext::new_memo_multi`], to
+/// split them into a memo for each value.
+pub trait MemoOutputs: Clone + PartialEq + Send + Sync + Sized + 'static {
+    /// A tuple with a [`Memo`] for each value.
+    type Memos;
+    /// Create a memo for each value held by the `outputs` memo.
+    fn split<S>(rctx: &mut ReactiveContext<S>, outputs: Memo<Self>) -> Self::Memos;
+}
+
+macro_rules! impl_MemoOutputs {
+    ($(($T: ident, $i: tt)),*) => {
+        impl<$($T: Clone + PartialEq + Send + Sync + 'static),*> MemoOutputs for ($($T,)*) {
+            type Memos = ($(Memo<$T>,)*);
+
+            fn split<S>(rctx: &mut ReactiveContext<S>, outputs: Memo<Self>) -> Self::Memos {
+                ($(rctx.new_memo((outputs,), |(outputs,)| outputs.$i.clone()),)*)
+            }
+        }
+    }
+}
+
+impl_MemoOutputs!((A, 0), (B, 1));
+impl_MemoOutputs!((A, 0), (B, 1), (C, 2));
+impl_MemoOutputs!((A, 0), (B, 1), (C, 2), (D, 3));
+impl_MemoOutputs!((A, 0), (B, 1), (C, 2), (D, 3), (E, 4));
+impl_MemoOutputs!((A, 0), (B, 1), (C, 2), (D, 3), (E, 4), (F, 5));
+impl_MemoOutputs!((A, 0), (B, 1), (C, 2), (D, 3), (E, 4), (F, 5), (G, 6));
+impl_MemoOutputs!(
+    (A, 0),
+    (B, 1),
+    (C, 2),
+    (D, 3),
+    (E, 4),
+    (F, 5),
+    (G, 6),
+    (H, 7)
+);
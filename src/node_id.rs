@@ -0,0 +1,105 @@
+//! Stable ids for reactive nodes, that survive rebuilding the graph, see [`NodeId`].
+
+use std::{any::TypeId, marker::PhantomData};
+
+use bevy_ecs::prelude::*;
+use bevy_utils::HashMap;
+
+use crate::{
+    error::RxError,
+    memo::{Memo, RxMemoNode},
+    observable::RxDataType,
+    signal::Signal,
+};
+
+/// A user-assigned id for a reactive node, assigned with [`crate::ReactiveContext::assign_id`].
+///
+/// Handles like [`Signal`] and [`Memo`] wrap an entity, which changes when the graph is rebuilt,
+/// e.g. after [`crate::ReactiveContext::clear`] or when loading a save. An id is stable: assign
+/// the same id to the node when rebuilding, and references stored as ids, like those in a saved
+/// UI layout, resolve to the new node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId(pub u64);
+
+/// Maps the assigned ids to the nodes they currently refer to.
+#[derive(Resource, Default)]
+pub(crate) struct RxNodeIds {
+    entities: HashMap<NodeId, Entity>,
+}
+
+impl RxNodeIds {
+    pub(crate) fn assign(rx_world: &mut World, node: Entity, id: NodeId) -> Result<(), RxError> {
+        if rx_world.get_entity(node).is_none() {
+            return Err(RxError::NodeDisposed(node));
+        }
+        let mut ids = rx_world.get_resource_or_insert_with(Self::default);
+        ids.entities.insert(id, node);
+        Ok(())
+    }
+
+    /// The id assigned to the `node`, if any. Ids reassigned to another node are not returned.
+    pub(crate) fn id_of(rx_world: &World, node: Entity) -> Option<NodeId> {
+        let ids = rx_world.get_resource::<Self>()?;
+        ids.entities
+            .iter()
+            .find(|(_, entity)| **entity == node)
+            .map(|(id, _)| *id)
+    }
+
+    /// The node with this id, checking that it still exists and holds a `T`.
+    fn resolve<T: 'static>(rx_world: &World, id: NodeId) -> Result<Entity, RxError> {
+        let node = rx_world
+            .get_resource::<Self>()
+            .and_then(|ids| ids.entities.get(&id))
+            .copied()
+            .ok_or(RxError::UnknownId(id))?;
+        let entity = rx_world
+            .get_entity(node)
+            .ok_or(RxError::NodeDisposed(node))?;
+        match entity.get::<RxDataType>() {
+            Some(data_type) if data_type.id != TypeId::of::<T>() => {
+                Err(data_type.mismatch::<T>(node))
+            }
+            _ => Ok(node),
+        }
+    }
+
+    pub(crate) fn resolve_signal<T: Send + Sync + 'static>(
+        rx_world: &World,
+        id: NodeId,
+    ) -> Result<Signal<T>, RxError> {
+        let node = Self::resolve::<T>(rx_world, id)?;
+        // A signal handle could be used to write to the memo.
+        if rx_world.get::<RxMemoNode>(node).is_some() {
+            return Err(RxError::MemoWrite(node));
+        }
+        Ok(Signal {
+            reactor_entity: node,
+            p: PhantomData,
+        })
+    }
+
+    pub(crate) fn resolve_memo<T: Send + Sync + 'static>(
+        rx_world: &World,
+        id: NodeId,
+    ) -> Result<Memo<T>, RxError> {
+        let node = Self::resolve::<T>(rx_world, id)?;
+        if rx_world.get::<RxMemoNode>(node).is_none() {
+            return Err(RxError::TypeMismatch {
+                entity: node,
+                expected: "memo",
+                found: "signal",
+            });
+        }
+        Ok(Memo {
+            reactor_entity: node,
+            p: PhantomData,
+        })
+    }
+
+    pub(crate) fn clear(rx_world: &mut World) {
+        if let Some(mut ids) = rx_world.get_resource_mut::<Self>() {
+            ids.entities.clear();
+        }
+    }
+}
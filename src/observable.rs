@@ -1,90 +1,648 @@
+use std::{
+    any::{type_name, TypeId},
+    hash::{BuildHasher, Hash},
+    ops::Deref,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
 use bevy_ecs::prelude::*;
+use bevy_utils::{all_tuples, hashbrown::hash_map::Entry, FixedState, HashMap};
 
 use crate::{
-    effect::{RxDeferredEffect, RxDeferredEffects},
+    callback::RxCallbacks,
+    dispose,
+    effect::{RxDeferredEffect, RxDeferredEffects, RxEffectWatchers},
+    error::{RxError, RxPanicHook},
+    history::RxHistory,
+    lifecycle::RxLifecycle,
+    memo::RxMemo,
+    signal::RxWriteMap,
+    traversal::{self, RxTraversal},
     ReactiveContext,
 };
 
 /// Generalizes over multiple bevy reactive components the user has access to, that are ultimately
 /// just handles containing the entity in the [`ReactiveContext`].
+///
+/// Domain types that wrap a handle can implement this by forwarding to it, so they can be read and
+/// used as memo inputs directly:
+///
+/// ```
+/// # use bevy_rx::{observable::Observable, prelude::*};
+/// # use bevy_ecs::entity::Entity;
+/// #[derive(Clone, Copy)]
+/// struct Button {
+///     active: Signal<bool>,
+/// }
+///
+/// impl Observable for Button {
+///     type DataType = bool;
+///     fn reactive_entity(&self) -> Entity {
+///         self.active.reactive_entity()
+///     }
+/// }
+///
+/// let mut reactor = ReactiveContext::<()>::default();
+/// let button = Button {
+///     active: reactor.new_signal(false),
+/// };
+/// let label = reactor.new_memo((button,), |(active,)| if *active { "on" } else { "off" });
+///
+/// reactor.send_signal(button.active, true);
+/// assert!(reactor.read(button));
+/// assert_eq!(*reactor.read(label), "on");
+/// ```
 pub trait Observable: Copy + Send + Sync + 'static {
     type DataType: PartialEq + Send + Sync + 'static;
     fn reactive_entity(&self) -> Entity;
 }
 
-/// The core reactive primitive that holds data, and a list of subscribers that are invoked when the
-/// data changes.
+/// An object safe version of [`Observable`], implemented for every observable, so handles holding
+/// different types of data can be stored together, e.g. in a `Vec<Box<dyn AnyObservable>>`.
+///
+/// Methods that only need to know which node to act on, like [`ReactiveContext::dispose`], accept
+/// any `impl AnyObservable`. Because [`AnyObservable::reactive_entity`] shares its name with
+/// [`Observable::reactive_entity`], avoid importing both traits into the same scope.
+pub trait AnyObservable: Send + Sync + 'static {
+    /// See [`Observable::reactive_entity`].
+    fn reactive_entity(&self) -> Entity;
+    /// The [`TypeId`] of the data held by the observable.
+    fn data_type_id(&self) -> TypeId;
+    /// The name of the type of data held by the observable, for display purposes.
+    fn data_type_name(&self) -> &'static str;
+}
+
+impl<O: Observable> AnyObservable for O {
+    fn reactive_entity(&self) -> Entity {
+        Observable::reactive_entity(self)
+    }
+
+    fn data_type_id(&self) -> TypeId {
+        TypeId::of::<O::DataType>()
+    }
+
+    fn data_type_name(&self) -> &'static str {
+        type_name::<O::DataType>()
+    }
+}
+
+impl AnyObservable for Box<dyn AnyObservable> {
+    fn reactive_entity(&self) -> Entity {
+        self.as_ref().reactive_entity()
+    }
+
+    fn data_type_id(&self) -> TypeId {
+        self.as_ref().data_type_id()
+    }
+
+    fn data_type_name(&self) -> &'static str {
+        self.as_ref().data_type_name()
+    }
+}
+
+/// The core reactive primitive that holds data. The subscribers that are invoked when the data
+/// changes are stored alongside it in [`RxSubscribers`].
 #[derive(Component)]
 pub(crate) struct RxObservableData<T> {
     pub data: T,
-    pub subscribers: Vec<Entity>,
+    /// The change tick at which `data` last changed, see [`ReactiveContext::last_changed_tick`].
+    pub changed_tick: u64,
 }
 
 impl<T: Send + Sync + 'static> RxObservableData<T> {
     #[allow(clippy::new_ret_no_self)]
     pub(crate) fn new<S>(rctx: &mut ReactiveContext<S>, data: T) -> Entity {
+        let changed_tick = RxTraversal::increment_change_tick(&mut rctx.reactive_state);
         rctx.reactive_state
-            .spawn(Self {
-                data,
-                subscribers: Vec::new(),
-            })
+            .spawn((
+                Self { data, changed_tick },
+                RxDataType::of::<T>(),
+                RxSubscribers::default(),
+            ))
             .id()
     }
 
-    pub(crate) fn subscribe(&mut self, entity: Entity) {
-        self.subscribers.push(entity);
+    /// Spawn a node for every value, along with its extra `bundle`, using a single batched spawn.
+    pub(crate) fn new_batch<S, B: Bundle>(
+        rctx: &mut ReactiveContext<S>,
+        values: impl IntoIterator<Item = (T, B)>,
+    ) -> Vec<Entity> {
+        let changed_tick = RxTraversal::increment_change_tick(&mut rctx.reactive_state);
+        let nodes = values.into_iter().map(|(data, bundle)| {
+            (
+                Self { data, changed_tick },
+                RxDataType::of::<T>(),
+                RxSubscribers::default(),
+                bundle,
+            )
+        });
+        rctx.reactive_state.spawn_batch(nodes).collect()
     }
 
     pub(crate) fn data(&self) -> &T {
         &self.data
     }
+
+    /// Read the data of the `observable` entity, see [`ReactiveContext::try_read`].
+    pub(crate) fn read(rx_world: &World, observable: Entity) -> Result<&T, RxError> {
+        let node = rx_world
+            .get_entity(observable)
+            .ok_or(RxError::NodeDisposed(observable))?;
+        if let Some(observable) = node.get::<Self>() {
+            return Ok(observable.data());
+        }
+        match node.get::<RxDataType>() {
+            Some(data_type) => Err(data_type.mismatch::<T>(observable)),
+            None => Err(RxError::NeverComputed(observable)),
+        }
+    }
+
+    /// Swap the data of the `observable` entity for `value`, returning the previous data, see
+    /// [`ReactiveContext::replace_value`].
+    pub(crate) fn replace(
+        rx_world: &mut World,
+        observable: Entity,
+        value: T,
+    ) -> Result<T, RxError> {
+        Self::read(rx_world, observable)?;
+        let mut reactive = rx_world.get_mut::<Self>(observable).unwrap();
+        Ok(std::mem::replace(&mut reactive.data, value))
+    }
+}
+
+/// A value that is diffed by comparing its hash, computed once when it is created, instead of
+/// comparing the values themselves. See [`ReactiveContext::new_signal_hashed`].
+///
+/// This is cheaper than [`PartialEq`] for large values, and works for types that only implement
+/// [`Hash`]. Two different values with the same hash are considered equal, so in the unlikely case
+/// of a 64 bit hash collision, a change is not propagated.
+#[derive(Debug, Clone)]
+pub struct Hashed<T> {
+    value: T,
+    hash: u64,
+}
+
+impl<T: Hash> Hashed<T> {
+    pub fn new(value: T) -> Self {
+        let hash = FixedState.hash_one(&value);
+        Self { value, hash }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value
+    }
 }
 
+impl<T> Deref for Hashed<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> PartialEq for Hashed<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.hash == other.hash
+    }
+}
+
+impl<T> Eq for Hashed<T> {}
+
+/// Whether a value changed since it was last read, see [`ReactiveContext::read_with_status`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Freshness {
+    /// The value changed since it was last read with its status, or is being read for the first
+    /// time.
+    Fresh,
+    /// The value is the same one that was last read with its status.
+    Unchanged,
+}
+
+/// The change tick of the value of a node when it was last read with
+/// [`ReactiveContext::read_with_status`].
+#[derive(Component)]
+pub(crate) struct RxLastRead(u64);
+
+impl Freshness {
+    /// Record that the value of the `observable` entity, which changed at `changed_tick`, was read,
+    /// returning whether it changed since the previous read.
+    pub(crate) fn observe(rx_world: &mut World, observable: Entity, changed_tick: u64) -> Self {
+        let mut node = rx_world.entity_mut(observable);
+        let last_read = node.get::<RxLastRead>().map(|read| read.0);
+        node.insert(RxLastRead(changed_tick));
+        match last_read {
+            Some(tick) if tick == changed_tick => Freshness::Unchanged,
+            _ => Freshness::Fresh,
+        }
+    }
+}
+
+/// The type of data held by a reactive node, recorded when its first value is stored, so accessing
+/// it with the wrong type can be reported clearly.
+#[derive(Component, Debug, Clone, Copy)]
+pub(crate) struct RxDataType {
+    pub id: TypeId,
+    pub name: &'static str,
+}
+
+impl RxDataType {
+    pub(crate) fn of<T: 'static>() -> Self {
+        Self {
+            id: TypeId::of::<T>(),
+            name: type_name::<T>(),
+        }
+    }
+
+    /// The error for accessing the node on `entity` as a `T`, which it doesn't hold.
+    pub(crate) fn mismatch<T: 'static>(&self, entity: Entity) -> RxError {
+        RxError::TypeMismatch {
+            entity,
+            expected: type_name::<T>(),
+            found: self.name,
+        }
+    }
+}
+
+/// Implemented on tuples of observables, to read all of their values at once with
+/// [`ReactiveContext::read_many`].
+pub trait ObservableTuple: Copy {
+    type Values;
+    fn read_cloned(self, rx_world: &World) -> Result<Self::Values, RxError>;
+    /// The entities of every observable in the tuple, in order.
+    fn reactive_entities(self) -> Vec<Entity>;
+}
+
+macro_rules! impl_ObservableTuple {
+    ($($O: ident),*) => {
+        impl<$($O: Observable),*> ObservableTuple for ($($O,)*)
+        where
+            $($O::DataType: Clone,)*
+        {
+            type Values = ($($O::DataType,)*);
+
+            #[allow(non_snake_case)]
+            fn read_cloned(self, rx_world: &World) -> Result<Self::Values, RxError> {
+                let ($($O,)*) = self;
+                Ok(($(
+                    RxObservableData::<$O::DataType>::read(rx_world, $O.reactive_entity())?.clone(),
+                )*))
+            }
+
+            #[allow(non_snake_case)]
+            fn reactive_entities(self) -> Vec<Entity> {
+                let ($($O,)*) = self;
+                vec![$($O.reactive_entity(),)*]
+            }
+        }
+    }
+}
+
+all_tuples!(impl_ObservableTuple, 1, 16, O);
+
+/// The reactive nodes that need to be recomputed when the data of an observable changes.
+///
+/// This is stored separately from [`RxObservableData`], so the graph can be walked without knowing
+/// the type of data held by each node.
+#[derive(Component, Debug)]
+pub(crate) struct RxSubscribers {
+    pub subscribers: Vec<Entity>,
+    /// The position of each subscriber in `subscribers`, for nodes with a high fan-out. When
+    /// present, subscribing and unsubscribing are O(1) instead of a linear scan, at the cost of
+    /// hashing on every subscribe. Added once the node has [`INDEX_THRESHOLD`] subscribers, or up
+    /// front, see [`crate::ReactiveContext::new_signal_high_fanout`].
+    index: Option<HashMap<Entity, usize>>,
+    /// Subscribers that stay subscribed when the observable changes, instead of having to
+    /// resubscribe, see [`crate::ReactiveContext::subscribe_persistent`]. They are also kept in
+    /// `subscribers`.
+    persistent: Vec<Entity>,
+    /// Increases with every node created, used to order subscribers by creation in deterministic
+    /// traversals, see [`crate::ReactiveContext::set_deterministic`].
+    pub(crate) creation_index: u64,
+    /// Set if the observable has lifecycle callbacks, see [`crate::ReactiveContext::on_connect`].
+    pub(crate) lifecycle: bool,
+    /// Set while the node is a lazy memo whose inputs changed since it was last computed, so it
+    /// must be computed before it is read, see [`crate::memo::Memo::new_lazy`].
+    pub(crate) stale: bool,
+}
+
+impl Default for RxSubscribers {
+    fn default() -> Self {
+        // Shared between contexts, which only affects the gaps between the indices of a graph, not
+        // their order.
+        static NEXT_CREATION_INDEX: AtomicU64 = AtomicU64::new(0);
+        Self {
+            subscribers: Vec::new(),
+            index: None,
+            persistent: Vec::new(),
+            creation_index: NEXT_CREATION_INDEX.fetch_add(1, Ordering::Relaxed),
+            lifecycle: false,
+            stale: false,
+        }
+    }
+}
+
+/// The number of subscribers at which a node starts indexing them, so skipping duplicate
+/// subscriptions doesn't scan every subscriber. Below this, a scan is faster than hashing.
+const INDEX_THRESHOLD: usize = 32;
+
+impl RxSubscribers {
+    /// Subscribers of a lazy memo that has not been computed yet.
+    pub(crate) fn stale() -> Self {
+        Self {
+            stale: true,
+            ..Self::default()
+        }
+    }
+
+    /// Subscribers backed by an index, for nodes with a very high fan-out.
+    pub(crate) fn indexed() -> Self {
+        Self {
+            index: Some(HashMap::default()),
+            ..Self::default()
+        }
+    }
+
+    /// Add a subscriber, unless it is already subscribed. Subscribers resubscribe every time they
+    /// are recomputed, so without this a node that is recomputed without its inputs changing would
+    /// be added again, and recomputed more than once when the inputs next change.
+    pub(crate) fn subscribe(&mut self, entity: Entity) {
+        match &mut self.index {
+            Some(index) => {
+                if let Entry::Vacant(position) = index.entry(entity) {
+                    position.insert(self.subscribers.len());
+                    self.subscribers.push(entity);
+                }
+            }
+            None if self.subscribers.len() < INDEX_THRESHOLD => {
+                if !self.subscribers.contains(&entity) {
+                    self.subscribers.push(entity);
+                }
+            }
+            None => {
+                let positions = self.subscribers.iter().enumerate();
+                self.index = Some(positions.map(|(i, subscriber)| (*subscriber, i)).collect());
+                self.subscribe(entity);
+            }
+        }
+    }
+
+    /// Add a subscriber that stays subscribed until it is explicitly unsubscribed.
+    pub(crate) fn subscribe_persistent(&mut self, entity: Entity) {
+        if !self.persistent.contains(&entity) {
+            self.persistent.push(entity);
+        }
+        self.subscribe(entity);
+    }
+
+    pub(crate) fn unsubscribe(&mut self, entity: Entity) {
+        self.persistent.retain(|subscriber| *subscriber != entity);
+        let Some(index) = &mut self.index else {
+            self.subscribers.retain(|subscriber| *subscriber != entity);
+            return;
+        };
+        let Some(position) = index.remove(&entity) else {
+            return;
+        };
+        self.subscribers.swap_remove(position);
+        if let Some(moved) = self.subscribers.get(position) {
+            index.insert(*moved, position);
+        }
+    }
+
+    /// Move every subscriber onto the traversal `stack`, leaving only the persistent ones.
+    /// Subscribers that still depend on the observable resubscribe when they are recomputed.
+    pub(crate) fn drain_into(&mut self, stack: &mut Vec<Entity>) {
+        stack.append(&mut self.subscribers);
+        if let Some(index) = &mut self.index {
+            index.clear();
+        }
+        for i in 0..self.persistent.len() {
+            self.subscribe(self.persistent[i]);
+        }
+    }
+
+    /// Recompute every subscriber of the `observable` entity, whether or not its value changed.
+    pub(crate) fn notify(rx_world: &mut World, observable: Entity) {
+        traversal::send(rx_world, move |world, stack| {
+            #[cfg(feature = "tracking")]
+            let len = stack.len();
+            if let Some(mut subscribers) = world.get_mut::<RxSubscribers>(observable) {
+                subscribers.drain_into(stack);
+            }
+            #[cfg(feature = "tracking")]
+            crate::tracker::RecomputeTracker::scheduled(world, observable, &stack[len..]);
+        });
+    }
+
+    /// Subscribe `subscriber` to the `observable` entity, returning `false` if the observable is
+    /// not a reactive node. If the observable has been disposed, the subscriber reacts according to
+    /// its [`DisposedInputs`](crate::dispose::DisposedInputs) policy.
+    pub(crate) fn add(rx_world: &mut World, observable: Entity, subscriber: Entity) -> bool {
+        if rx_world.get_entity(observable).is_none() {
+            dispose::input_disposed(rx_world, subscriber, observable);
+            return false;
+        }
+        let Some(mut subscribers) = rx_world.get_mut::<Self>(observable) else {
+            return false;
+        };
+        if subscribers.stale {
+            // Bring the lazy memo up to date before the subscriber reads it, so computing it
+            // doesn't invalidate the subscriber again.
+            RxMemo::pull(rx_world, observable);
+            let Some(current) = rx_world.get_mut::<Self>(observable) else {
+                return false;
+            };
+            subscribers = current;
+        }
+        subscribers.subscribe(subscriber);
+        if subscribers.lifecycle {
+            RxLifecycle::subscribed(rx_world, observable);
+        }
+        true
+    }
+
+    /// Persistently subscribe `subscriber` to the `observable` entity, returning `false` if either
+    /// is not in the reactive world.
+    pub(crate) fn add_persistent(
+        rx_world: &mut World,
+        observable: Entity,
+        subscriber: Entity,
+    ) -> bool {
+        if rx_world.get_entity(subscriber).is_none() {
+            return false;
+        }
+        let Some(mut subscribers) = rx_world.get_mut::<Self>(observable) else {
+            return false;
+        };
+        subscribers.subscribe_persistent(subscriber);
+        let lifecycle = subscribers.lifecycle;
+        let mut subscriber = rx_world.entity_mut(subscriber);
+        match subscriber.get_mut::<RxPersistentSources>() {
+            Some(mut sources) => sources.0.push(observable),
+            None => {
+                subscriber.insert(RxPersistentSources(vec![observable]));
+            }
+        }
+        if lifecycle {
+            RxLifecycle::subscribed(rx_world, observable);
+        }
+        true
+    }
+}
+
+/// The observables a node is persistently subscribed to, so those edges can be removed when the
+/// node is disposed, see [`RxSubscribers::add_persistent`].
+#[derive(Component, Default)]
+pub(crate) struct RxPersistentSources(pub(crate) Vec<Entity>);
+
 impl<T: Clone + PartialEq + Send + Sync + 'static> RxObservableData<T> {
-    /// Update the reactive value, and push subscribers onto the stack.
+    /// Update the reactive value, and push subscribers onto the stack. The value is stored without
+    /// being cloned.
     pub fn update_value(
         rx_world: &mut World,
         stack: &mut Vec<Entity>,
         observable: Entity,
         value: T,
     ) {
+        let value = RxWriteMap::apply(rx_world, observable, value);
+        Self::write(rx_world, stack, observable, value, false);
+    }
+
+    /// Update the reactive value like [`RxObservableData::update_value`], but if `force` is set,
+    /// notify subscribers even if the value is equal to the current one. Returns `false` if
+    /// propagation was cut off because the value did not change, or the node has been disposed,
+    /// e.g. by an earlier write in the same traversal. The value is stored as is, the caller
+    /// applies the signal's [`RxWriteMap`].
+    fn write(
+        rx_world: &mut World,
+        stack: &mut Vec<Entity>,
+        observable: Entity,
+        value: T,
+        force: bool,
+    ) -> bool {
+        let changed_tick = rx_world.resource::<RxTraversal>().change_tick + 1;
         if let Some(mut reactive) = rx_world.get_mut::<RxObservableData<T>>(observable) {
-            if reactive.data == value {
-                return; // Diff the value and early exit if no change.
+            if !force && reactive.data == value {
+                // Diff the value and early exit if no change, cutting off propagation here.
+                rx_world.resource_mut::<RxTraversal>().counters.cutoffs += 1;
+                return false;
+            }
+            reactive.changed_tick = changed_tick;
+            // The value is moved in, it is only cloned later if an effect needs a copy.
+            let previous = std::mem::replace(&mut reactive.data, value);
+            if let Some(mut history) = rx_world.get_mut::<RxHistory<T>>(observable) {
+                history.record(previous);
             }
-            reactive.data = value.clone();
-            // Remove all subscribers from this entity. If any of these subscribers end up
-            // using this data, they will resubscribe themselves. This is the
-            // auto-unsubscribe part of the reactive implementation.
-            //
-            // We push these subscribers on the stack, so that they can be executed, just
-            // like this one was. We use a stack instead of recursion to avoid stack
-            // overflow.
-            stack.append(&mut reactive.subscribers);
         } else {
-            rx_world.entity_mut(observable).insert(RxObservableData {
-                data: value.clone(),
-                subscribers: Default::default(),
-            });
-        }
-        if rx_world.get_mut::<RxDeferredEffect>(observable).is_some() {
-            rx_world
-                .resource_mut::<RxDeferredEffects>()
-                .push::<T>(observable);
+            // The first value of a memo whose inputs were missing, or that is computed outside of
+            // the graph. Anything that already tried to read it still needs to be recomputed.
+            let Some(mut entity) = rx_world.get_entity_mut(observable) else {
+                return false;
+            };
+            if let Some(data_type) = entity.get::<RxDataType>() {
+                if data_type.id != TypeId::of::<T>() {
+                    let error = data_type.mismatch::<T>(observable);
+                    RxPanicHook::report(rx_world, "Failed to update observable", error);
+                    return false;
+                }
+            }
+            entity.insert((
+                RxObservableData {
+                    data: value,
+                    changed_tick,
+                },
+                RxDataType::of::<T>(),
+            ));
         }
+        rx_world.resource_mut::<RxTraversal>().change_tick = changed_tick;
+        Self::notify_changed(rx_world, stack, observable);
+        true
     }
-    /// Update value of this reactive entity, additionally, trigger all subscribers. The
-    /// [`Reactive`] component will be added if it is missing.
-    pub(crate) fn send_signal(world: &mut World, signal_target: Entity, value: T) {
-        let mut stack = Vec::new();
 
-        Self::update_value(world, &mut stack, signal_target, value);
+    /// Push the subscribers of `observable` onto the stack after its value changed, and run its
+    /// callbacks and queue its effects, unless they are suppressed.
+    pub(crate) fn notify_changed(
+        rx_world: &mut World,
+        stack: &mut Vec<Entity>,
+        observable: Entity,
+    ) {
+        // Remove all subscribers from this entity. If any of these subscribers end up
+        // using this data, they will resubscribe themselves. This is the
+        // auto-unsubscribe part of the reactive implementation.
+        //
+        // We push these subscribers on the stack, so that they can be executed, just
+        // like this one was. We use a stack instead of recursion to avoid stack
+        // overflow.
+        #[cfg(feature = "tracking")]
+        let len = stack.len();
+        if let Some(mut subscribers) = rx_world.get_mut::<RxSubscribers>(observable) {
+            subscribers.drain_into(stack);
+        }
+        #[cfg(feature = "tracking")]
+        crate::tracker::RecomputeTracker::scheduled(rx_world, observable, &stack[len..]);
+        if rx_world.resource::<RxDeferredEffects>().suppressed {
+            return;
+        }
+        RxCallbacks::<T>::notify(rx_world, observable);
+        if rx_world.get::<RxDeferredEffect>(observable).is_some() {
+            RxDeferredEffects::enqueue::<T>(rx_world, observable);
+        }
+        RxEffectWatchers::input_changed(rx_world, observable);
+    }
 
-        while let Some(sub) = stack.pop() {
-            if let Some(mut calculation) = world.entity_mut(sub).take::<crate::memo::RxMemo>() {
-                calculation.execute(world, &mut stack);
-                world.entity_mut(sub).insert(calculation);
-            }
+    /// Update the value without notifying subscribers, callbacks, or effects. Returns `true` if the
+    /// value changed.
+    pub(crate) fn set_silent(rx_world: &mut World, observable: Entity, value: T) -> bool {
+        let value = RxWriteMap::apply(rx_world, observable, value);
+        let changed_tick = rx_world.resource::<RxTraversal>().change_tick + 1;
+        let Some(mut reactive) = rx_world.get_mut::<RxObservableData<T>>(observable) else {
+            return false;
+        };
+        if reactive.data == value {
+            return false;
+        }
+        reactive.changed_tick = changed_tick;
+        let previous = std::mem::replace(&mut reactive.data, value);
+        rx_world.resource_mut::<RxTraversal>().change_tick = changed_tick;
+        if let Some(mut history) = rx_world.get_mut::<RxHistory<T>>(observable) {
+            history.record(previous);
         }
+        true
+    }
+
+    /// Update the value of this reactive entity, and trigger all subscribers. The component is
+    /// added if it is missing. Returns `true` if the value changed.
+    ///
+    /// If the graph is already being traversed, the write is applied after it has settled, and the
+    /// value is diffed against the current value instead.
+    pub(crate) fn send_signal(world: &mut World, signal_target: Entity, value: T) -> bool {
+        let value = RxWriteMap::apply(world, signal_target, value);
+        // A queued write can't be diffed until it is applied, so compare it to the current value.
+        let queued = world.resource::<RxTraversal>().in_progress;
+        let differs =
+            queued && Self::read(world, signal_target).map_or(true, |data| *data != value);
+        traversal::send(world, move |world, stack| {
+            Self::write(world, stack, signal_target, value, false)
+        })
+        .unwrap_or(differs)
+    }
+
+    /// Like [`RxObservableData::send_signal`], but the value is stored without passing it through
+    /// the signal's [`RxWriteMap`], to restore a value that was already mapped when it was first
+    /// stored, e.g. by undo or reset.
+    pub(crate) fn send_signal_unmapped(world: &mut World, signal_target: Entity, value: T) {
+        traversal::send(world, move |world, stack| {
+            Self::write(world, stack, signal_target, value, false);
+        });
+    }
+
+    /// Like [`RxObservableData::send_signal`], but subscribers are notified even if the value did
+    /// not change.
+    pub(crate) fn send_signal_force(world: &mut World, signal_target: Entity, value: T) {
+        traversal::send(world, move |world, stack| {
+            let value = RxWriteMap::apply(world, signal_target, value);
+            Self::write(world, stack, signal_target, value, true);
+        });
     }
 }
@@ -0,0 +1,129 @@
+use std::ops::{Deref, DerefMut};
+
+use bevy_app::{PostUpdate, PreUpdate};
+use bevy_ecs::{prelude::*, system::SystemParam};
+
+use crate::{
+    component::RxTrackedComponents, events::RxEventSignal, receiver::RxReceivers,
+    time::RxTimeSignals, ReactiveContext,
+};
+
+pub struct ReactiveExtensionsPlugin;
+
+impl ReactiveExtensionsPlugin {
+    fn apply_deferred_effects(world: &mut World) {
+        world.resource_scope::<ReactiveContext<World>, _>(|world, mut rctx| {
+            rctx.flush_effects(world);
+        })
+    }
+}
+
+impl bevy_app::Plugin for ReactiveExtensionsPlugin {
+    fn build(&self, app: &mut bevy_app::App) {
+        app.init_resource::<ReactiveContext<World>>()
+            .add_systems(PreUpdate, (RxTimeSignals::update, RxReceivers::update))
+            .add_systems(PostUpdate, Self::apply_deferred_effects);
+    }
+}
+
+/// Extends the bevy [`App`](bevy_app::App) with integrations between the ECS and the
+/// [`ReactiveContext`].
+pub trait ReactiveAppExt {
+    /// Forward bevy events of type `E` to the signal returned by
+    /// [`ReactiveContext::signal_from_events`], once per frame in [`PreUpdate`].
+    fn add_event_signal<E: Event + Clone + PartialEq>(&mut self) -> &mut Self;
+
+    /// Send changes to components of type `C` to the signals created with
+    /// [`ReactiveContext::track_component`], once per frame in [`PreUpdate`].
+    fn track_components<C: Component>(&mut self) -> &mut Self;
+
+    /// Register [`Inspected<T>`](crate::inspect::Inspected) for reflection, and mirror the values
+    /// of observables into it once per frame in [`PostUpdate`], so they can be viewed in an
+    /// inspector.
+    #[cfg(feature = "reflect")]
+    fn inspect<T>(&mut self) -> &mut Self
+    where
+        T: bevy_reflect::Reflect
+            + bevy_reflect::FromReflect
+            + bevy_reflect::TypePath
+            + bevy_reflect::GetTypeRegistration
+            + Clone
+            + PartialEq;
+}
+
+impl ReactiveAppExt for bevy_app::App {
+    fn add_event_signal<E: Event + Clone + PartialEq>(&mut self) -> &mut Self {
+        self.add_event::<E>()
+            .add_systems(PreUpdate, RxEventSignal::<E>::forward_events)
+    }
+
+    fn track_components<C: Component>(&mut self) -> &mut Self {
+        self.add_systems(PreUpdate, RxTrackedComponents::<C>::send_changes)
+    }
+
+    #[cfg(feature = "reflect")]
+    fn inspect<T>(&mut self) -> &mut Self
+    where
+        T: bevy_reflect::Reflect
+            + bevy_reflect::FromReflect
+            + bevy_reflect::TypePath
+            + bevy_reflect::GetTypeRegistration
+            + Clone
+            + PartialEq,
+    {
+        use crate::inspect::Inspected;
+        self.register_type::<Inspected<T>>().add_systems(
+            PostUpdate,
+            Inspected::<T>::mirror.after(ReactiveExtensionsPlugin::apply_deferred_effects),
+        )
+    }
+}
+
+/// A system param to make accessing the [`ReactiveContext`] less verbose.
+///
+/// It also holds the [`Commands`] of the system, so a system can react to the graph by changing
+/// the main world directly with [`Reactor::scope`], instead of registering a deferred effect.
+#[derive(SystemParam)]
+pub struct Reactor<'w, 's> {
+    context: ResMut<'w, ReactiveContext<World>>,
+    commands: Commands<'w, 's>,
+}
+
+impl<'w, 's> Reactor<'w, 's> {
+    /// Run `f` with both the [`ReactiveContext`] and the [`Commands`] of this system, e.g. to
+    /// spawn an entity when a memo has changed:
+    ///
+    /// ```ignore
+    /// fn spawn_on_level_up(mut reactor: Reactor) {
+    ///     reactor.scope(|rctx, commands| {
+    ///         if rctx.read_with_status(level).1 == Freshness::Fresh {
+    ///             commands.spawn(LevelUpBanner);
+    ///         }
+    ///     });
+    /// }
+    /// ```
+    ///
+    /// The commands are applied at the next sync point after this system, like any other
+    /// commands. Effects triggered by signals sent inside `f` are not run here, but when the
+    /// effects are flushed in [`PostUpdate`], so for a system in `Update` the commands issued in
+    /// `f` are applied before those effects run.
+    pub fn scope<R>(
+        &mut self,
+        f: impl FnOnce(&mut ReactiveContext<World>, &mut Commands<'w, 's>) -> R,
+    ) -> R {
+        f(&mut self.context, &mut self.commands)
+    }
+}
+
+impl<'w, 's> Deref for Reactor<'w, 's> {
+    type Target = ReactiveContext<World>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.context
+    }
+}
+impl<'w, 's> DerefMut for Reactor<'w, 's> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.context
+    }
+}
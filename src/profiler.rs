@@ -0,0 +1,75 @@
+//! Timing memo recomputes, to find the expensive nodes in a large graph. Only available with the
+//! `profiling` feature, so production builds pay nothing for it.
+
+use std::time::Duration;
+
+use bevy_ecs::prelude::*;
+
+use crate::ReactiveContext;
+
+/// How long the recomputes of a memo took, since it was created or the profile was reset. See
+/// [`ReactiveContext::profile_report`].
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NodeProfile {
+    /// The memo's node in the reactive world.
+    pub node: Entity,
+    pub recomputes: u32,
+    pub total: Duration,
+    pub min: Duration,
+    pub max: Duration,
+}
+
+impl NodeProfile {
+    /// The average time taken by a recompute.
+    pub fn average(&self) -> Duration {
+        self.total / self.recomputes.max(1)
+    }
+
+    pub(crate) fn record(rx_world: &mut World, memo: Entity, elapsed: Duration) {
+        let Some(mut entity) = rx_world.get_entity_mut(memo) else {
+            return;
+        };
+        let Some(mut profile) = entity.get_mut::<Self>() else {
+            entity.insert(Self {
+                node: memo,
+                recomputes: 1,
+                total: elapsed,
+                min: elapsed,
+                max: elapsed,
+            });
+            return;
+        };
+        profile.recomputes += 1;
+        profile.total += elapsed;
+        profile.min = profile.min.min(elapsed);
+        profile.max = profile.max.max(elapsed);
+    }
+}
+
+impl<S> ReactiveContext<S> {
+    /// The recompute timings of every memo that has been recomputed, sorted by the total time
+    /// spent recomputing it, so the hottest memos come first.
+    pub fn profile_report(&mut self) -> Vec<NodeProfile> {
+        let mut report: Vec<NodeProfile> = self
+            .reactive_state
+            .query::<&NodeProfile>()
+            .iter(&self.reactive_state)
+            .copied()
+            .collect();
+        report.sort_by_key(|profile| std::cmp::Reverse(profile.total));
+        report
+    }
+
+    /// Discard the timings gathered so far, e.g. after the graph has been built, to only profile
+    /// the updates that follow.
+    pub fn reset_profile(&mut self) {
+        let nodes: Vec<Entity> = self
+            .reactive_state
+            .query_filtered::<Entity, With<NodeProfile>>()
+            .iter(&self.reactive_state)
+            .collect();
+        for node in nodes {
+            self.reactive_state.entity_mut(node).remove::<NodeProfile>();
+        }
+    }
+}
@@ -0,0 +1,135 @@
+//! Signals fed by channels, to bridge external event sources like websockets into the graph. See
+//! [`ReactiveContext::signal_from_receiver`]. Observables of another context are imported the same
+//! way, see [`ReactiveContext::import`].
+
+use std::sync::{
+    mpsc::{self, Receiver, TryRecvError},
+    Mutex,
+};
+
+use bevy_ecs::prelude::*;
+
+use crate::{
+    callback::RxCallbacks,
+    observable::{Observable, RxObservableData},
+    signal::Signal,
+    ReactiveContext,
+};
+
+/// How the items waiting in a channel are sent to its signal when it is polled.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReceiverMode {
+    /// Only send the most recent item, skipping any that arrived before it since the last poll.
+    #[default]
+    Latest,
+    /// Send every item in order, propagating each one through the graph.
+    All,
+}
+
+/// Drains a channel into its signal. Returns `false` once the channel or signal is gone.
+type PollFn = dyn FnMut(&mut World) -> bool + Send + Sync;
+
+/// The channels feeding signals, polled by [`ReactiveContext::poll_receivers`].
+#[derive(Resource, Default)]
+pub(crate) struct RxReceivers {
+    pollers: Vec<Box<PollFn>>,
+}
+
+impl RxReceivers {
+    pub(crate) fn add<S, T: Clone + Send + Sync + PartialEq + 'static>(
+        rctx: &mut ReactiveContext<S>,
+        receiver: Receiver<T>,
+        mode: ReceiverMode,
+    ) -> Signal<Option<T>> {
+        let signal = rctx.new_signal(None);
+        Self::feed(rctx, receiver, mode, signal.reactive_entity(), Some);
+        signal
+    }
+
+    /// Mirror the `observable` of the `source` context into a signal of `rctx`, through a channel
+    /// that is polled like any other. See [`ReactiveContext::import`].
+    pub(crate) fn import<S, S2, O: Observable>(
+        rctx: &mut ReactiveContext<S>,
+        source: &mut ReactiveContext<S2>,
+        observable: O,
+    ) -> Signal<O::DataType>
+    where
+        O::DataType: Clone,
+    {
+        let signal = rctx.new_signal(source.read_cloned(observable));
+        let (sender, receiver) = mpsc::channel();
+        RxCallbacks::add_while(
+            &mut source.reactive_state,
+            observable.reactive_entity(),
+            move |value: &O::DataType| sender.send(value.clone()).is_ok(),
+        )
+        .detach();
+        let target = signal.reactive_entity();
+        Self::feed(
+            rctx,
+            receiver,
+            ReceiverMode::Latest,
+            target,
+            std::convert::identity,
+        );
+        signal
+    }
+
+    /// Poll the `receiver` into the `target` signal, converting every item with `into`.
+    fn feed<S, T, U>(
+        rctx: &mut ReactiveContext<S>,
+        receiver: Receiver<T>,
+        mode: ReceiverMode,
+        target: Entity,
+        into: fn(T) -> U,
+    ) where
+        T: Send + 'static,
+        U: Clone + Send + Sync + PartialEq + 'static,
+    {
+        // `Receiver` is not `Sync`, but is only ever accessed through `&mut World`.
+        let mut receiver = Mutex::new(receiver);
+        let poll = move |rx_world: &mut World| {
+            if rx_world.get_entity(target).is_none() {
+                return false; // Disposed.
+            }
+            let receiver = receiver
+                .get_mut()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            let mut latest = None;
+            let connected = loop {
+                match receiver.try_recv() {
+                    Ok(item) if mode == ReceiverMode::All => {
+                        RxObservableData::send_signal(rx_world, target, into(item));
+                    }
+                    Ok(item) => latest = Some(item),
+                    Err(TryRecvError::Empty) => break true,
+                    Err(TryRecvError::Disconnected) => break false,
+                }
+            };
+            if let Some(item) = latest {
+                RxObservableData::send_signal(rx_world, target, into(item));
+            }
+            connected
+        };
+        rctx.reactive_state
+            .get_resource_or_insert_with(Self::default)
+            .pollers
+            .push(Box::new(poll));
+    }
+
+    /// Send the items waiting in every channel to their signals. Channels whose sender has been
+    /// dropped, or whose signal has been disposed, are removed.
+    pub(crate) fn poll(rx_world: &mut World) {
+        let Some(mut receivers) = rx_world.remove_resource::<Self>() else {
+            return;
+        };
+        receivers.pollers.retain_mut(|poll| poll(rx_world));
+        rx_world.insert_resource(receivers);
+    }
+
+    /// Poll the channels once per frame.
+    #[cfg(feature = "bevy_app")]
+    pub(crate) fn update(mut rctx: ResMut<ReactiveContext<World>>) {
+        rctx.poll_receivers();
+    }
+}
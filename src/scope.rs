@@ -0,0 +1,116 @@
+//! Grouping the nodes and effects created together, like those of a UI screen, so they can be
+//! disposed of at once. See [`ReactiveContext::scope`].
+
+use bevy_ecs::prelude::*;
+use bevy_utils::{HashMap, HashSet};
+
+use crate::{dispose, effect::RxDeferredEffect, ReactiveContext};
+
+/// Identifies a scope created with [`ReactiveContext::scope`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ScopeId(u64);
+
+/// The scope a node was created in.
+#[derive(Component)]
+pub(crate) struct RxScope(ScopeId);
+
+/// The scope an effect was created in, when it observes a node created outside of the scope.
+#[derive(Component)]
+pub(crate) struct RxEffectScope(ScopeId);
+
+/// Allocates scope ids, and tracks which scopes were created inside of others.
+#[derive(Resource, Default)]
+pub(crate) struct RxScopes {
+    next: u64,
+    parents: HashMap<ScopeId, ScopeId>,
+}
+
+impl RxScopes {
+    pub(crate) fn run<S, R>(
+        rctx: &mut ReactiveContext<S>,
+        build: impl FnOnce(&mut ReactiveContext<S>) -> R,
+    ) -> (ScopeId, R) {
+        let mut scopes = rctx
+            .reactive_state
+            .get_resource_or_insert_with(Self::default);
+        let scope = ScopeId(scopes.next);
+        scopes.next += 1;
+        let existing = Self::existing(&rctx.reactive_state);
+
+        let result = build(rctx);
+
+        let rx_world = &mut rctx.reactive_state;
+        let mut scopes = rx_world.resource_mut::<Self>();
+        for inner in (scope.0 + 1..scopes.next).map(ScopeId) {
+            scopes.parents.entry(inner).or_insert(scope);
+        }
+        let created: Vec<(Entity, bool)> = rx_world
+            .iter_entities()
+            .filter_map(|entity| match existing.get(&entity.id()) {
+                None => (!entity.contains::<RxScope>()).then_some((entity.id(), true)),
+                // An effect added to a node that existed before the scope.
+                Some(had_effect) => (!had_effect
+                    && entity.contains::<RxDeferredEffect>()
+                    && !entity.contains::<RxEffectScope>())
+                .then_some((entity.id(), false)),
+            })
+            .collect();
+        for (entity, is_node) in created {
+            match is_node {
+                true => rx_world.entity_mut(entity).insert(RxScope(scope)),
+                false => rx_world.entity_mut(entity).insert(RxEffectScope(scope)),
+            };
+        }
+        (scope, result)
+    }
+
+    /// Every entity in the reactive world, and whether it has an effect.
+    fn existing(rx_world: &World) -> HashMap<Entity, bool> {
+        rx_world
+            .iter_entities()
+            .map(|entity| (entity.id(), entity.contains::<RxDeferredEffect>()))
+            .collect()
+    }
+
+    /// Dispose of every node created in the `scope` or the scopes created inside it, and remove
+    /// the effects they added to other nodes. Returns the number of nodes disposed.
+    pub(crate) fn dispose(rx_world: &mut World, scope: ScopeId) -> usize {
+        let Some(mut scopes) = rx_world.get_resource_mut::<Self>() else {
+            return 0;
+        };
+        let mut disposed = HashSet::from_iter([scope]);
+        // Children always have higher ids than their parents, so one pass in order finds them all.
+        let mut children: Vec<_> = scopes.parents.keys().copied().collect();
+        children.sort();
+        for child in children {
+            if disposed.contains(&scopes.parents[&child]) {
+                disposed.insert(child);
+            }
+        }
+        scopes.parents.retain(|child, _| !disposed.contains(child));
+
+        let effects: Vec<Entity> = rx_world
+            .iter_entities()
+            .filter(|entity| {
+                entity
+                    .get::<RxEffectScope>()
+                    .is_some_and(|effect| disposed.contains(&effect.0))
+            })
+            .map(|entity| entity.id())
+            .collect();
+        for effect in effects {
+            let mut entity = rx_world.entity_mut(effect);
+            entity.remove::<(RxDeferredEffect, RxEffectScope)>();
+        }
+        let nodes: Vec<Entity> = rx_world
+            .iter_entities()
+            .filter(|entity| {
+                entity
+                    .get::<RxScope>()
+                    .is_some_and(|node| disposed.contains(&node.0))
+            })
+            .map(|entity| entity.id())
+            .collect();
+        dispose::dispose_all(rx_world, nodes)
+    }
+}
@@ -1,14 +1,22 @@
 use std::marker::PhantomData;
 
 use bevy_ecs::prelude::*;
+use bevy_utils::HashMap;
 
-use crate::{observable::RxObservableData, Observable, ReactiveContext};
+use crate::{
+    effect::Effect,
+    error::RxError,
+    memo::RxMemoNode,
+    observable::{RxObservableData, RxSubscribers},
+    traversal::{self, PendingWrite},
+    Observable, ReactiveContext,
+};
 
 /// A reactive component that can updated with new values or read through the [`ReactiveContext`].
 #[derive(Debug, Component)]
 pub struct Signal<T: Send + Sync + 'static> {
-    reactor_entity: Entity,
-    p: PhantomData<T>,
+    pub(crate) reactor_entity: Entity,
+    pub(crate) p: PhantomData<T>,
 }
 
 impl<T: Send + Sync + PartialEq> Observable for Signal<T> {
@@ -26,24 +34,386 @@ impl<T: Send + Sync + PartialEq> Clone for Signal<T> {
 
 impl<T: Send + Sync + PartialEq> Copy for Signal<T> {}
 
+impl<T: Send + Sync> PartialEq for Signal<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.reactor_entity == other.reactor_entity
+    }
+}
+
+impl<T: Send + Sync> Eq for Signal<T> {}
+
 impl<T: Clone + Send + Sync + PartialEq> Signal<T> {
     pub(crate) fn new<S>(rctx: &mut ReactiveContext<S>, initial_value: T) -> Self {
+        let reset = RxSignalReset::new(initial_value.clone());
+        let reactor_entity = RxObservableData::new(rctx, initial_value);
+        rctx.reactive_state.entity_mut(reactor_entity).insert(reset);
         Self {
-            reactor_entity: RxObservableData::new(rctx, initial_value),
+            reactor_entity,
             p: PhantomData,
         }
     }
 
-    pub fn read<'r, S>(&self, rctx: &'r mut ReactiveContext<S>) -> &'r T {
+    /// Create a signal for every value, see [`ReactiveContext::new_signals`].
+    pub(crate) fn new_batch<S>(
+        rctx: &mut ReactiveContext<S>,
+        values: impl IntoIterator<Item = T>,
+    ) -> Vec<Self> {
+        let values = values
+            .into_iter()
+            .map(|value| (value.clone(), RxSignalReset::new(value)));
+        RxObservableData::new_batch(rctx, values)
+            .into_iter()
+            .map(|reactor_entity| Self {
+                reactor_entity,
+                p: PhantomData,
+            })
+            .collect()
+    }
+
+    /// Create a signal whose values are passed through `map` before they are stored.
+    pub(crate) fn new_mapped<S>(
+        rctx: &mut ReactiveContext<S>,
+        initial_value: T,
+        map: impl Fn(T) -> T + Send + Sync + 'static,
+    ) -> Self {
+        let signal = Self::new(rctx, map(initial_value));
         rctx.reactive_state
-            .get::<RxObservableData<T>>(self.reactor_entity)
-            .unwrap()
-            .data()
+            .entity_mut(signal.reactor_entity)
+            .insert(RxWriteMap::<T> { map: Box::new(map) });
+        signal
+    }
+
+    /// Create a signal whose subscribers are indexed, see
+    /// [`ReactiveContext::new_signal_high_fanout`].
+    pub(crate) fn new_high_fanout<S>(rctx: &mut ReactiveContext<S>, initial_value: T) -> Self {
+        let signal = Self::new(rctx, initial_value);
+        rctx.reactive_state
+            .entity_mut(signal.reactor_entity)
+            .insert(RxSubscribers::indexed());
+        signal
+    }
+
+    /// See [`ReactiveContext::read`].
+    pub fn read<'r, S>(&self, rctx: &'r mut ReactiveContext<S>) -> &'r T {
+        rctx.read(*self)
+    }
+
+    /// See [`ReactiveContext::try_read`].
+    pub fn try_read<'r, S>(&self, rctx: &'r mut ReactiveContext<S>) -> Result<&'r T, RxError> {
+        rctx.try_read(*self)
+    }
+
+    /// Compute a result from the current value, borrowing the context only for the duration of
+    /// `f`. See [`ReactiveContext::projected_read`].
+    pub fn with<S, R>(&self, rctx: &ReactiveContext<S>, f: impl FnOnce(&T) -> R) -> R {
+        rctx.projected_read(*self, f)
+    }
+
+    /// Wait for the signal to hold a value satisfying `predicate`, see
+    /// [`ReactiveContext::wait_for`].
+    pub fn wait_for<S>(
+        &self,
+        rctx: &mut ReactiveContext<S>,
+        predicate: impl Fn(&T) -> bool + Send + Sync + 'static,
+    ) -> impl std::future::Future<Output = T> + Send + 'static
+    where
+        T: 'static,
+    {
+        rctx.wait_for(*self, predicate)
+    }
+
+    /// Run `effect_system` when the signal changes, see [`ReactiveContext::new_deferred_effect`].
+    /// This keeps the signal and its effect together, e.g. `volume.effect(rctx, apply_volume)`.
+    pub fn effect<S, M>(
+        &self,
+        rctx: &mut ReactiveContext<S>,
+        effect_system: impl IntoSystem<(), (), M>,
+    ) -> Effect {
+        Effect::new_deferred(rctx, *self, effect_system)
     }
 
     /// See [`ReactiveContext::send_signal`].
     #[inline]
-    pub fn send<S>(&self, rctx: &mut ReactiveContext<S>, value: T) {
-        RxObservableData::send_signal(&mut rctx.reactive_state, self.reactor_entity, value)
+    pub fn send<S>(&self, rctx: &mut ReactiveContext<S>, value: T) -> bool {
+        rctx.send_signal(*self, value)
+    }
+
+    /// See [`ReactiveContext::try_send_signal`].
+    pub fn try_send<S>(&self, rctx: &mut ReactiveContext<S>, value: T) -> Result<bool, RxError> {
+        rctx.try_send_signal(*self, value)
+    }
+
+    /// See [`ReactiveContext::send_signal_force`].
+    pub fn send_force<S>(&self, rctx: &mut ReactiveContext<S>, value: T) {
+        RxMemoNode::assert_write(&rctx.reactive_state, self.reactor_entity);
+        RxObservableData::send_signal_force(&mut rctx.reactive_state, self.reactor_entity, value)
+    }
+
+    /// Update the value of this signal without recomputing its subscribers, or running its
+    /// callbacks and effects. The value is still diffed, and `true` is returned if it changed.
+    ///
+    /// Memos that depend on this signal are stale until they are recomputed, e.g. with
+    /// [`ReactiveContext::recompute_subscribers`] once a batch of silent writes is done.
+    pub fn set_silent<S>(&self, rctx: &mut ReactiveContext<S>, value: T) -> bool {
+        RxMemoNode::assert_write(&rctx.reactive_state, self.reactor_entity);
+        RxObservableData::set_silent(&mut rctx.reactive_state, self.reactor_entity, value)
+    }
+
+    /// See [`ReactiveContext::write_coalesced`].
+    pub fn write_coalesced<S>(&self, rctx: &mut ReactiveContext<S>, value: T) {
+        let reactor_entity = self.reactor_entity;
+        RxMemoNode::assert_write(&rctx.reactive_state, reactor_entity);
+        rctx.reactive_state
+            .resource_mut::<RxCoalescedWrites>()
+            .insert(reactor_entity, move |world, stack| {
+                RxObservableData::update_value(world, stack, reactor_entity, value);
+            });
+    }
+
+    /// Restore the value this signal was created with, triggering subscribers if that differs from
+    /// the current value.
+    pub fn reset<S>(&self, rctx: &mut ReactiveContext<S>) {
+        RxSignalReset::run(&mut rctx.reactive_state, self.reactor_entity);
+    }
+
+    /// A handle to this signal that can be read and observed, but not sent, to hand to code that
+    /// should only react to the signal.
+    pub fn read_only(self) -> ReadSignal<T> {
+        ReadSignal { signal: self }
+    }
+
+    /// A handle to this signal that can be sent, but not read or observed, to hand to code that
+    /// should only update the signal.
+    pub fn write_only(self) -> WriteSignal<T> {
+        WriteSignal { signal: self }
+    }
+}
+
+/// A read only view of a [`Signal`], see [`Signal::read_only`]. It can be read, and used as the
+/// input of memos and effects, like any other observable.
+#[derive(Debug)]
+pub struct ReadSignal<T: Send + Sync + 'static> {
+    signal: Signal<T>,
+}
+
+impl<T: Send + Sync + PartialEq> Observable for ReadSignal<T> {
+    type DataType = T;
+    fn reactive_entity(&self) -> Entity {
+        self.signal.reactor_entity
+    }
+}
+
+impl<T: Send + Sync + PartialEq> Clone for ReadSignal<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Send + Sync + PartialEq> Copy for ReadSignal<T> {}
+
+impl<T: Send + Sync> PartialEq for ReadSignal<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.signal == other.signal
+    }
+}
+
+impl<T: Send + Sync> Eq for ReadSignal<T> {}
+
+impl<T: Clone + Send + Sync + PartialEq> ReadSignal<T> {
+    /// See [`ReactiveContext::read`].
+    pub fn read<'r, S>(&self, rctx: &'r mut ReactiveContext<S>) -> &'r T {
+        rctx.read(*self)
+    }
+
+    /// See [`ReactiveContext::try_read`].
+    pub fn try_read<'r, S>(&self, rctx: &'r mut ReactiveContext<S>) -> Result<&'r T, RxError> {
+        rctx.try_read(*self)
+    }
+
+    /// Compute a result from the current value, borrowing the context only for the duration of
+    /// `f`. See [`ReactiveContext::projected_read`].
+    pub fn with<S, R>(&self, rctx: &ReactiveContext<S>, f: impl FnOnce(&T) -> R) -> R {
+        rctx.projected_read(*self, f)
+    }
+}
+
+/// A write only handle to a [`Signal`], see [`Signal::write_only`]. It can't be read, or used as
+/// the input of memos and effects.
+#[derive(Debug)]
+pub struct WriteSignal<T: Send + Sync + 'static> {
+    signal: Signal<T>,
+}
+
+impl<T: Send + Sync + PartialEq> Clone for WriteSignal<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Send + Sync + PartialEq> Copy for WriteSignal<T> {}
+
+impl<T: Send + Sync> PartialEq for WriteSignal<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.signal == other.signal
+    }
+}
+
+impl<T: Send + Sync> Eq for WriteSignal<T> {}
+
+impl<T: Clone + Send + Sync + PartialEq> WriteSignal<T> {
+    /// See [`Signal::send`].
+    pub fn send<S>(&self, rctx: &mut ReactiveContext<S>, value: T) -> bool {
+        self.signal.send(rctx, value)
+    }
+
+    /// See [`Signal::write_coalesced`].
+    pub fn write_coalesced<S>(&self, rctx: &mut ReactiveContext<S>, value: T) {
+        self.signal.write_coalesced(rctx, value);
+    }
+
+    /// See [`Signal::reset`].
+    pub fn reset<S>(&self, rctx: &mut ReactiveContext<S>) {
+        self.signal.reset(rctx);
+    }
+}
+
+/// A signal that carries no data, and notifies its subscribers every time it is sent, like an event
+/// with no payload. Use it to trigger recomputes or effects, e.g. as a "tick" or "invalidate".
+///
+/// Its value is the number of times it has been sent, which always changes, so it is never
+/// suppressed by diffing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component)]
+pub struct Pulse {
+    reactor_entity: Entity,
+}
+
+impl Observable for Pulse {
+    type DataType = u64;
+    fn reactive_entity(&self) -> Entity {
+        self.reactor_entity
+    }
+}
+
+impl Pulse {
+    pub(crate) fn new<S>(rctx: &mut ReactiveContext<S>) -> Self {
+        Self {
+            reactor_entity: RxObservableData::new(rctx, 0_u64),
+        }
+    }
+
+    /// Notify every subscriber of this pulse.
+    pub fn send<S>(&self, rctx: &mut ReactiveContext<S>) {
+        Self::send_in(&mut rctx.reactive_state, self.reactor_entity);
+    }
+
+    /// Send the pulse on `entity`. The count is incremented when the write is applied, so pulses
+    /// sent while a traversal is in progress are each counted, instead of all writing the same
+    /// count.
+    pub(crate) fn send_in(rx_world: &mut World, entity: Entity) {
+        traversal::send(rx_world, move |world, stack| {
+            let Ok(count) = RxObservableData::<u64>::read(world, entity) else {
+                return;
+            };
+            let count = count.wrapping_add(1);
+            RxObservableData::update_value(world, stack, entity, count);
+        });
+    }
+}
+
+/// A function that transforms values written to a signal.
+type WriteMapFn<T> = dyn Fn(T) -> T + Send + Sync;
+
+/// Transforms every value written to a signal before it is diffed and stored, to validate or
+/// normalize it.
+#[derive(Component)]
+pub(crate) struct RxWriteMap<T> {
+    map: Box<WriteMapFn<T>>,
+}
+
+impl<T: Send + Sync + 'static> RxWriteMap<T> {
+    /// Returns the transformed `value` to store in the `signal` entity.
+    pub(crate) fn apply(rx_world: &World, signal: Entity, value: T) -> T {
+        match rx_world.get::<Self>(signal) {
+            Some(write_map) => (write_map.map)(value),
+            None => value,
+        }
+    }
+}
+
+/// A type erased function that sends a stored value to the signal on the given entity.
+type ResetFn = dyn Fn(&mut World, Entity) + Send + Sync;
+
+/// Stores the initial value of a signal, so it can be [`Signal::reset`]. The value is captured in a
+/// type erased function, so every signal can be reset without knowing its type.
+#[derive(Component)]
+pub(crate) struct RxSignalReset {
+    reset: Box<ResetFn>,
+}
+
+impl RxSignalReset {
+    fn new<T: Clone + Send + Sync + PartialEq + 'static>(initial_value: T) -> Self {
+        let reset = move |world: &mut World, entity: Entity| {
+            RxObservableData::send_signal_unmapped(world, entity, initial_value.clone());
+        };
+        Self {
+            reset: Box::new(reset),
+        }
+    }
+
+    pub(crate) fn run(world: &mut World, signal: Entity) {
+        let Some(reset) = world
+            .get_entity_mut(signal)
+            .and_then(|mut entity| entity.take::<Self>())
+        else {
+            return;
+        };
+        (reset.reset)(world, signal);
+        world.entity_mut(signal).insert(reset);
+    }
+}
+
+/// Signal writes that are deferred until the next flush, keeping only the latest value written to
+/// each signal.
+#[derive(Resource, Default)]
+pub(crate) struct RxCoalescedWrites {
+    /// Signals in the order they were first written to this frame.
+    order: Vec<Entity>,
+    writes: HashMap<Entity, Box<PendingWrite>>,
+}
+
+impl RxCoalescedWrites {
+    fn insert(
+        &mut self,
+        signal: Entity,
+        write: impl FnOnce(&mut World, &mut Vec<Entity>) + Send + Sync + 'static,
+    ) {
+        if self.writes.insert(signal, Box::new(write)).is_none() {
+            self.order.push(signal);
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.order.clear();
+        self.writes.clear();
+    }
+
+    /// Apply every pending write, and propagate the changes in a single traversal.
+    pub(crate) fn apply(rx_world: &mut World) {
+        let mut coalesced = rx_world.resource_mut::<Self>();
+        if coalesced.order.is_empty() {
+            return;
+        }
+        let order = std::mem::take(&mut coalesced.order);
+        let mut writes = std::mem::take(&mut coalesced.writes);
+        traversal::send(rx_world, move |world, stack| {
+            for signal in order {
+                if world.get_entity(signal).is_none() {
+                    continue; // Disposed before the flush.
+                }
+                if let Some(write) = writes.remove(&signal) {
+                    write(world, stack);
+                }
+            }
+        });
     }
 }
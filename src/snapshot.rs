@@ -0,0 +1,113 @@
+use std::any::TypeId;
+
+use bevy_ecs::prelude::*;
+use bevy_utils::HashMap;
+
+use crate::{memo::RxMemoNode, observable::RxObservableData};
+
+/// The serialized values of every registered signal, captured with
+/// [`crate::ReactiveContext::snapshot`] and applied with [`crate::ReactiveContext::restore`].
+///
+/// Memos are not included, they are recomputed from the restored signals.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Snapshot {
+    values: HashMap<Entity, Vec<u8>>,
+}
+
+impl Snapshot {
+    /// The serialized value of the signal on this reactive entity, if it was captured.
+    pub fn get(&self, entity: Entity) -> Option<&[u8]> {
+        self.values.get(&entity).map(Vec::as_slice)
+    }
+
+    /// Iterate over the reactive entity and serialized value of every captured signal.
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, &[u8])> {
+        self.values
+            .iter()
+            .map(|(entity, value)| (*entity, value.as_slice()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub(crate) fn capture(rx_world: &World) -> Self {
+        let mut snapshot = Self::default();
+        if let Some(serializers) = rx_world.get_resource::<RxSerializers>() {
+            for serializer in serializers.types.values() {
+                (serializer.capture)(rx_world, &mut snapshot.values);
+            }
+        }
+        snapshot
+    }
+
+    pub(crate) fn restore(&self, rx_world: &mut World) {
+        if !rx_world.contains_resource::<RxSerializers>() {
+            return;
+        }
+        rx_world.resource_scope::<RxSerializers, _>(|rx_world, serializers| {
+            for serializer in serializers.types.values() {
+                (serializer.restore)(rx_world, &self.values);
+            }
+        });
+    }
+}
+
+type CaptureFn = dyn Fn(&World, &mut HashMap<Entity, Vec<u8>>) + Send + Sync;
+type RestoreFn = dyn Fn(&mut World, &HashMap<Entity, Vec<u8>>) + Send + Sync;
+
+/// Type erased functions used to capture and restore signals of a single type.
+struct Serializer {
+    capture: Box<CaptureFn>,
+    restore: Box<RestoreFn>,
+}
+
+/// The registry of types that are included in a [`Snapshot`].
+#[derive(Resource, Default)]
+pub(crate) struct RxSerializers {
+    types: HashMap<TypeId, Serializer>,
+}
+
+impl RxSerializers {
+    pub(crate) fn register<T: Clone + PartialEq + Send + Sync + 'static>(
+        &mut self,
+        serialize: impl Fn(&T) -> Vec<u8> + Send + Sync + 'static,
+        deserialize: impl Fn(&[u8]) -> Option<T> + Send + Sync + 'static,
+    ) {
+        let capture = move |rx_world: &World, values: &mut HashMap<Entity, Vec<u8>>| {
+            let signals = rx_world
+                .iter_entities()
+                .filter(|entity| !entity.contains::<RxMemoNode>());
+            for entity in signals {
+                if let Some(observable) = entity.get::<RxObservableData<T>>() {
+                    values.insert(entity.id(), serialize(observable.data()));
+                }
+            }
+        };
+        let restore = move |rx_world: &mut World, values: &HashMap<Entity, Vec<u8>>| {
+            let restored: Vec<(Entity, T)> = values
+                .iter()
+                .filter(|(entity, _)| {
+                    rx_world.get::<RxObservableData<T>>(**entity).is_some()
+                        && rx_world.get::<RxMemoNode>(**entity).is_none()
+                })
+                .filter_map(|(entity, bytes)| Some((*entity, deserialize(bytes)?)))
+                .collect();
+            // Sending the values as signals recomputes every memo that depends on them.
+            for (entity, value) in restored {
+                RxObservableData::send_signal_unmapped(rx_world, entity, value);
+            }
+        };
+        self.types.insert(
+            TypeId::of::<T>(),
+            Serializer {
+                capture: Box::new(capture),
+                restore: Box::new(restore),
+            },
+        );
+    }
+}
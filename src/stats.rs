@@ -0,0 +1,319 @@
+use std::{collections::VecDeque, fmt};
+
+use bevy_ecs::prelude::*;
+use bevy_utils::{HashMap, HashSet};
+
+use crate::{
+    callback::RxCallbacks,
+    effect::{RxDeferredEffect, RxEffectWatchers},
+    memo::{MemoIdentity, RxMemo},
+    observable::{RxDataType, RxSubscribers},
+    system_memo::RxSystemMemo,
+};
+
+/// Counters describing the size and shape of the reactive graph, see
+/// [`crate::ReactiveContext::stats`].
+///
+/// A subscriber fan-out that keeps growing while the graph's structure stays the same usually means
+/// subscribers are being duplicated.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ReactiveStats {
+    /// The number of reactive nodes (signals and memos) in the graph.
+    pub nodes: usize,
+    /// The total number of subscriber edges between nodes.
+    pub subscriber_edges: usize,
+    /// The largest number of subscribers held by a single node.
+    pub max_fan_out: usize,
+}
+
+impl ReactiveStats {
+    /// Walk every node of the reactive world. This is linear in the number of nodes.
+    pub(crate) fn collect(rx_world: &World) -> Self {
+        rx_world
+            .iter_entities()
+            .filter_map(|entity| entity.get::<RxSubscribers>())
+            .fold(Self::default(), |stats, subscribers| {
+                let fan_out = subscribers.subscribers.len();
+                Self {
+                    nodes: stats.nodes + 1,
+                    subscriber_edges: stats.subscriber_edges + fan_out,
+                    max_fan_out: stats.max_fan_out.max(fan_out),
+                }
+            })
+    }
+}
+
+/// Running totals of the work done while propagating changes, see
+/// [`crate::ReactiveContext::propagation_counters`].
+///
+/// A high ratio of cutoffs to recomputes means diffing is doing its job, stopping changes from
+/// propagating past nodes whose values didn't change.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PropagationCounters {
+    /// The number of times a memo was recomputed.
+    pub recomputes: u64,
+    /// The number of writes and recomputes that produced an unchanged value, stopping propagation
+    /// at that node.
+    pub cutoffs: u64,
+}
+
+/// Memos that nothing in the graph depends on: they have no subscribers and no effect. These are
+/// either read directly through a handle, or were orphaned by mistake.
+pub(crate) fn dead_nodes(rx_world: &World) -> Vec<Entity> {
+    rx_world
+        .iter_entities()
+        .filter(|entity| entity.contains::<RxMemo>() || entity.contains::<RxSystemMemo>())
+        .filter(|entity| !entity.contains::<RxDeferredEffect>())
+        .filter(|entity| {
+            entity
+                .get::<RxSubscribers>()
+                .map_or(true, |subscribers| subscribers.subscribers.is_empty())
+        })
+        .map(|entity| entity.id())
+        .collect()
+}
+
+/// Returns `true` if anything reacts to changes of the `node`: a subscribed memo, a callback, or an
+/// effect.
+pub(crate) fn is_observed<T: Send + Sync + 'static>(rx_world: &World, node: Entity) -> bool {
+    let Some(entity) = rx_world.get_entity(node) else {
+        return false;
+    };
+    entity
+        .get::<RxSubscribers>()
+        .is_some_and(|subscribers| !subscribers.subscribers.is_empty())
+        || entity.contains::<RxCallbacks<T>>()
+        || has_effect(entity)
+}
+
+/// Returns `true` if an effect runs when the `node` changes, either on the node itself, or on any
+/// node downstream of it.
+pub(crate) fn is_observed_by_effect(rx_world: &World, node: Entity) -> bool {
+    let mut visited = HashSet::default();
+    let mut stack = vec![node];
+    while let Some(next) = stack.pop() {
+        let Some(entity) = rx_world.get_entity(next) else {
+            continue;
+        };
+        if has_effect(entity) {
+            return true;
+        }
+        if let Some(subscribers) = entity.get::<RxSubscribers>() {
+            let unvisited = subscribers
+                .subscribers
+                .iter()
+                .filter(|s| visited.insert(**s));
+            stack.extend(unvisited);
+        }
+    }
+    false
+}
+
+/// Every node downstream of `node`, nearest first: its subscribers, their subscribers, and so on.
+pub(crate) fn transitive_subscribers(rx_world: &World, node: Entity) -> Vec<Entity> {
+    walk(node, |next| {
+        rx_world
+            .get::<RxSubscribers>(next)
+            .map_or(Vec::new(), |subscribers| subscribers.subscribers.clone())
+    })
+}
+
+/// Every node upstream of `node`, nearest first: the nodes it is subscribed to, or that it lists as
+/// memo inputs, and their own dependencies in turn. This is linear in the size of the graph, as the
+/// subscriber edges are only stored in the downstream direction.
+pub(crate) fn transitive_dependencies(rx_world: &World, node: Entity) -> Vec<Entity> {
+    let mut sources: HashMap<Entity, Vec<Entity>> = HashMap::default();
+    for entity in rx_world.iter_entities() {
+        if let Some(subscribers) = entity.get::<RxSubscribers>() {
+            for &subscriber in &subscribers.subscribers {
+                sources.entry(subscriber).or_default().push(entity.id());
+            }
+        }
+        if let Some(memo) = entity.get::<RxMemo>() {
+            sources.entry(entity.id()).or_default().extend(&memo.inputs);
+        }
+    }
+    walk(node, |next| sources.get(&next).cloned().unwrap_or_default())
+}
+
+/// Breadth first search from `start` along the edges returned by `next`, returning every node
+/// reached except `start`. Nodes are visited once, so this terminates even if the graph has cycles.
+fn walk(start: Entity, next: impl Fn(Entity) -> Vec<Entity>) -> Vec<Entity> {
+    let mut visited = HashSet::from([start]);
+    let mut reached = Vec::new();
+    let mut queue = VecDeque::from([start]);
+    while let Some(node) = queue.pop_front() {
+        for neighbor in next(node) {
+            if visited.insert(neighbor) {
+                reached.push(neighbor);
+                queue.push_back(neighbor);
+            }
+        }
+    }
+    reached
+}
+
+fn has_effect(entity: EntityRef) -> bool {
+    entity.contains::<RxDeferredEffect>() || entity.contains::<RxEffectWatchers>()
+}
+
+/// A broken invariant of the reactive graph, found by
+/// [`crate::ReactiveContext::debug_validate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GraphViolation {
+    /// A node lists a subscriber that no longer exists.
+    DanglingSubscriber { node: Entity, subscriber: Entity },
+    /// A node lists the same subscriber more than once, so it would be recomputed more than once.
+    DuplicateSubscriber { node: Entity, subscriber: Entity },
+    /// A memo depends on an input that no longer exists.
+    MissingInput { memo: Entity, input: Entity },
+    /// A memo depends on an input that has never had a value.
+    InputWithoutData { memo: Entity, input: Entity },
+    /// The nodes subscribe to each other in a loop, in this order.
+    Cycle(Vec<Entity>),
+}
+
+impl fmt::Display for GraphViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GraphViolation::DanglingSubscriber { node, subscriber } => write!(
+                f,
+                "node {node:?} has subscriber {subscriber:?}, which no longer exists"
+            ),
+            GraphViolation::DuplicateSubscriber { node, subscriber } => write!(
+                f,
+                "node {node:?} lists subscriber {subscriber:?} more than once"
+            ),
+            GraphViolation::MissingInput { memo, input } => {
+                write!(
+                    f,
+                    "memo {memo:?} depends on {input:?}, which no longer exists"
+                )
+            }
+            GraphViolation::InputWithoutData { memo, input } => {
+                write!(f, "memo {memo:?} depends on {input:?}, which has no value")
+            }
+            GraphViolation::Cycle(nodes) => write!(f, "nodes {nodes:?} subscribe to each other"),
+        }
+    }
+}
+
+/// Check every node of the reactive world for broken invariants. This is linear in the size of the
+/// graph.
+pub(crate) fn validate(rx_world: &World) -> Vec<GraphViolation> {
+    let mut violations = Vec::new();
+    let mut edges: HashMap<Entity, &[Entity]> = HashMap::default();
+    for entity in rx_world.iter_entities() {
+        let node = entity.id();
+        if let Some(subscribers) = entity.get::<RxSubscribers>() {
+            let mut seen = HashSet::default();
+            for &subscriber in &subscribers.subscribers {
+                if rx_world.get_entity(subscriber).is_none() {
+                    violations.push(GraphViolation::DanglingSubscriber { node, subscriber });
+                } else if !seen.insert(subscriber) {
+                    violations.push(GraphViolation::DuplicateSubscriber { node, subscriber });
+                }
+            }
+            edges.insert(node, &subscribers.subscribers);
+        }
+        for &input in entity.get::<RxMemo>().map_or(&[][..], |memo| &memo.inputs) {
+            match rx_world.get_entity(input) {
+                None => violations.push(GraphViolation::MissingInput { memo: node, input }),
+                Some(input_node) if !input_node.contains::<RxDataType>() => {
+                    violations.push(GraphViolation::InputWithoutData { memo: node, input });
+                }
+                Some(_) => {}
+            }
+        }
+    }
+    violations.extend(find_cycles(&edges).into_iter().map(GraphViolation::Cycle));
+    violations
+}
+
+/// Panic if the graph has a violation, ignoring the dangling subscribers and missing inputs left
+/// behind by disposed nodes, see [`crate::ReactiveContext::set_strict`].
+pub(crate) fn check_strict(rx_world: &World) {
+    let violations: Vec<_> = validate(rx_world)
+        .into_iter()
+        .filter(|violation| {
+            !matches!(
+                violation,
+                GraphViolation::DanglingSubscriber { .. } | GraphViolation::MissingInput { .. }
+            )
+        })
+        .map(|violation| violation.to_string())
+        .collect();
+    if !violations.is_empty() {
+        panic!(
+            "Strict mode: the reactive graph is broken: {}",
+            violations.join(", ")
+        );
+    }
+}
+
+/// Find loops in the subscriber `edges` with a depth first search, returning the nodes of each.
+fn find_cycles(edges: &HashMap<Entity, &[Entity]>) -> Vec<Vec<Entity>> {
+    #[derive(Clone, Copy, PartialEq)]
+    enum Visit {
+        InProgress,
+        Done,
+    }
+    let mut visits: HashMap<Entity, Visit> = HashMap::default();
+    let mut cycles = Vec::new();
+    for &root in edges.keys() {
+        if visits.contains_key(&root) {
+            continue;
+        }
+        // The path from the root, with the index of the next subscriber to visit from each node.
+        let mut path = vec![(root, 0)];
+        visits.insert(root, Visit::InProgress);
+        while let Some((node, next)) = path.last_mut() {
+            let subscribers = edges.get(node).copied().unwrap_or_default();
+            let Some(&subscriber) = subscribers.get(*next) else {
+                visits.insert(*node, Visit::Done);
+                path.pop();
+                continue;
+            };
+            *next += 1;
+            match visits.get(&subscriber) {
+                Some(Visit::InProgress) => {
+                    let start = path.iter().position(|(n, _)| *n == subscriber).unwrap();
+                    cycles.push(path[start..].iter().map(|(n, _)| *n).collect());
+                }
+                Some(Visit::Done) => {}
+                None => {
+                    visits.insert(subscriber, Visit::InProgress);
+                    path.push((subscriber, 0));
+                }
+            }
+        }
+    }
+    cycles
+}
+
+/// Groups of memos with the same calculation and the same inputs, see
+/// [`crate::ReactiveContext::find_duplicate_memos`].
+pub(crate) fn duplicate_memos(rx_world: &World) -> Vec<Vec<Entity>> {
+    let mut groups: HashMap<(&MemoIdentity, &[Entity]), Vec<Entity>> = HashMap::default();
+    for entity in rx_world.iter_entities() {
+        let Some(memo) = entity.get::<RxMemo>() else {
+            continue;
+        };
+        if let Some(identity) = memo.identity.as_ref() {
+            groups
+                .entry((identity, memo.inputs.as_slice()))
+                .or_default()
+                .push(entity.id());
+        }
+    }
+    let mut duplicates: Vec<_> = groups
+        .into_values()
+        .filter(|memos| memos.len() > 1)
+        .map(|mut memos| {
+            memos.sort();
+            memos
+        })
+        .collect();
+    duplicates.sort();
+    duplicates
+}
@@ -0,0 +1,112 @@
+use std::marker::PhantomData;
+
+use bevy_ecs::{prelude::*, system::BoxedSystem};
+
+use crate::{
+    memo::{Memo, MemoQuery, RxMemo, RxMemoNode},
+    observable::{RxObservableData, RxSubscribers},
+    ReactiveContext,
+};
+
+/// Runs a system memo's system against the main world, and sends its output to the memo.
+type SystemMemoFn = dyn FnMut(&mut World, &mut World, Entity) + Send + Sync;
+
+/// A memo computed by a system run against the main world, stored alongside its
+/// [`RxObservableData`]. The main world is only available when effects are flushed, so the system
+/// is run then, if one of its inputs changed, or every flush if it is polled.
+#[derive(Component)]
+pub(crate) struct RxSystemMemo {
+    function: Box<SystemMemoFn>,
+    dirty: bool,
+    polled: bool,
+}
+
+impl RxSystemMemo {
+    fn spawn<S, T: Clone + PartialEq + Send + Sync + 'static, M>(
+        rctx: &mut ReactiveContext<S>,
+        polled: bool,
+        system: impl IntoSystem<(), T, M>,
+    ) -> Entity {
+        let mut system: BoxedSystem<(), T> = Box::new(IntoSystem::into_system(system));
+        let mut initialized = false;
+        let function = move |main_world: &mut World, rx_world: &mut World, entity: Entity| {
+            if !std::mem::replace(&mut initialized, true) {
+                system.initialize(main_world);
+            }
+            let value = system.run((), main_world);
+            system.apply_deferred(main_world);
+            RxObservableData::send_signal(rx_world, entity, value);
+        };
+        rctx.reactive_state
+            .spawn((
+                RxSubscribers::default(),
+                RxMemoNode,
+                Self {
+                    function: Box::new(function),
+                    dirty: true,
+                    polled,
+                },
+            ))
+            .id()
+    }
+
+    /// Run the system of every system memo that needs to be recomputed.
+    pub(crate) fn update_all(main_world: &mut World, rx_world: &mut World) {
+        let entities: Vec<Entity> = rx_world
+            .query::<(Entity, &Self)>()
+            .iter(rx_world)
+            .filter(|(_, memo)| memo.dirty || memo.polled)
+            .map(|(entity, _)| entity)
+            .collect();
+        for entity in entities {
+            let Some(mut memo) = rx_world.entity_mut(entity).take::<Self>() else {
+                continue;
+            };
+            memo.dirty = false;
+            (memo.function)(main_world, rx_world, entity);
+            rx_world.entity_mut(entity).insert(memo);
+        }
+    }
+}
+
+impl<T: Clone + PartialEq + Send + Sync + 'static> Memo<T> {
+    /// Create a memo computed by running `system` against the main world. The system is run the
+    /// next time effects are flushed after any of the `input_deps` change.
+    ///
+    /// The memo has no value until effects are flushed for the first time.
+    pub fn new_system<S, D: MemoQuery<()>, M>(
+        rctx: &mut ReactiveContext<S>,
+        input_deps: D,
+        system: impl IntoSystem<(), T, M>,
+    ) -> Self {
+        let entity = RxSystemMemo::spawn(rctx, false, system);
+        // The inputs are only read to subscribe to them, and mark the system as dirty.
+        let mut invalidate = RxMemo::from_fn(move |world, _| {
+            D::read_and_derive(world, entity, |_| (), input_deps);
+            if let Some(mut memo) = world.get_mut::<RxSystemMemo>(entity) {
+                memo.dirty = true;
+            }
+        });
+        invalidate.execute(&mut rctx.reactive_state, &mut Vec::new());
+        rctx.reactive_state.entity_mut(entity).insert(invalidate);
+        Self {
+            reactor_entity: entity,
+            p: PhantomData,
+        }
+    }
+
+    /// Create a memo computed by running `system` against the main world every time effects are
+    /// flushed, i.e. once per frame when using the plugin. The value is still diffed, so
+    /// subscribers are only recomputed when it changes.
+    ///
+    /// The memo has no value until effects are flushed for the first time.
+    pub fn new_polled_system<S, M>(
+        rctx: &mut ReactiveContext<S>,
+        system: impl IntoSystem<(), T, M>,
+    ) -> Self {
+        Self {
+            reactor_entity: RxSystemMemo::spawn(rctx, true, system),
+            p: PhantomData,
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use std::time::Duration;
+
+use bevy_ecs::prelude::*;
+
+use crate::{signal::Signal, ReactiveContext};
+
+/// Built-in signals driven by the plugin once per frame, created when first requested.
+#[derive(Resource, Default)]
+pub(crate) struct RxTimeSignals {
+    pub(crate) elapsed: Option<Signal<Duration>>,
+    pub(crate) frame: Option<Signal<u64>>,
+}
+
+impl RxTimeSignals {
+    pub(crate) fn elapsed<S>(rctx: &mut ReactiveContext<S>) -> Signal<Duration> {
+        if let Some(signal) = Self::get(rctx).elapsed {
+            return signal;
+        }
+        let signal = rctx.new_signal(Duration::ZERO);
+        rctx.reactive_state.resource_mut::<Self>().elapsed = Some(signal);
+        signal
+    }
+
+    pub(crate) fn frame<S>(rctx: &mut ReactiveContext<S>) -> Signal<u64> {
+        if let Some(signal) = Self::get(rctx).frame {
+            return signal;
+        }
+        let signal = rctx.new_signal(0);
+        rctx.reactive_state.resource_mut::<Self>().frame = Some(signal);
+        signal
+    }
+
+    fn get<S>(rctx: &mut ReactiveContext<S>) -> Mut<'_, Self> {
+        rctx.reactive_state
+            .get_resource_or_insert_with(Self::default)
+    }
+
+    /// Send the elapsed time and increment the frame counter, if their signals were created.
+    #[cfg(feature = "bevy_app")]
+    pub(crate) fn update(
+        time: Option<Res<bevy_time::Time>>,
+        mut frame_count: Local<u64>,
+        mut rctx: ResMut<ReactiveContext<World>>,
+    ) {
+        *frame_count += 1;
+        let Some(signals) = rctx.reactive_state.get_resource::<Self>() else {
+            return;
+        };
+        let (elapsed, frame) = (signals.elapsed, signals.frame);
+        if let (Some(signal), Some(time)) = (elapsed, time) {
+            rctx.send_signal(signal, time.elapsed());
+        }
+        if let Some(signal) = frame {
+            rctx.send_signal(signal, *frame_count);
+        }
+    }
+}
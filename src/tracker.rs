@@ -0,0 +1,105 @@
+//! Counting recomputes and effect runs per node, to assert the performance properties of a graph in
+//! tests, and recording what triggered each recompute. Only available with the `tracking` feature,
+//! so production builds pay nothing for it.
+
+use bevy_ecs::prelude::*;
+use bevy_utils::HashMap;
+
+use crate::{effect::Effect, observable::AnyObservable, ReactiveContext};
+
+/// Counts how many times each memo was recomputed, and each effect ran, since it was installed or
+/// last reset. See [`ReactiveContext::track_recomputes`].
+#[derive(Resource, Debug, Default)]
+pub struct RecomputeTracker {
+    memos: HashMap<Entity, usize>,
+    effects: HashMap<Entity, usize>,
+    /// The node that most recently scheduled each memo that hasn't been recomputed yet.
+    scheduled: HashMap<Entity, Entity>,
+    /// The node that scheduled the last recompute of each memo.
+    triggers: HashMap<Entity, Entity>,
+}
+
+impl RecomputeTracker {
+    /// The number of times the `memo` was recomputed.
+    pub fn count(&self, memo: impl AnyObservable) -> usize {
+        self.memos
+            .get(&memo.reactive_entity())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// The number of times the `effect` ran.
+    pub fn effect_count(&self, effect: Effect) -> usize {
+        self.effects
+            .get(&effect.reactor_entity)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// The total number of memo recomputes across the whole graph.
+    pub fn total(&self) -> usize {
+        self.memos.values().sum()
+    }
+
+    /// The entity of the node whose change scheduled the last recompute of the `memo`, or `None`
+    /// if it hasn't been recomputed since the tracker was installed or reset. This is the input
+    /// that changed, or the memo itself if it was recomputed with [`crate::Memo::force_recompute`].
+    /// The node may have been disposed since, if its disposal is what scheduled the memo.
+    pub fn last_trigger(&self, memo: impl AnyObservable) -> Option<Entity> {
+        self.triggers.get(&memo.reactive_entity()).copied()
+    }
+
+    /// Reset every count to zero, e.g. before the operation under test.
+    pub fn reset(&mut self) {
+        self.memos.clear();
+        self.effects.clear();
+        self.scheduled.clear();
+        self.triggers.clear();
+    }
+
+    pub(crate) fn memo_recomputed(rx_world: &mut World, memo: Entity) {
+        if let Some(mut tracker) = rx_world.get_resource_mut::<Self>() {
+            *tracker.memos.entry(memo).or_default() += 1;
+            if let Some(trigger) = tracker.scheduled.remove(&memo) {
+                tracker.triggers.insert(memo, trigger);
+            }
+        }
+    }
+
+    /// Record that a change of the `trigger` node moved its `subscribers` onto the traversal stack.
+    pub(crate) fn scheduled(rx_world: &mut World, trigger: Entity, subscribers: &[Entity]) {
+        if let Some(mut tracker) = rx_world.get_resource_mut::<Self>() {
+            for subscriber in subscribers {
+                tracker.scheduled.insert(*subscriber, trigger);
+            }
+        }
+    }
+
+    pub(crate) fn effect_ran(rx_world: &mut World, effect: Entity) {
+        if let Some(mut tracker) = rx_world.get_resource_mut::<Self>() {
+            *tracker.effects.entry(effect).or_default() += 1;
+        }
+    }
+}
+
+impl<S> ReactiveContext<S> {
+    /// Start counting recomputes and effect runs, returning the tracker so it can be reset before
+    /// the operation under test. Installing it again returns the existing tracker.
+    pub fn track_recomputes(&mut self) -> Mut<'_, RecomputeTracker> {
+        self.reactive_state
+            .get_resource_or_insert_with(RecomputeTracker::default)
+    }
+
+    /// The counts gathered since [`ReactiveContext::track_recomputes`] was called, or `None` if it
+    /// hasn't been.
+    pub fn recompute_tracker(&self) -> Option<&RecomputeTracker> {
+        self.reactive_state.get_resource::<RecomputeTracker>()
+    }
+
+    /// The node whose change scheduled the last recompute of the `memo`, to debug why it ran. See
+    /// [`RecomputeTracker::last_trigger`]. Only recomputes made since
+    /// [`ReactiveContext::track_recomputes`] was called are recorded.
+    pub fn last_trigger(&self, memo: impl AnyObservable) -> Option<Entity> {
+        self.recompute_tracker()?.last_trigger(memo)
+    }
+}
@@ -0,0 +1,373 @@
+//! Walking the reactive graph when a signal is sent.
+//!
+//! By default, traversal is single threaded. Recomputing a memo subscribes it to its inputs, which
+//! mutates those inputs' subscriber lists, and some memos (like
+//! [`crate::ReactiveContext::flatten`]) only discover their inputs while running, so a calculation
+//! normally has exclusive access to the reactive world. [`Traversal::Parallel`] opts into
+//! splitting the work of memos created from a [`crate::MemoQuery`]: their inputs are subscribed to
+//! one at a time, the calculations of a wave run at the same time against a shared world, and their
+//! values are then updated one at a time, in order.
+//!
+//! Signals sent while a traversal is already in progress, e.g. by a callback or a calculation, do
+//! not start a nested traversal. They are queued, and applied in the order they were sent once the
+//! current traversal has settled.
+
+use std::collections::VecDeque;
+
+use bevy_ecs::prelude::*;
+use bevy_tasks::{ComputeTaskPool, TaskPool};
+use bevy_utils::{tracing::warn, HashSet};
+
+use crate::{
+    lifecycle::RxLifecycle,
+    memo::{MemoScheduling, RxMemo, RxScheduledMemos, SharedDeriveFn},
+    observable::RxSubscribers,
+    stats::PropagationCounters,
+};
+
+/// The order in which subscribers are recomputed when a signal is sent.
+///
+/// Regardless of the order, every affected memo is recomputed before
+/// [`crate::ReactiveContext::send_signal`] returns, and effects are queued in the order their
+/// observables changed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Traversal {
+    /// Recompute subscribers using a stack. The most recently queued subscriber is recomputed
+    /// first, so a branch of the graph is followed to its end before its siblings are visited. This
+    /// is the fastest mode.
+    #[default]
+    DepthFirst,
+    /// Recompute subscribers using a queue. Nodes closer to the signal are recomputed first, and
+    /// the subscribers of a node are recomputed in the order they subscribed. Use this when effects
+    /// or memos have side effects whose order needs to be predictable.
+    BreadthFirst,
+    /// Recompute subscribers in waves, like [`Traversal::BreadthFirst`]. The eager memos of a wave
+    /// created from a [`crate::MemoQuery`], e.g. with [`crate::ReactiveContext::new_memo`], are
+    /// computed at the same time on bevy's `ComputeTaskPool`, reading the values left by the
+    /// previous wave, then updated in the order they were queued. Other subscribers are recomputed
+    /// one at a time, before the rest of their wave. A subscriber queued more than once in a wave
+    /// is recomputed once. Use this when waves contain many expensive memos.
+    ///
+    /// Only memos created, or given a new calculation, while this mode is set can be computed in
+    /// parallel, so set it before building the graph. Other modes skip that setup.
+    Parallel,
+}
+
+/// A signal write that was sent while a traversal was in progress. It updates the signal's value
+/// and pushes the invalidated subscribers onto the stack.
+pub(crate) type PendingWrite = dyn FnOnce(&mut World, &mut Vec<Entity>) + Send + Sync;
+
+/// Settings and state for walking the reactive graph.
+#[derive(Resource, Default)]
+pub(crate) struct RxTraversal {
+    pub(crate) mode: Traversal,
+    /// Order the subscribers invalidated by each change by creation, see
+    /// [`crate::ReactiveContext::set_deterministic`].
+    pub(crate) deterministic: bool,
+    /// The maximum number of subscribers recomputed by a single signal, see
+    /// [`crate::ReactiveContext::set_max_propagation_steps`].
+    pub(crate) max_steps: Option<usize>,
+    /// Subscribers left over when propagation was stopped by `max_steps`. They have already been
+    /// removed from their inputs' subscribers, so they are recomputed by the next traversal.
+    stalled: Vec<Entity>,
+    /// The stack of subscribers to recompute, kept between traversals so its allocation is reused.
+    scratch: Vec<Entity>,
+    /// Set while the graph is being traversed, to detect re-entrant signals.
+    pub(crate) in_progress: bool,
+    /// Re-entrant signal writes, applied once the current traversal has settled.
+    pending: VecDeque<Box<PendingWrite>>,
+    pub(crate) counters: PropagationCounters,
+    /// Incremented every time the value of an observable changes, see
+    /// [`crate::ReactiveContext::change_tick`].
+    pub(crate) change_tick: u64,
+    /// Incremented every time a traversal starts, see [`crate::ReactiveContext::generation`].
+    pub(crate) generation: u64,
+    /// Check the graph after every change, see [`crate::ReactiveContext::set_strict`].
+    pub(crate) strict: bool,
+    /// Set once any observable has lifecycle callbacks, so traversals only check for disconnected
+    /// observables when there are any, see [`crate::ReactiveContext::on_disconnect`].
+    pub(crate) lifecycles: bool,
+}
+
+impl RxTraversal {
+    /// Discard the subscribers left over by a stalled traversal, and any queued writes.
+    pub(crate) fn clear(&mut self) {
+        self.stalled.clear();
+        self.pending.clear();
+    }
+
+    /// Returns `true` if memos created now also need the shared form of their calculation, see
+    /// [`Traversal::Parallel`].
+    pub(crate) fn is_parallel(rx_world: &World) -> bool {
+        rx_world.resource::<Self>().mode == Traversal::Parallel
+    }
+
+    /// Increment the change tick, returning the new tick.
+    pub(crate) fn increment_change_tick(rx_world: &mut World) -> u64 {
+        let mut traversal = rx_world.resource_mut::<Self>();
+        traversal.change_tick += 1;
+        traversal.change_tick
+    }
+}
+
+/// Apply a signal `write` and propagate the change through the graph, returning the output of the
+/// write. If a traversal is already in progress, the write is queued and applied once that
+/// traversal has settled instead, and `None` is returned.
+pub(crate) fn send<R>(
+    rx_world: &mut World,
+    write: impl FnOnce(&mut World, &mut Vec<Entity>) -> R + Send + Sync + 'static,
+) -> Option<R> {
+    let mut traversal = rx_world.resource_mut::<RxTraversal>();
+    if traversal.in_progress {
+        traversal.pending.push_back(Box::new(move |world, stack| {
+            write(world, stack);
+        }));
+        return None;
+    }
+    traversal.in_progress = true;
+    traversal.generation += 1;
+    let mut stack = std::mem::take(&mut traversal.scratch);
+
+    let guard = InProgress(rx_world);
+    let output = write(guard.0, &mut stack);
+    stack = propagate(guard.0, stack);
+
+    while let Some(write) = guard.0.resource_mut::<RxTraversal>().pending.pop_front() {
+        write(guard.0, &mut stack);
+        stack = propagate(guard.0, stack);
+    }
+    guard.0.resource_mut::<RxTraversal>().scratch = stack;
+    drop(guard);
+    let traversal = rx_world.resource::<RxTraversal>();
+    let strict = traversal.strict;
+    if traversal.lifecycles {
+        RxLifecycle::settle(rx_world);
+    }
+    if strict {
+        crate::stats::check_strict(rx_world);
+    }
+    Some(output)
+}
+
+/// Ends the traversal when dropped, even if a calculation or callback panicked, so later signals
+/// aren't queued forever behind a traversal that will never settle.
+struct InProgress<'w>(&'w mut World);
+
+impl Drop for InProgress<'_> {
+    fn drop(&mut self) {
+        let mut traversal = self.0.resource_mut::<RxTraversal>();
+        traversal.in_progress = false;
+        traversal.pending.clear();
+    }
+}
+
+/// Recompute every subscriber in `stack`, and any subscribers they in turn invalidate, until the
+/// graph has settled. Returns the emptied stack, so its allocation can be reused.
+fn propagate(rx_world: &mut World, mut stack: Vec<Entity>) -> Vec<Entity> {
+    let mut traversal = rx_world.resource_mut::<RxTraversal>();
+    let stalled = std::mem::take(&mut traversal.stalled);
+    let (mode, deterministic) = (traversal.mode, traversal.deterministic);
+    let mut budget = StepBudget {
+        remaining: traversal.max_steps.unwrap_or(usize::MAX),
+        max_steps: traversal.max_steps,
+    };
+    if deterministic {
+        order_by_creation(rx_world, &mut stack, mode);
+    }
+    stack.splice(0..0, stalled);
+    match mode {
+        Traversal::DepthFirst => {
+            while let Some(subscriber) = stack.pop() {
+                if !budget.step(subscriber) {
+                    stack.push(subscriber);
+                    rx_world.resource_mut::<RxTraversal>().stalled = stack;
+                    return Vec::new();
+                }
+                let len = stack.len();
+                execute(rx_world, subscriber, &mut stack);
+                if deterministic {
+                    order_by_creation(rx_world, &mut stack[len..], mode);
+                }
+            }
+            stack
+        }
+        Traversal::BreadthFirst => {
+            let mut queue = VecDeque::from(stack);
+            let mut invalidated = Vec::new();
+            while let Some(subscriber) = queue.pop_front() {
+                if !budget.step(subscriber) {
+                    queue.push_front(subscriber);
+                    rx_world.resource_mut::<RxTraversal>().stalled = queue.into();
+                    return Vec::new();
+                }
+                execute(rx_world, subscriber, &mut invalidated);
+                if deterministic {
+                    order_by_creation(rx_world, &mut invalidated, mode);
+                }
+                queue.extend(invalidated.drain(..));
+            }
+            queue.into()
+        }
+        Traversal::Parallel => {
+            let mut wave = stack;
+            let mut next = Vec::new();
+            while !wave.is_empty() {
+                let stalled = wave
+                    .iter()
+                    .position(|subscriber| !budget.step(*subscriber))
+                    .map(|i| wave.split_off(i));
+                execute_wave(rx_world, &wave, &mut next);
+                if deterministic {
+                    order_by_creation(rx_world, &mut next, mode);
+                }
+                if let Some(mut stalled) = stalled {
+                    stalled.append(&mut next);
+                    rx_world.resource_mut::<RxTraversal>().stalled = stalled;
+                    return Vec::new();
+                }
+                wave.clear();
+                std::mem::swap(&mut wave, &mut next);
+            }
+            wave
+        }
+    }
+}
+
+/// Sort subscribers so the oldest is recomputed first: last for a stack, first for a queue.
+fn order_by_creation(rx_world: &World, subscribers: &mut [Entity], mode: Traversal) {
+    let creation_index = |subscriber: &Entity| {
+        rx_world
+            .get::<RxSubscribers>(*subscriber)
+            .map_or(u64::MAX, |subscribers| subscribers.creation_index)
+    };
+    match mode {
+        Traversal::DepthFirst => subscribers.sort_by_key(|s| std::cmp::Reverse(creation_index(s))),
+        Traversal::BreadthFirst | Traversal::Parallel => subscribers.sort_by_key(creation_index),
+    }
+}
+
+/// Counts the subscribers recomputed by a traversal, to stop runaway updates.
+struct StepBudget {
+    remaining: usize,
+    max_steps: Option<usize>,
+}
+
+impl StepBudget {
+    /// Returns `false` if the budget is exhausted, and `subscriber` must not be recomputed.
+    fn step(&mut self, subscriber: Entity) -> bool {
+        if self.remaining == 0 {
+            warn!(
+                "Stopped propagating a signal after {} steps, before recomputing {subscriber:?}. \
+                The rest of the graph is stale until the next signal is sent.",
+                self.max_steps.unwrap_or_default()
+            );
+            return false;
+        }
+        self.remaining -= 1;
+        true
+    }
+}
+
+pub(crate) fn execute(rx_world: &mut World, subscriber: Entity, stack: &mut Vec<Entity>) {
+    let Some(memo) = rx_world.get::<RxMemo>(subscriber) else {
+        return;
+    };
+    if !memo.is_idle() {
+        return;
+    }
+    if memo.lazy {
+        RxMemo::invalidate(rx_world, subscriber, stack);
+        return;
+    }
+    if memo.scheduling == MemoScheduling::Scheduled {
+        RxScheduledMemos::mark(rx_world, subscriber);
+        return;
+    }
+    recompute(rx_world, subscriber, stack);
+}
+
+/// Recompute a `wave` of subscribers for [`Traversal::Parallel`], pushing the subscribers they
+/// invalidate onto `next`.
+fn execute_wave(rx_world: &mut World, wave: &[Entity], next: &mut Vec<Entity>) {
+    let mut seen = HashSet::new();
+    let mut shared = Vec::new();
+    for &subscriber in wave {
+        if !seen.insert(subscriber) {
+            continue;
+        }
+        let Some(derive) = RxMemo::shared(rx_world, subscriber) else {
+            execute(rx_world, subscriber, next);
+            continue;
+        };
+        let inputs = rx_world
+            .get::<RxMemo>(subscriber)
+            .map(|memo| memo.inputs.clone())
+            .unwrap_or_default();
+        // A memo with a disposed input is recomputed serially, which handles the input with its
+        // `DisposedInputs` policy.
+        let missing = |input: &Entity| rx_world.get::<RxSubscribers>(*input).is_none();
+        if inputs.iter().any(missing) {
+            execute(rx_world, subscriber, next);
+            continue;
+        }
+        let mut subscribed = true;
+        for input in inputs {
+            subscribed &= RxSubscribers::add(rx_world, input, subscriber);
+        }
+        if !subscribed {
+            execute(rx_world, subscriber, next);
+            continue;
+        }
+        rx_world.resource_mut::<RxTraversal>().counters.recomputes += 1;
+        #[cfg(feature = "tracking")]
+        crate::tracker::RecomputeTracker::memo_recomputed(rx_world, subscriber);
+        shared.push((subscriber, derive));
+    }
+
+    let world: &World = rx_world;
+    let mut writes: Vec<Option<SharedOutput>> = shared.iter().map(|_| None).collect();
+    ComputeTaskPool::get_or_init(TaskPool::default).scope(|scope| {
+        for ((_, derive), write) in shared.iter().zip(&mut writes) {
+            scope.spawn(async move { *write = Some(compute_shared(derive.as_ref(), world)) });
+        }
+    });
+    for ((_subscriber, _), write) in shared.into_iter().zip(writes.into_iter().flatten()) {
+        #[cfg(feature = "profiling")]
+        let (write, elapsed) = write;
+        #[cfg(feature = "profiling")]
+        crate::profiler::NodeProfile::record(rx_world, _subscriber, elapsed);
+        if let Some(write) = write {
+            write(rx_world, next);
+        }
+    }
+}
+
+/// The write that updates a memo computed from a shared world, and how long the calculation took
+/// when profiling.
+#[cfg(not(feature = "profiling"))]
+type SharedOutput = Option<Box<PendingWrite>>;
+#[cfg(feature = "profiling")]
+type SharedOutput = (Option<Box<PendingWrite>>, bevy_utils::Duration);
+
+/// Compute a memo from a shared world, see [`Traversal::Parallel`].
+fn compute_shared(derive: &SharedDeriveFn, world: &World) -> SharedOutput {
+    #[cfg(feature = "profiling")]
+    {
+        let start = bevy_utils::Instant::now();
+        (derive(world), start.elapsed())
+    }
+    #[cfg(not(feature = "profiling"))]
+    derive(world)
+}
+
+/// Run the calculation of the memo on the `subscriber` node, recording it in the counters.
+pub(crate) fn recompute(rx_world: &mut World, subscriber: Entity, stack: &mut Vec<Entity>) {
+    rx_world.resource_mut::<RxTraversal>().counters.recomputes += 1;
+    #[cfg(feature = "tracking")]
+    crate::tracker::RecomputeTracker::memo_recomputed(rx_world, subscriber);
+    #[cfg(feature = "profiling")]
+    let start = bevy_utils::Instant::now();
+    RxMemo::execute_in_place(rx_world, subscriber, stack);
+    #[cfg(feature = "profiling")]
+    crate::profiler::NodeProfile::record(rx_world, subscriber, start.elapsed());
+}
@@ -0,0 +1,8 @@
+//! Checks that the macros reject invalid input with a helpful error. Run with `TRYBUILD=overwrite`
+//! to update the expected errors after changing them.
+
+#[test]
+fn compile_fail() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
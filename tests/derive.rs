@@ -0,0 +1,59 @@
+//! Checks that the derive macros expand to working code when used from outside the crate.
+use bevy_rx::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, Reactive)]
+struct Player {
+    name: String,
+    health: u32,
+}
+
+#[derive(Clone, Copy, MemoQuery)]
+struct HealthInputs {
+    health: Signal<u32>,
+    max_health: Memo<u32>,
+}
+
+#[test]
+fn reactive_round_trip() {
+    let mut reactor = ReactiveContext::<()>::default();
+    let player = Player {
+        name: "Jane".to_string(),
+        health: 100,
+    };
+    let reactive = player.clone().into_reactive(&mut reactor);
+    assert_eq!(reactive.get(&mut reactor), player);
+
+    let label = reactor.new_memo((reactive.name, reactive.health), |(name, health)| {
+        format!("{name}: {health}")
+    });
+    assert!(reactive.set_health(&mut reactor, 80));
+    assert!(!reactive.set_health(&mut reactor, 80));
+    assert_eq!(*reactive.health(&mut reactor), 80);
+    assert_eq!(reactor.read(label), "Jane: 80");
+    assert_eq!(
+        reactive.get(&mut reactor),
+        Player {
+            health: 80,
+            ..player
+        }
+    );
+}
+
+#[test]
+fn memo_query() {
+    let mut reactor = ReactiveContext::<()>::default();
+    let level = reactor.new_signal(1);
+    let inputs = HealthInputs {
+        health: reactor.new_signal(50),
+        max_health: reactor.new_memo((level,), |(level,)| level * 100),
+    };
+    let fraction = reactor.new_memo(inputs, |inputs: HealthInputsQuery| {
+        *inputs.health as f32 / *inputs.max_health as f32
+    });
+    assert_eq!(*reactor.read(fraction), 0.5);
+
+    reactor.send_signal(level, 2);
+    assert_eq!(*reactor.read(fraction), 0.25);
+    reactor.send_signal(inputs.health, 100);
+    assert_eq!(*reactor.read(fraction), 0.5);
+}
@@ -0,0 +1,6 @@
+use bevy_rx::prelude::*;
+
+#[derive(Clone, Copy, MemoQuery)]
+struct NoInputs {}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use bevy_rx::prelude::*;
+
+#[derive(Clone, Copy, MemoQuery)]
+enum Inputs {
+    Health(Signal<u32>),
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use bevy_rx::prelude::*;
+
+#[derive(Reactive)]
+struct Wrapper<T> {
+    value: T,
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use bevy_rx::prelude::*;
+
+#[derive(Reactive)]
+struct Position(f32, f32);
+
+fn main() {}
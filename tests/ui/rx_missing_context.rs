@@ -0,0 +1,7 @@
+use bevy_rx::prelude::*;
+
+fn main() {
+    rx! {
+        let a = signal(1);
+    }
+}
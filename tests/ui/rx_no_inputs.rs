@@ -0,0 +1,10 @@
+use bevy_rx::prelude::*;
+
+fn main() {
+    let mut reactor = ReactiveContext::<()>::default();
+    rx! {
+        reactor;
+        let a = signal(1);
+        let b = memo(format!("{a}"));
+    }
+}